@@ -0,0 +1,23 @@
+use std::path::Path;
+use std::process::Command;
+
+/// With the `bundled-resources` feature, compile `resources/` into a GResource
+/// bundle that `main.rs` embeds, so the binary runs without the directory.
+/// Needs `glib-compile-resources` (part of GLib's development tools).
+fn main() {
+    if std::env::var_os("CARGO_FEATURE_BUNDLED_RESOURCES").is_none() {
+        return;
+    }
+    let out_dir = std::env::var("OUT_DIR").expect("cargo sets OUT_DIR");
+    let target = Path::new(&out_dir).join("toweroops.gresource");
+
+    println!("cargo:rerun-if-changed=toweroops.gresource.xml");
+    println!("cargo:rerun-if-changed=resources");
+    let status = Command::new("glib-compile-resources")
+        .arg("--sourcedir=resources")
+        .arg(format!("--target={}", target.display()))
+        .arg("toweroops.gresource.xml")
+        .status()
+        .expect("glib-compile-resources is needed for the bundled-resources feature");
+    assert!(status.success(), "glib-compile-resources failed");
+}
@@ -1,31 +1,201 @@
 stat-player = Spieler
 stat-computer = Computer
-stat-drawn = Unentschieden
+stat-player-one = Spieler 1
+stat-player-two = Spieler 2
+stat-wins = { $name }: { $count ->
+    [one] { $count } Sieg
+   *[other] { $count } Siege
+}
+stat-draws = { $count } Unentschieden
+stat-streak = Serie: { $streak } (beste: { $best ->
+    [one] { $best } Sieg
+   *[other] { $best } Siege
+})
+stat-turn-player = Sie sind am Zug
+stat-turn-computer = Computer denkt…
+stat-turn-player-one = Spieler 1 ist am Zug
+stat-turn-player-two = Spieler 2 ist am Zug
+stat-result-won = Sie haben gewonnen
+stat-result-lost = Der Computer hat gewonnen
+stat-result-drawn = Unentschieden
+stat-result-player-one = Spieler 1 hat gewonnen
+stat-result-player-two = Spieler 2 hat gewonnen
+stat-result-time = { $result } ({ $time })
+header-score = { $left } { $left_height } – { $right_height } { $right }
+stat-pieces = Steine: { $stones }, Bomben: { $bombs }, Bananen: { $bananas }
+stat-pieces-by-value = Steine +1 / +2 / +3 / +4: { $stones }
+    Bomben −1 / −2 / −3 / −4: { $bombs }
 menu-game = Spiel
 menu-help = Hilfe
 menu-new-game = Neues Spiel
+menu-undo = Zug zurücknehmen
+menu-offer-draw = Remis anbieten
+menu-load-game = Spiel laden…
+menu-copy-position = Stellung kopieren
+menu-paste-position = Stellung einfügen
+menu-daily-puzzle = Tagesrätsel
+menu-race-last-run = Gegen die letzte Partie antreten
 menu-computer-begins = Computer beginnt
 menu-settings = Einstellungen
 menu-hint = Vorschlagszug
+menu-pause-cpu = Vor Computerzug anhalten
+menu-watch-ai = KI gegen KI zuschauen
+menu-zen-mode = Zen-Modus
+zen-hint = Esc drücken für das Menü
+axis-prompt = Zelle anklicken öffnet ihre Spalte – Rechtsklick öffnet ihre Reihe
+banana-axis-prompt = Joker-Banane: Zelle anklicken öffnet ihre Spalte – Rechtsklick öffnet ihre Reihe
+bombs-only-prompt = In dieser Linie liegen nur noch Bomben – die kleinste kostet am wenigsten
 menu-exit = Beenden
 menu-index = Index
+menu-export-stats = Statistik exportieren…
+menu-replay = Züge nachspielen…
+menu-export-replay = Partie als GIF exportieren…
+menu-tutorial = Spielanleitung
 menu-info = Info
 surrender-title = Aufgeben?
 surrender-message = Möchten Sie das laufende Spiel aufgeben?
+draw-offer-title = Remis vereinbaren?
+draw-offer-message = Sind beide Spieler einverstanden, dieses Spiel als Unentschieden zu beenden?
+harmful-move-title = Bombe nehmen?
+harmful-move-message = Diese Bombe senkt Ihren Turm. Trotzdem nehmen?
 help-title = Hilfe
 help-message = Hilf mir!
 settings-title = Einstellungen
 settings-computer = Computer
 settings-level = Schwierigkeit
+settings-adaptive-difficulty = Schwierigkeit an Serien anpassen
+settings-watch-level = Schwierigkeit der KI für Sie bei KI gegen KI
+settings-ai-personality = Spielweise des Computers
+ai-personality-balanced = Ausgewogen
+ai-personality-aggressive = Angriffslustig
+ai-personality-defensive = Vorsichtig
 settings-statistics = Statistik
 settings-reset = Zurücksetzen
+settings-game-mode = Gegner (nächstes Spiel)
+game-mode-vs-computer = Computer
+game-mode-two-player = Zweiter Spieler (abwechselnd am Gerät)
+settings-opener = Wer beginnt
+opener-player = Spieler
+opener-alternate = Abwechselnd
+settings-value-pips = Werte als Punkte anzeigen
+settings-show-values = Werte als Zahlen anzeigen
+settings-show-threats = Züge markieren, mit denen der Gegner gewinnt
+settings-axis-heatmap = Zeilen und Spalten nach ihrem Wert einfärben
+settings-show-coordinates = Koordinaten anzeigen
+settings-fractional-heights = Wachsende Turmhöhen anzeigen
+settings-color-scheme = Farbschema
+settings-board-brightness = Helligkeit des Spielfelds
+settings-board-contrast = Kontrast des Spielfelds
+color-scheme-default = Standard
+color-scheme-deuteranopia = Deuteranopie (Grünschwäche)
+color-scheme-protanopia = Protanopie (Rotschwäche)
+color-scheme-tritanopia = Tritanopie (Blauschwäche)
+settings-player-side = Ihr Turm
+player-side-left = Links
+player-side-right = Rechts
+settings-board-size = Spielfeldgröße (nächstes Spiel)
+settings-balanced-boards = Ausgeglichene Spielfelder (nächstes Spiel)
+settings-advanced-generation = Erweiterte Erzeugung
+settings-gen-bananas = Bananen (%)
+settings-gen-stones = Steine (%)
+settings-gen-bombs = Bomben (%)
+settings-gen-value-weights = Gewichte der Werte 1–4
+settings-gen-hint = Die Anteile müssen zusammen 100 % ergeben, sonst wird die Standardmischung verwendet. Das Tagesrätsel verwendet immer die Standardmischung.
+settings-target-height = Turmhöhe zum Sieg (nächstes Spiel)
+settings-win-rule = Siegregel (nächstes Spiel)
+win-rule-first-to-target = Zuerst die Zielhöhe erreichen
+win-rule-tallest-at-end = Höchster Turm am Ende
+settings-banana-rule = Bananen (nächstes Spiel)
+banana-rule-keep-axis = Achse bleibt
+banana-rule-free-choice = Joker: nächste Achse wählen
+settings-pick-axis = Startreihe oder -spalte selbst wählen
+settings-sound = Soundeffekte
+settings-restore-defaults = Standardwerte wiederherstellen
 settings-animation-speed = Animationsgeschwindigkeit
+settings-animation-speed-unit = Reihen/s
+settings-pulse-duration = Zughervorhebung
+settings-cpu-wait = Pause vor dem Computerzug
+settings-duration-unit = ms
+settings-reduce-motion = Bewegung reduzieren
+settings-confirm-harmful = Vor dem Nehmen einer Bombe nachfragen
+settings-blitz = Blitz: Zeit pro Zug
+settings-blitz-unit = s
 ok = Ok
 cancel = Abbrechen
+retry = Erneut versuchen
+config-warning-title = Einstellungen können nicht gespeichert werden
+config-warning-message = Der Konfigurationsordner konnte nicht angelegt werden oder ist nicht beschreibbar ({ $error }). Sie können weiterspielen, aber Einstellungen und Statistik werden nicht gespeichert.
+config-warning-retry-failed = Weiterhin fehlgeschlagen
+save-failed-path = Der Fortschritt konnte nicht in { $path } gespeichert werden ({ $error }).
+save-failed = Der Fortschritt konnte nicht gespeichert werden ({ $error }).
+level-adapted = Der Computer spielt jetzt auf Stufe { $level }.
+ai-debug-title = KI-Entscheidung
+ai-debug-empty = Der Computer hat noch nicht gezogen.
+ai-debug-searched = { $cell } gewählt nach einer Suche über { $depth } Halbzüge:
+ai-debug-immediate = { $cell } gewählt nach dem unmittelbaren Wert jedes Felds:
+ai-debug-unscored = { $cell } gewählt, ohne andere Felder abzuwägen (Eröffnungsbuch oder nachgespielte Partie).
 close-confirm-title = Spiel beenden?
 close-confirm-message = Ein Spiel läuft noch. Möchten Sie wirklich beenden?
 
 # Application / Info dialog
 app-title = Tower Oops!
 info-body = Tower Oops!\n\nRust/GTK4-Implementierung\nVersion: { $version }\n\nErstellt mit "vibe coding" von Laserlicht
+info-runtime = Systemsprache: { $locale }\nSprache: { $language }\nRessourcen: { $resources }\nBilder: { $svg } SVG, { $raster } PNG
+info-locale-unknown = unbekannt
+info-resources-bundled = im Programm eingebaut
 info-link = <a href="https://www.crazybytes.at/games/games_free_D.htm#towers">Original Crazy Towers</a>
+export-stats-title = Statistik exportieren
+export-stats-save = Speichern
+export-stats-failed = Export fehlgeschlagen
+export-replay-title = Partie exportieren
+export-replay-fps = Bildrate
+export-replay-fps-option = { $fps ->
+    [one] { $fps } Bild pro Sekunde
+   *[other] { $fps } Bilder pro Sekunde
+}
+export-replay-failed = Export der Partie fehlgeschlagen
+position-code-invalid-title = Keine Stellung zum Einfügen
+position-code-invalid = Die Zwischenablage enthält keinen gültigen Stellungscode.
+missing-assets-title = Fehlende Bilder
+missing-assets-body = Einige Bilder konnten nicht aus dem Ressourcenverzeichnis geladen werden. An ihrer Stelle werden Platzhalter angezeigt.
+missing-assets-list = Fehlende Dateien
+replay-title = Wiederholung
+replay-step = Zug { $step } / { $total }
+tutorial-title = Spielanleitung
+tutorial-step = Schritt { $step } / { $total }
+tutorial-goal = Bauen Sie Ihren Turm (links) bis zur Zielhöhe, bevor der Computer seinen Turm (rechts) fertig hat. Abwechselnd wird ein Teil aus der markierten Spalte oder Reihe genommen.
+tutorial-stones = Steine erhöhen Ihren Turm: ein Stein mit dem Wert 3 fügt drei Reihen hinzu.
+tutorial-bombs = Bomben nehmen Reihen von Ihrem eigenen Turm – meiden Sie sie, wenn es geht.
+tutorial-banana = Eine Banane bringt nichts, aber der nächste Zug bleibt in derselben Spalte oder Reihe.
+tutorial-axis = Nach einem Teil aus einer Spalte wird als Nächstes in der Reihe dieses Teils gezogen – nach einem Teil aus einer Reihe in seiner Spalte. Überlegen Sie, was Sie dem Gegner übrig lassen!
+load-game-title = Spiel laden
+load-game-slot = Speicherplatz { $slot }
+load-game-slot-current = Speicherplatz { $slot } (aktuell)
+load-game-details = { $moves } Züge, gespeichert { $date }
+load-game-empty = Leer
+load-game-open = Laden
+load-game-start = Beginnen
+info-level-stats = Statistik je Stufe
+stats-level-line = Stufe { $level }: { $won } gewonnen, { $lost } verloren, { $drawn } unentschieden
+stats-total-time = Gespielte Zeit: { $time }
+stats-fastest-win = Schnellster Sieg: { $time }
+daily-puzzle-title = Tagesrätsel { $date }
+ghost-race-title = Duell mit der letzten Partie
+ghost-race-unavailable = Beenden Sie zuerst eine Runde gegen den Computer. Deren Brett und Ihre Züge spielt der Computer dann nach.
+daily-replay-title = Nochmal spielen?
+daily-replay-message = Sie haben das heutige Rätsel bereits { $attempts }-mal gespielt. Ihr bestes Ergebnis: { $result }. Es zählt nur das beste Ergebnis. Nochmal spielen?
+daily-result-won = mit { $margin } gewonnen
+daily-result-lost = mit { $margin } verloren
+daily-result-drawn = unentschieden
+a11y-board = Spielfeld
+a11y-main-menu = Hauptmenü
+a11y-board-state = Turm { $left } { $left_height }, Turm { $right } { $right_height }, { $turn }, { $axis }.
+a11y-turn-player = Sie sind am Zug
+a11y-turn-computer = der Computer ist am Zug
+a11y-cpu-thinking = Der Computer denkt nach
+a11y-turn-player-one = Spieler 1 ist am Zug
+a11y-turn-player-two = Spieler 2 ist am Zug
+a11y-turn-over = Runde beendet
+a11y-axis-row = Zeile { $index } aktiv
+a11y-axis-column = Spalte { $index } aktiv
+a11y-axis-choose = Zeile oder Spalte wählen
@@ -1,31 +1,204 @@
 stat-player = Player
 stat-computer = Computer
-stat-drawn = Drawn
+stat-player-one = Player 1
+stat-player-two = Player 2
+stat-wins = { $name }: { $count ->
+    [one] { $count } win
+   *[other] { $count } wins
+}
+stat-draws = { $count ->
+    [one] { $count } draw
+   *[other] { $count } draws
+}
+stat-streak = Streak: { $streak } (best: { $best ->
+    [one] { $best } win
+   *[other] { $best } wins
+})
+stat-turn-player = Your turn
+stat-turn-computer = Computer thinking…
+stat-turn-player-one = Player 1's turn
+stat-turn-player-two = Player 2's turn
+stat-result-won = You won
+stat-result-lost = The computer won
+stat-result-drawn = Draw
+stat-result-player-one = Player 1 won
+stat-result-player-two = Player 2 won
+stat-result-time = { $result } ({ $time })
+header-score = { $left } { $left_height } – { $right_height } { $right }
+stat-pieces = Stones: { $stones }, Bombs: { $bombs }, Bananas: { $bananas }
+stat-pieces-by-value = Stones +1 / +2 / +3 / +4: { $stones }
+    Bombs −1 / −2 / −3 / −4: { $bombs }
 menu-game = Game
 menu-help = Help
 menu-new-game = New Game
+menu-undo = Undo Move
+menu-offer-draw = Offer Draw
+menu-load-game = Load Game…
+menu-copy-position = Copy Position
+menu-paste-position = Paste Position
+menu-daily-puzzle = Daily Puzzle
+menu-race-last-run = Race Your Last Game
 menu-computer-begins = Computer Begins
 menu-settings = Settings
 menu-hint = Hint
+menu-pause-cpu = Pause Before Computer Moves
+menu-watch-ai = Watch AI vs AI
+menu-zen-mode = Zen Mode
+zen-hint = Press Esc for menu
+axis-prompt = Click a cell to open its column – right-click to open its row
+banana-axis-prompt = Wildcard banana: click a cell to open its column – right-click to open its row
+bombs-only-prompt = Only bombs left in this line – the smallest one costs least
 menu-exit = Exit
 menu-index = Index
+menu-export-stats = Export Statistics…
+menu-replay = Replay Moves…
+menu-export-replay = Export Replay as GIF…
+menu-tutorial = How to Play
 menu-info = Info
 surrender-title = Resign?
 surrender-message = Do you want to resign the current game?
+draw-offer-title = Agree to a Draw?
+draw-offer-message = Do both players agree to end this game as a draw?
+harmful-move-title = Take the bomb?
+harmful-move-message = This bomb lowers your tower. Take it anyway?
 help-title = Help
 help-message = Help me!
 settings-title = Settings
 settings-computer = Computer
 settings-level = Level
+settings-adaptive-difficulty = Adjust the level to my streaks
+settings-watch-level = Level playing for you in AI vs AI
+settings-ai-personality = Computer style
+ai-personality-balanced = Balanced
+ai-personality-aggressive = Aggressive
+ai-personality-defensive = Defensive
 settings-statistics = Statistics
 settings-reset = Reset
+settings-game-mode = Opponent (next game)
+game-mode-vs-computer = Computer
+game-mode-two-player = Second player (hot seat)
+settings-opener = Who begins
+opener-player = Player
+opener-alternate = Alternate each game
+settings-value-pips = Show values as pips
+settings-show-values = Show values as numbers
+settings-show-threats = Mark moves that let the opponent win
+settings-axis-heatmap = Tint rows and columns by their value
+settings-show-coordinates = Show coordinates
+settings-fractional-heights = Show growing tower heights
+settings-color-scheme = Colour scheme
+settings-board-brightness = Board brightness
+settings-board-contrast = Board contrast
+color-scheme-default = Default
+color-scheme-deuteranopia = Deuteranopia (green-weak)
+color-scheme-protanopia = Protanopia (red-weak)
+color-scheme-tritanopia = Tritanopia (blue-weak)
+settings-player-side = Your tower
+player-side-left = Left
+player-side-right = Right
+settings-board-size = Board size (next game)
+settings-balanced-boards = Balanced boards (next game)
+settings-advanced-generation = Advanced generation
+settings-gen-bananas = Bananas (%)
+settings-gen-stones = Stones (%)
+settings-gen-bombs = Bombs (%)
+settings-gen-value-weights = Weights of the values 1–4
+settings-gen-hint = The shares must add up to 100%, otherwise the default mix is used. The daily puzzle always uses the default mix.
+settings-target-height = Tower height to win (next game)
+settings-win-rule = Win rule (next game)
+win-rule-first-to-target = First to the target height
+win-rule-tallest-at-end = Tallest tower at the end
+settings-banana-rule = Bananas (next game)
+banana-rule-keep-axis = Keep the axis
+banana-rule-free-choice = Wildcard: pick the next axis
+settings-pick-axis = Choose the opening row or column
+settings-sound = Sound effects
+settings-restore-defaults = Restore defaults
 settings-animation-speed = Animation Speed
+settings-animation-speed-unit = rows/s
+settings-pulse-duration = Move highlight
+settings-cpu-wait = Pause before the computer moves
+settings-duration-unit = ms
+settings-reduce-motion = Reduce motion
+settings-confirm-harmful = Ask before taking a bomb
+settings-blitz = Blitz: time per move
+settings-blitz-unit = s
 ok = Ok
 cancel = Cancel
+retry = Retry
+config-warning-title = Settings Can't Be Saved
+config-warning-message = The configuration folder could not be created or is not writable ({ $error }). You can keep playing, but settings and statistics will not be saved.
+config-warning-retry-failed = Still failing
+save-failed-path = Couldn't save progress to { $path } ({ $error }).
+save-failed = Couldn't save progress ({ $error }).
+level-adapted = The computer now plays at level { $level }.
+ai-debug-title = AI decision
+ai-debug-empty = The computer has not moved yet.
+ai-debug-searched = Chose { $cell } after a search of { $depth } plies:
+ai-debug-immediate = Chose { $cell } by the immediate value of each cell:
+ai-debug-unscored = Chose { $cell } without weighing other cells (opening book or a replayed game).
 close-confirm-title = Quit Game?
 close-confirm-message = A game is still in progress. Do you really want to quit?
 
 # Application / Info dialog
 app-title = Tower Oops!
 info-body = Tower Oops!\n\nRust/GTK4 Implementation\nVersion: { $version }\n\nCreated with vibe coding by Laserlicht
+info-runtime = System locale: { $locale }\nLanguage: { $language }\nResources: { $resources }\nImages: { $svg } SVG, { $raster } PNG
+info-locale-unknown = unknown
+info-resources-bundled = built into the program
 info-link = <a href="https://www.crazybytes.at/games/games_free_D.htm#towers">Original Crazy Towers</a>
+export-stats-title = Export statistics
+export-stats-save = Save
+export-stats-failed = Export failed
+export-replay-title = Export replay
+export-replay-fps = Frame rate
+export-replay-fps-option = { $fps ->
+    [one] { $fps } frame per second
+   *[other] { $fps } frames per second
+}
+export-replay-failed = Export of the replay failed
+position-code-invalid-title = No position to paste
+position-code-invalid = The clipboard does not hold a valid position code.
+missing-assets-title = Missing images
+missing-assets-body = Some images could not be loaded from the resources directory. Placeholders are shown in their place.
+missing-assets-list = Missing files
+replay-title = Replay
+replay-step = Move { $step } / { $total }
+tutorial-title = How to Play
+tutorial-step = Step { $step } / { $total }
+tutorial-goal = Build your tower (left) up to the target height before the computer builds its tower (right). You take turns picking one piece from the highlighted column or row.
+tutorial-stones = Stones add to your tower: a stone with value 3 adds three rows.
+tutorial-bombs = Bombs take rows off your own tower, so avoid them when you can.
+tutorial-banana = A banana adds nothing, but the next move stays on the same column or row.
+tutorial-axis = After a piece from a column, the next move has to be made in the row of that piece – and after a piece from a row, in its column. Think about what you leave your opponent!
+load-game-title = Load Game
+load-game-slot = Slot { $slot }
+load-game-slot-current = Slot { $slot } (current)
+load-game-details = { $moves } moves, saved { $date }
+load-game-empty = Empty
+load-game-open = Load
+load-game-start = Start
+info-level-stats = Statistics per level
+stats-level-line = Level { $level }: { $won } won, { $lost } lost, { $drawn } drawn
+stats-total-time = Time played: { $time }
+stats-fastest-win = Fastest win: { $time }
+daily-puzzle-title = Daily Puzzle { $date }
+ghost-race-title = Race Against Your Last Game
+ghost-race-unavailable = Finish a round against the computer first. Its board and your moves are then replayed by the computer.
+daily-replay-title = Play Again?
+daily-replay-message = You have already played today's puzzle { $attempts } times. Your best result: { $result }. Only the best result counts. Play it again?
+daily-result-won = won by { $margin }
+daily-result-lost = lost by { $margin }
+daily-result-drawn = drawn
+a11y-board = Game board
+a11y-main-menu = Main menu
+a11y-board-state = { $left } tower { $left_height }, { $right } tower { $right_height }, { $turn }, { $axis }.
+a11y-turn-player = your move
+a11y-turn-computer = computer's move
+a11y-cpu-thinking = The computer is thinking
+a11y-turn-player-one = Player 1's move
+a11y-turn-player-two = Player 2's move
+a11y-turn-over = round over
+a11y-axis-row = row { $index } active
+a11y-axis-column = column { $index } active
+a11y-axis-choose = choose a row or column
@@ -1,38 +1,339 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::time::{Duration, Instant};
+
+use rand::distributions::{Distribution, WeightedIndex};
 use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::game::field::Board;
+use crate::game::types::{AiPersonality, BananaRule, CellKind, Selection, WinRule};
 
-use crate::game::field::{Board, BOARD_SIZE};
-use crate::game::types::{CellKind, Selection};
+/// 6 AI levels: 0 (random) .. 5 (deep minimax, iterative deepening on a time budget).
+pub const MAX_AI_LEVEL: i32 = 5;
+/// The first level that searches ahead (see [`opening_move`]).
+pub const FIRST_SEARCH_LEVEL: i32 = 3;
 
-/// 5 AI levels: 0 (random) .. 4 (deep minimax).
-pub const MAX_AI_LEVEL: i32 = 4;
+/// Bring `level` into the AI's levels, `0..=MAX_AI_LEVEL`.
+pub fn clamp_level(level: i32) -> i32 {
+    level.clamp(0, MAX_AI_LEVEL)
+}
 
-/// Calculate the best move for the given AI level.
+/// Calculate the best move for the given AI level. Towers grow up to
+/// `target_height`; `win_rule` tells whether reaching it wins at once and
+/// `banana_rule` whether a banana lets its taker pick the next axis (see
+/// [`banana_axis`]). `personality` only affects the searching levels.
 /// Returns (col, row).
+#[allow(clippy::too_many_arguments)]
 pub fn calculate_move(
     level: i32,
+    personality: AiPersonality,
     board: &Board,
     selection: Selection,
     tower_self: i32,
     tower_opponent: i32,
+    target_height: i32,
+    win_rule: WinRule,
+    banana_rule: BananaRule,
 ) -> (usize, usize) {
+    calculate_move_scored(
+        level,
+        personality,
+        board,
+        selection,
+        tower_self,
+        tower_opponent,
+        target_height,
+        win_rule,
+        banana_rule,
+    )
+    .0
+}
+
+/// Like [`calculate_move`], but also returns the score of the chosen move from
+/// the mover's point of view. Minimax levels report the search score; levels
+/// without a search report the immediate `cell_value` of the chosen cell.
+#[allow(clippy::too_many_arguments)]
+pub fn calculate_move_scored(
+    level: i32,
+    personality: AiPersonality,
+    board: &Board,
+    selection: Selection,
+    tower_self: i32,
+    tower_opponent: i32,
+    target_height: i32,
+    win_rule: WinRule,
+    banana_rule: BananaRule,
+) -> ((usize, usize), i32) {
+    let mut rng = rand::thread_rng();
+    calculate_move_with_rng(
+        level,
+        personality,
+        board,
+        selection,
+        tower_self,
+        tower_opponent,
+        target_height,
+        win_rule,
+        banana_rule,
+        &mut rng,
+    )
+}
+
+/// Like [`calculate_move_scored`], but draws all randomness (levels 0 and 1, tie
+/// breaks) from `rng`, so a seeded generator gives repeatable moves. The
+/// deepest level still depends on its time budget.
+#[allow(clippy::too_many_arguments)]
+pub fn calculate_move_with_rng(
+    level: i32,
+    personality: AiPersonality,
+    board: &Board,
+    selection: Selection,
+    tower_self: i32,
+    tower_opponent: i32,
+    target_height: i32,
+    win_rule: WinRule,
+    banana_rule: BananaRule,
+    rng: &mut impl Rng,
+) -> ((usize, usize), i32) {
+    let decision = decide_move(
+        level,
+        personality,
+        board,
+        selection,
+        tower_self,
+        tower_opponent,
+        target_height,
+        win_rule,
+        banana_rule,
+        rng,
+    );
+    (decision.chosen, decision.score)
+}
+
+/// How the AI arrived at a move: the score of every candidate of the active
+/// selection, best first, and the search depth behind them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MoveDecision {
+    pub chosen: (usize, usize),
+    /// The score of `chosen`, as [`calculate_move_scored`] reports it.
+    pub score: i32,
+    /// The plies searched, `None` for the levels without a search and for
+    /// moves that were not searched at all.
+    pub depth: Option<i32>,
+    /// The scored candidates, best first; equal scores keep their board
+    /// order. A search that found an instant win stops listing there.
+    pub candidates: Vec<((usize, usize), i32)>,
+    /// The axis to hand over if `chosen` takes a wildcard banana, as the
+    /// search settled it; `None` leaves the choice to [`banana_axis`].
+    pub axis: Option<Selection>,
+}
+
+impl MoveDecision {
+    /// A move that was not weighed against others, e.g. from the opening book.
+    pub fn unscored(chosen: (usize, usize)) -> Self {
+        Self {
+            chosen,
+            score: 0,
+            depth: None,
+            candidates: Vec::new(),
+            axis: None,
+        }
+    }
+}
+
+/// How far a running search has got, see [`decide_move_with_progress`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchProgress {
+    /// A fixed-depth search has scored `done` of the `total` root moves.
+    RootMoves { done: usize, total: usize },
+    /// Iterative deepening started searching this many plies.
+    Depth(i32),
+}
+
+/// Like [`calculate_move_with_rng`], but keeps the scores of all candidates.
+#[allow(clippy::too_many_arguments)]
+pub fn decide_move(
+    level: i32,
+    personality: AiPersonality,
+    board: &Board,
+    selection: Selection,
+    tower_self: i32,
+    tower_opponent: i32,
+    target_height: i32,
+    win_rule: WinRule,
+    banana_rule: BananaRule,
+    rng: &mut impl Rng,
+) -> MoveDecision {
+    decide_move_with_progress(
+        level,
+        personality,
+        board,
+        selection,
+        tower_self,
+        tower_opponent,
+        target_height,
+        win_rule,
+        banana_rule,
+        rng,
+        &mut |_| {},
+    )
+}
+
+/// Like [`decide_move`], but the searching levels report their progress to
+/// `progress` as they go: the fixed-depth levels after every root move,
+/// the deepest level whenever it starts a deeper iteration.
+#[allow(clippy::too_many_arguments)]
+pub fn decide_move_with_progress(
+    level: i32,
+    personality: AiPersonality,
+    board: &Board,
+    selection: Selection,
+    tower_self: i32,
+    tower_opponent: i32,
+    target_height: i32,
+    win_rule: WinRule,
+    banana_rule: BananaRule,
+    rng: &mut impl Rng,
+    progress: &mut dyn FnMut(SearchProgress),
+) -> MoveDecision {
+    let with_cell_values = |chosen: (usize, usize)| {
+        let mut candidates: Vec<_> = (0..board.size())
+            .map(|i| sel_coords(selection, i))
+            .filter_map(|(col, row)| {
+                let cell = board.get(col, row);
+                (cell.kind != CellKind::Empty)
+                    .then(|| ((col, row), cell_value(cell.kind, cell.value)))
+            })
+            .collect();
+        candidates.sort_by_key(|&(_, score)| std::cmp::Reverse(score));
+        let cell = board.get(chosen.0, chosen.1);
+        MoveDecision {
+            chosen,
+            score: cell_value(cell.kind, cell.value),
+            depth: None,
+            candidates,
+            axis: None,
+        }
+    };
+    let root = || SearchState {
+        board: board.clone(),
+        selection,
+        tower_me: tower_self,
+        tower_opp: tower_opponent,
+        target: target_height,
+        win_rule,
+        banana_rule,
+        personality,
+    };
     match level {
-        0 => random_move(board, selection),
-        1 => greedy_move(board, selection),
-        2 => minimax_move(board, selection, tower_self, tower_opponent, 2),
-        3 => minimax_move(board, selection, tower_self, tower_opponent, 4),
-        4 => minimax_move(board, selection, tower_self, tower_opponent, 8),
-        _ => minimax_move(board, selection, tower_self, tower_opponent, 8),
+        0 => with_cell_values(random_move(board, selection, rng)),
+        1 => with_cell_values(weighted_random_move(
+            board,
+            selection,
+            WEIGHTED_RANDOM_TEMPERATURE,
+            rng,
+        )),
+        2 => with_cell_values(greedy_move(board, selection, rng)),
+        3 => fixed_depth_move(&root(), 2, rng, progress),
+        4 => fixed_depth_move(&root(), 4, rng, progress),
+        _ => iterative_deepening_move(&root(), DEEPEST_LEVEL_BUDGET, rng, progress),
+    }
+}
+
+/// Score every move of the active selection with a minimax search of `depth`
+/// plies, from the mover's point of view. Returns the moves best-first; equal
+/// scores keep their board order.
+#[allow(clippy::too_many_arguments)]
+pub fn analyze_moves(
+    board: &Board,
+    selection: Selection,
+    tower_self: i32,
+    tower_opponent: i32,
+    target_height: i32,
+    win_rule: WinRule,
+    banana_rule: BananaRule,
+    depth: i32,
+) -> Vec<((usize, usize), i32)> {
+    let root = SearchState {
+        board: board.clone(),
+        selection,
+        tower_me: tower_self,
+        tower_opp: tower_opponent,
+        target: target_height,
+        win_rule,
+        banana_rule,
+        personality: AiPersonality::Balanced,
+    };
+    let mut search = Search::new(SearchOptions::default());
+    let mut scored: Vec<_> = (0..board.size())
+        .map(|i| sel_coords(selection, i))
+        .filter(|&(col, row)| board.get(col, row).kind != CellKind::Empty)
+        .map(|(col, row)| {
+            let score = successors(&root, col, row, true)
+                .into_iter()
+                .map(|(_, child)| {
+                    if child.reaches_target(child.tower_me) {
+                        win_score(&child.board)
+                    } else {
+                        -negamax(
+                            &child,
+                            depth - 1,
+                            -INFINITY,
+                            INFINITY,
+                            false,
+                            None,
+                            &mut search,
+                        )
+                        .expect("search without a deadline always completes")
+                    }
+                })
+                .max()
+                .expect("every move has a successor");
+            ((col, row), score)
+        })
+        .collect();
+    scored.sort_by_key(|&(_, score)| std::cmp::Reverse(score));
+    scored
+}
+
+/// Opening book for the searching levels, for the first move of a round only:
+/// a search cannot tell the many similar moves of a full board apart anyway.
+/// Takes one of the most valuable cells of the active selection, preferring
+/// cells near the middle of the axis.
+pub fn opening_move(board: &Board, selection: Selection, rng: &mut impl Rng) -> (usize, usize) {
+    let center = (board.size() - 1) as f64 / 2.0;
+    let mut best_key = None;
+    let mut best_candidates = Vec::new();
+    for i in 0..board.size() {
+        let (col, row) = sel_coords(selection, i);
+        let cell = board.get(col, row);
+        if cell.kind == CellKind::Empty {
+            continue;
+        }
+        // Twice the distance to the middle keeps the key integral.
+        let off_center = (2.0 * (i as f64 - center)).abs() as i32;
+        let key = (cell_value(cell.kind, cell.value), -off_center);
+        if best_key.is_none_or(|best| key > best) {
+            best_key = Some(key);
+            best_candidates.clear();
+            best_candidates.push(i);
+        } else if Some(key) == best_key {
+            best_candidates.push(i);
+        }
     }
+    let &idx = best_candidates.choose(rng).unwrap_or(&0);
+    sel_coords(selection, idx)
 }
 
 // ════════════════════════════════════════════════════════════════════════════
 // Level 0 – Random
 // ════════════════════════════════════════════════════════════════════════════
 
-fn random_move(board: &Board, selection: Selection) -> (usize, usize) {
-    let mut rng = rand::thread_rng();
-    let mut candidates: Vec<usize> = (0..BOARD_SIZE).collect();
-    candidates.shuffle(&mut rng);
+fn random_move(board: &Board, selection: Selection, rng: &mut impl Rng) -> (usize, usize) {
+    let mut candidates: Vec<usize> = (0..board.size()).collect();
+    candidates.shuffle(rng);
 
     for &i in &candidates {
         let (col, row) = sel_coords(selection, i);
@@ -44,14 +345,50 @@ fn random_move(board: &Board, selection: Selection) -> (usize, usize) {
 }
 
 // ════════════════════════════════════════════════════════════════════════════
-// Level 1 – Greedy (pick best immediate value)
+// Level 1 – Weighted random (prefer good cells, still blunder now and then)
 // ════════════════════════════════════════════════════════════════════════════
 
-fn greedy_move(board: &Board, selection: Selection) -> (usize, usize) {
+/// Softmax temperature of level 1, in `cell_value` units. Lower values play
+/// closer to greedy, higher values closer to random.
+const WEIGHTED_RANDOM_TEMPERATURE: f64 = 15.0;
+
+/// Pick a non-empty cell with a probability growing with its `cell_value`
+/// (softmax at `temperature`).
+fn weighted_random_move(
+    board: &Board,
+    selection: Selection,
+    temperature: f64,
+    rng: &mut impl Rng,
+) -> (usize, usize) {
+    let candidates: Vec<((usize, usize), i32)> = (0..board.size())
+        .map(|i| sel_coords(selection, i))
+        .filter_map(|(col, row)| {
+            let cell = board.get(col, row);
+            (cell.kind != CellKind::Empty).then(|| ((col, row), cell_value(cell.kind, cell.value)))
+        })
+        .collect();
+    // Shifting by the best value keeps the exponentials in range.
+    let Some(best) = candidates.iter().map(|&(_, value)| value).max() else {
+        return sel_coords(selection, 0);
+    };
+    let weights = candidates
+        .iter()
+        .map(|&(_, value)| ((value - best) as f64 / temperature).exp());
+    match WeightedIndex::new(weights) {
+        Ok(dist) => candidates[dist.sample(rng)].0,
+        Err(_) => candidates[0].0,
+    }
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// Level 2 – Greedy (pick best immediate value)
+// ════════════════════════════════════════════════════════════════════════════
+
+fn greedy_move(board: &Board, selection: Selection, rng: &mut impl Rng) -> (usize, usize) {
     let mut best_score = i32::MIN;
     let mut best_candidates = Vec::new();
 
-    for i in 0..BOARD_SIZE {
+    for i in 0..board.size() {
         let (col, row) = sel_coords(selection, i);
         let cell = board.get(col, row);
         if cell.kind == CellKind::Empty {
@@ -67,16 +404,22 @@ fn greedy_move(board: &Board, selection: Selection) -> (usize, usize) {
         }
     }
 
-    let mut rng = rand::thread_rng();
-    let &idx = best_candidates.choose(&mut rng).unwrap_or(&0);
+    let &idx = best_candidates.choose(rng).unwrap_or(&0);
     sel_coords(selection, idx)
 }
 
 // ════════════════════════════════════════════════════════════════════════════
-// Levels 2–4 – Minimax with Alpha-Beta Pruning
+// Levels 3–5 – Minimax with Alpha-Beta Pruning
 // ════════════════════════════════════════════════════════════════════════════
 
-const MAX_TOWER: i32 = 20;
+/// Below this many pieces on the board, positions are solved exactly.
+const EXACT_SOLVE_PIECES: usize = 10;
+
+/// Wall-clock budget for the deepest level's iterative deepening.
+const DEEPEST_LEVEL_BUDGET: Duration = Duration::from_millis(250);
+
+/// Bound of the search window; unlike `i32::MIN` it can be negated.
+const INFINITY: i32 = i32::MAX;
 
 /// State used during minimax search (to avoid cloning Board repeatedly).
 #[derive(Clone)]
@@ -85,44 +428,362 @@ struct SearchState {
     selection: Selection,
     tower_me: i32,  // the AI player ("maximizer")
     tower_opp: i32, // the human player ("minimizer")
+    target: i32,    // tower height that wins (or caps, see `win_rule`)
+    win_rule: WinRule,
+    banana_rule: BananaRule,
+    personality: AiPersonality,
 }
 
-fn minimax_move(
-    board: &Board,
-    selection: Selection,
-    tower_self: i32,
-    tower_opponent: i32,
+impl SearchState {
+    /// A tower of this height ends the round at once.
+    fn reaches_target(&self, tower: i32) -> bool {
+        self.win_rule == WinRule::FirstToTarget && tower >= self.target
+    }
+}
+
+/// How a stored score relates to the true minimax value of a position.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Bound {
+    /// The score is the exact value.
+    Exact,
+    /// The search failed high – the true value is at least the score.
+    Lower,
+    /// The search failed low – the true value is at most the score.
+    Upper,
+}
+
+#[derive(Clone, Copy)]
+struct TtEntry {
     depth: i32,
-) -> (usize, usize) {
-    let mut rng = rand::thread_rng();
+    score: i32,
+    bound: Bound,
+}
 
-    let state = SearchState {
-        board: board.clone(),
-        selection,
-        tower_me: tower_self,
-        tower_opp: tower_opponent,
+/// Upper limit on stored positions so a deep search cannot exhaust memory.
+const TT_MAX_ENTRIES: usize = 1 << 19;
+
+/// Remembers already searched positions so that transpositions (the same
+/// position reached by a different move order) are not searched again.
+struct TranspositionTable {
+    entries: HashMap<u64, TtEntry>,
+    /// Exact values from [`negamax_exact`]; they hold at any depth.
+    exact: HashMap<u64, i32>,
+}
+
+impl TranspositionTable {
+    fn new() -> Self {
+        Self {
+            entries: HashMap::new(),
+            exact: HashMap::new(),
+        }
+    }
+
+    fn key(state: &SearchState, ai_to_move: bool) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        state.board.hash(&mut hasher);
+        state.selection.hash(&mut hasher);
+        state.tower_me.hash(&mut hasher);
+        state.tower_opp.hash(&mut hasher);
+        state.target.hash(&mut hasher);
+        ai_to_move.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Only entries searched to exactly `depth` are used: scores depend on the
+    /// remaining depth (faster wins score higher), so a deeper entry would make
+    /// the result differ from a plain search.
+    fn probe(&self, key: u64, depth: i32) -> Option<TtEntry> {
+        self.entries.get(&key).copied().filter(|e| e.depth == depth)
+    }
+
+    fn store(&mut self, key: u64, entry: TtEntry) {
+        if self.entries.len() < TT_MAX_ENTRIES || self.entries.contains_key(&key) {
+            self.entries.insert(key, entry);
+        }
+    }
+
+    fn store_exact(&mut self, key: u64, score: i32) {
+        if self.exact.len() < TT_MAX_ENTRIES {
+            self.exact.insert(key, score);
+        }
+    }
+}
+
+/// Shortcuts of the search. They never change a result, only how much is
+/// searched, so both are always on outside of tests that compare against them.
+#[derive(Clone, Copy)]
+struct SearchOptions {
+    /// Search the most promising moves first (see [`ordered_moves`]).
+    move_ordering: bool,
+    /// Search one root move of each symmetric class (see [`root_representatives`]).
+    root_symmetry: bool,
+}
+
+impl Default for SearchOptions {
+    fn default() -> Self {
+        Self {
+            move_ordering: true,
+            root_symmetry: true,
+        }
+    }
+}
+
+/// What one search carries from node to node.
+struct Search {
+    table: TranspositionTable,
+    options: SearchOptions,
+    /// Number of `negamax` calls so far, a measure of the search effort.
+    nodes: u64,
+}
+
+impl Search {
+    fn new(options: SearchOptions) -> Self {
+        Self {
+            table: TranspositionTable::new(),
+            options,
+            nodes: 0,
+        }
+    }
+
+    /// The moves of the active selection in the order the options ask for.
+    fn moves(&self, state: &SearchState) -> Vec<(usize, usize)> {
+        if self.options.move_ordering {
+            ordered_moves(state)
+        } else {
+            selection_moves(state)
+        }
+    }
+}
+
+/// One of the eight symmetries of the square board: an optional swap of
+/// columns and rows, then optional mirroring of either.
+#[derive(Clone, Copy)]
+struct Symmetry {
+    transpose: bool,
+    mirror_cols: bool,
+    mirror_rows: bool,
+}
+
+impl Symmetry {
+    fn all() -> impl Iterator<Item = Self> {
+        (0..8).map(|bits| Self {
+            transpose: bits & 1 != 0,
+            mirror_cols: bits & 2 != 0,
+            mirror_rows: bits & 4 != 0,
+        })
+    }
+
+    fn apply(self, size: usize, (col, row): (usize, usize)) -> (usize, usize) {
+        let (col, row) = if self.transpose {
+            (row, col)
+        } else {
+            (col, row)
+        };
+        let col = if self.mirror_cols {
+            size - 1 - col
+        } else {
+            col
+        };
+        let row = if self.mirror_rows {
+            size - 1 - row
+        } else {
+            row
+        };
+        (col, row)
+    }
+}
+
+/// For every root move (by index along the active selection) the first move
+/// it is equivalent to: a symmetry that maps the board and the selection onto
+/// themselves maps each move onto one that scores the same, so only the first
+/// of each class needs a search. Wildcard bananas break ties between axes by
+/// their position, so under that rule every move stands alone.
+fn root_representatives(state: &SearchState) -> Vec<usize> {
+    let (board, selection) = (&state.board, state.selection);
+    let size = board.size();
+    let mut representatives: Vec<usize> = (0..size).collect();
+    if state.banana_rule == BananaRule::FreeChoice {
+        return representatives;
+    }
+    let cells = || (0..size).flat_map(move |col| (0..size).map(move |row| (col, row)));
+    let index_on_selection = |(col, row)| match selection {
+        Selection::Row(r) => (row == r).then_some(col),
+        Selection::Column(c) => (col == c).then_some(row),
     };
+    for symmetry in Symmetry::all().skip(1) {
+        let keeps_board = cells().all(|(col, row)| {
+            let (image_col, image_row) = symmetry.apply(size, (col, row));
+            let (a, b) = (board.get(col, row), board.get(image_col, image_row));
+            (a.kind, a.value) == (b.kind, b.value)
+        });
+        if !keeps_board {
+            continue;
+        }
+        let images: Option<Vec<usize>> = (0..size)
+            .map(|i| index_on_selection(symmetry.apply(size, sel_coords(selection, i))))
+            .collect();
+        if let Some(images) = images {
+            for (representative, image) in representatives.iter_mut().zip(images) {
+                *representative = (*representative).min(image);
+            }
+        }
+    }
+    representatives
+}
+
+/// The non-empty cells of the active selection, in board order.
+fn selection_moves(state: &SearchState) -> Vec<(usize, usize)> {
+    (0..state.board.size())
+        .map(|i| sel_coords(state.selection, i))
+        .filter(|&(col, row)| state.board.get(col, row).kind != CellKind::Empty)
+        .collect()
+}
+
+/// The non-empty cells of the active selection, most promising first.
+/// `cell_value` is from the picker's point of view, so sorting it in
+/// descending order puts the best moves first for both sides, which lets
+/// alpha-beta cut off the remaining moves early.
+fn ordered_moves(state: &SearchState) -> Vec<(usize, usize)> {
+    let mut moves = selection_moves(state);
+    // Stable sort: equal values keep their index order.
+    moves.sort_by_key(|&(col, row)| {
+        let cell = state.board.get(col, row);
+        std::cmp::Reverse(cell_value(cell.kind, cell.value))
+    });
+    moves
+}
+
+/// Search to a fixed depth regardless of how long it takes.
+fn fixed_depth_move(
+    root: &SearchState,
+    depth: i32,
+    rng: &mut impl Rng,
+    progress: &mut dyn FnMut(SearchProgress),
+) -> MoveDecision {
+    let mut search = Search::new(SearchOptions::default());
+    minimax_move(root, depth, None, &mut search, rng, progress)
+        .expect("search without a deadline always completes")
+}
+
+/// Deepen the search one ply at a time until `budget` is used up and return the
+/// best move of the last fully completed depth. Keeps the response time steady:
+/// crowded boards stop early, sparse endgames are searched to the end.
+fn iterative_deepening_move(
+    root: &SearchState,
+    budget: Duration,
+    rng: &mut impl Rng,
+    progress: &mut dyn FnMut(SearchProgress),
+) -> MoveDecision {
+    let deadline = Instant::now() + budget;
+    // Depth 1 is cheap and always completes, so there is always a move to play.
+    progress(SearchProgress::Depth(1));
+    let mut best = fixed_depth_move(root, 1, rng, &mut |_| {});
+    let board = &root.board;
+
+    // The game cannot last longer than the number of remaining pieces.
+    let remaining = remaining_pieces(board) as i32;
+
+    for depth in 2..=remaining {
+        if Instant::now() >= deadline {
+            break;
+        }
+        progress(SearchProgress::Depth(depth));
+        let mut search = Search::new(SearchOptions::default());
+        match minimax_move(root, depth, Some(deadline), &mut search, rng, &mut |_| {}) {
+            Some(result) => best = result,
+            None => break, // ran out of time mid-depth – keep the previous result
+        }
+    }
+    best
+}
+
+/// Search every move of the active selection to `depth` plies, reporting
+/// each scored move to `progress`.
+/// Returns `None` if `deadline` passed before the search completed.
+fn minimax_move(
+    state: &SearchState,
+    depth: i32,
+    deadline: Option<Instant>,
+    search: &mut Search,
+    rng: &mut impl Rng,
+    progress: &mut dyn FnMut(SearchProgress),
+) -> Option<MoveDecision> {
+    let (board, selection) = (&state.board, state.selection);
 
     let mut best_score = i32::MIN;
     let mut best_candidates = Vec::new();
+    let mut scored = Vec::new();
+    let decision = |chosen, score, mut candidates: Vec<_>, axis| {
+        candidates.sort_by_key(|&(_, score): &((usize, usize), i32)| std::cmp::Reverse(score));
+        MoveDecision {
+            chosen,
+            score,
+            depth: Some(depth),
+            candidates,
+            axis,
+        }
+    };
+    let representatives = if search.options.root_symmetry {
+        root_representatives(state)
+    } else {
+        (0..board.size()).collect()
+    };
+    let mut scores_by_index = vec![None; board.size()];
+    let total = (0..board.size())
+        .filter(|&i| {
+            let (col, row) = sel_coords(selection, i);
+            board.get(col, row).kind != CellKind::Empty
+        })
+        .count();
+    progress(SearchProgress::RootMoves { done: 0, total });
 
     // Evaluate all possible moves
-    for i in 0..BOARD_SIZE {
+    for i in 0..board.size() {
         let (col, row) = sel_coords(selection, i);
         let cell = *board.get(col, row);
         if cell.kind == CellKind::Empty {
             continue;
         }
 
-        let mut child = state.clone();
-        apply_move_to(&mut child, col, row, true); // true = AI's move (maximizer)
-
-        // Check for immediate terminal state
-        if child.tower_me >= MAX_TOWER {
-            return (col, row); // instant win – take it
-        }
+        // A mirror image of an earlier move scores the same. After a wildcard
+        // banana the move scores as well as the best axis it can hand over.
+        let (score, axis) = match scores_by_index[representatives[i]] {
+            Some(scored) => scored,
+            None => {
+                let mut best = (i32::MIN, None);
+                // true = AI's move (maximizer)
+                for (axis, child) in successors(state, col, row, true) {
+                    // Check for immediate terminal state
+                    if child.reaches_target(child.tower_me) {
+                        // Instant win – take it.
+                        let score = win_score(&child.board);
+                        scored.push(((col, row), score));
+                        return Some(decision((col, row), score, scored, axis));
+                    }
 
-        let score = minimax(&child, depth - 1, i32::MIN, i32::MAX, false);
+                    // The opponent moves next; its score is negated for the AI.
+                    let score = -negamax(
+                        &child,
+                        depth - 1,
+                        -INFINITY,
+                        INFINITY,
+                        false,
+                        deadline,
+                        search,
+                    )?;
+                    if score > best.0 {
+                        best = (score, axis);
+                    }
+                }
+                best
+            }
+        };
+        scores_by_index[i] = Some((score, axis));
+        scored.push(((col, row), score));
+        progress(SearchProgress::RootMoves {
+            done: scored.len(),
+            total,
+        });
 
         if score > best_score {
             best_score = score;
@@ -133,92 +794,205 @@ fn minimax_move(
         }
     }
 
-    let &idx = best_candidates.choose(&mut rng).unwrap_or(&0);
-    sel_coords(selection, idx)
+    let &idx = best_candidates.choose(rng).unwrap_or(&0);
+    let axis = scores_by_index[idx].and_then(|(_, axis)| axis);
+    Some(decision(
+        sel_coords(selection, idx),
+        best_score,
+        scored,
+        axis,
+    ))
 }
 
-/// Minimax with alpha-beta pruning.
-/// `maximizing` = true means it's the AI's turn, false = opponent's turn.
-fn minimax(
+/// Negamax with alpha-beta pruning: every node maximizes from the point of view
+/// of the side to move, so the child's score is negated and the window swapped
+/// on recursion. `ai_to_move` flips with every ply and picks whose tower a move
+/// builds. Returns `None` if `deadline` passed before the search completed.
+fn negamax(
     state: &SearchState,
     depth: i32,
     mut alpha: i32,
     mut beta: i32,
-    maximizing: bool,
-) -> i32 {
+    ai_to_move: bool,
+    deadline: Option<Instant>,
+    search: &mut Search,
+) -> Option<i32> {
+    search.nodes += 1;
+    if deadline.is_some_and(|d| Instant::now() >= d) {
+        return None;
+    }
+
+    // Scores below are from the AI's point of view; flip them for the opponent.
+    let sign = if ai_to_move { 1 } else { -1 };
+
     // Terminal conditions
-    if state.tower_me >= MAX_TOWER {
-        return 10000 + depth; // AI wins – prefer faster wins
+    if state.reaches_target(state.tower_me) {
+        return Some(sign * win_score(&state.board)); // AI wins – prefer faster wins
+    }
+    if state.reaches_target(state.tower_opp) {
+        return Some(-sign * win_score(&state.board)); // opponent wins
     }
-    if state.tower_opp >= MAX_TOWER {
-        return -10000 - depth; // opponent wins
+
+    // No move left that can change a tower (empty or banana-only axis)
+    if state.board.round_over(state.selection, state.banana_rule) {
+        return Some(sign * evaluate_final(state));
     }
 
-    // Check if selection is exhausted (no moves available)
-    if state.board.selection_exhausted(state.selection) {
-        return evaluate_final(state);
+    if remaining_pieces(&state.board) < EXACT_SOLVE_PIECES {
+        return Some(sign * solve_exact(state, ai_to_move, search));
     }
 
     if depth <= 0 {
-        return evaluate(state);
+        return Some(sign * evaluate_leaf(state, ai_to_move));
     }
 
-    if maximizing {
-        let mut best = i32::MIN;
-        for i in 0..BOARD_SIZE {
-            let (col, row) = sel_coords(state.selection, i);
-            let cell = *state.board.get(col, row);
-            if cell.kind == CellKind::Empty {
-                continue;
-            }
+    let key = TranspositionTable::key(state, ai_to_move);
+    let (alpha_orig, beta_orig) = (alpha, beta);
+    if let Some(entry) = search.table.probe(key, depth) {
+        match entry.bound {
+            Bound::Exact => return Some(entry.score),
+            Bound::Lower => alpha = alpha.max(entry.score),
+            Bound::Upper => beta = beta.min(entry.score),
+        }
+        if alpha >= beta {
+            return Some(entry.score);
+        }
+    }
 
-            let mut child = state.clone();
-            apply_move_to(&mut child, col, row, true);
-            let score = minimax(&child, depth - 1, alpha, beta, false);
+    let mut best = i32::MIN;
+    'moves: for (col, row) in search.moves(state) {
+        for (_, child) in successors(state, col, row, ai_to_move) {
+            let score = -negamax(
+                &child,
+                depth - 1,
+                -beta,
+                -alpha,
+                !ai_to_move,
+                deadline,
+                search,
+            )?;
 
             best = best.max(score);
             alpha = alpha.max(score);
             if alpha >= beta {
-                break; // beta cutoff
+                break 'moves; // cutoff
             }
         }
-        if best == i32::MIN {
-            evaluate_final(state) // no moves available
-        } else {
-            best
-        }
+    }
+    if best == i32::MIN {
+        best = sign * evaluate_final(state); // no moves available
+    }
+
+    let bound = if best <= alpha_orig {
+        Bound::Upper
+    } else if best >= beta_orig {
+        Bound::Lower
     } else {
-        let mut best = i32::MAX;
-        for i in 0..BOARD_SIZE {
-            let (col, row) = sel_coords(state.selection, i);
-            let cell = *state.board.get(col, row);
-            if cell.kind == CellKind::Empty {
-                continue;
-            }
+        Bound::Exact
+    };
+    search.table.store(
+        key,
+        TtEntry {
+            depth,
+            score: best,
+            bound,
+        },
+    );
+    Some(best)
+}
 
-            let mut child = state.clone();
-            apply_move_to(&mut child, col, row, false);
-            let score = minimax(&child, depth - 1, alpha, beta, true);
+/// Exact value of a position (from the AI's point of view, like
+/// [`evaluate_leaf`]), found by searching every line to the end of the round.
+/// Only affordable with few pieces left; the heuristic evaluation is never
+/// used. Faster wins score higher, see [`win_score`].
+fn solve_exact(state: &SearchState, ai_to_move: bool, search: &mut Search) -> i32 {
+    let sign = if ai_to_move { 1 } else { -1 };
+    sign * negamax_exact(state, ai_to_move, search)
+}
 
-            best = best.min(score);
-            beta = beta.min(score);
-            if alpha >= beta {
-                break; // alpha cutoff
-            }
-        }
-        if best == i32::MAX {
-            evaluate_final(state)
-        } else {
-            best
+/// Unpruned negamax to the end of the round, from the point of view of the side
+/// to move. Results are cached in the search's table; see [`solve_exact`].
+fn negamax_exact(state: &SearchState, ai_to_move: bool, search: &mut Search) -> i32 {
+    // Scores below are from the AI's point of view; flip them for the opponent.
+    let sign = if ai_to_move { 1 } else { -1 };
+    if state.reaches_target(state.tower_me) {
+        return sign * win_score(&state.board);
+    }
+    if state.reaches_target(state.tower_opp) {
+        return -sign * win_score(&state.board);
+    }
+    if state.board.round_over(state.selection, state.banana_rule) {
+        return sign * evaluate_final(state);
+    }
+
+    let key = TranspositionTable::key(state, ai_to_move);
+    if let Some(&score) = search.table.exact.get(&key) {
+        return score;
+    }
+    let mut best = i32::MIN;
+    for (col, row) in search.moves(state) {
+        for (_, child) in successors(state, col, row, ai_to_move) {
+            best = best.max(-negamax_exact(&child, !ai_to_move, search));
         }
     }
+    search.table.store_exact(key, best);
+    best
 }
 
-/// Apply a move to a SearchState, modifying it in place.
-fn apply_move_to(state: &mut SearchState, col: usize, row: usize, is_maximizer: bool) {
+/// Score of a won position for the winner. Every move takes one piece, so
+/// the pieces left count the plies the win spares: faster wins score higher,
+/// on one scale in the depth-limited and the exact search alike.
+fn win_score(board: &Board) -> i32 {
+    10000 + remaining_pieces(board) as i32
+}
+
+/// Number of pieces left anywhere on the board.
+fn remaining_pieces(board: &Board) -> usize {
+    (0..board.size())
+        .flat_map(|c| (0..board.size()).map(move |r| (c, r)))
+        .filter(|&(c, r)| board.get(c, r).kind != CellKind::Empty)
+        .count()
+}
+
+/// The positions a move leads to, each with the axis handed over on the
+/// way: just one for most moves, but after a wildcard banana one for every
+/// axis its taker may pick (see [`handover_axes`]), so the search weighs
+/// each hand-off as a move of its own.
+fn successors(
+    state: &SearchState,
+    col: usize,
+    row: usize,
+    ai_to_move: bool,
+) -> Vec<(Option<Selection>, SearchState)> {
+    let wildcard = state.banana_rule == BananaRule::FreeChoice
+        && state.board.get(col, row).kind == CellKind::Banana;
+    let mut child = state.clone();
+    apply_move_to(&mut child, col, row, ai_to_move);
+    let axes = if wildcard {
+        handover_axes(&child.board, child.selection)
+    } else {
+        Vec::new()
+    };
+    if axes.is_empty() {
+        return vec![(None, child)];
+    }
+    axes.into_iter()
+        .map(|axis| {
+            let mut handed = child.clone();
+            handed.selection = axis;
+            (Some(axis), handed)
+        })
+        .collect()
+}
+
+/// Apply a move to a SearchState, modifying it in place. A banana keeps the
+/// axis under either rule; the axes a wildcard can hand over instead are
+/// the business of [`successors`].
+fn apply_move_to(state: &mut SearchState, col: usize, row: usize, ai_to_move: bool) {
     let cell = *state.board.get(col, row);
+    let target = state.target;
 
-    let tower = if is_maximizer {
+    let tower = if ai_to_move {
         &mut state.tower_me
     } else {
         &mut state.tower_opp
@@ -226,7 +1000,7 @@ fn apply_move_to(state: &mut SearchState, col: usize, row: usize, is_maximizer:
 
     match cell.kind {
         CellKind::Stone => {
-            *tower = (*tower + cell.value + 1).min(MAX_TOWER);
+            *tower = (*tower + cell.value + 1).min(target);
         }
         CellKind::Bomb => {
             *tower = (*tower - cell.value - 1).max(0);
@@ -234,6 +1008,8 @@ fn apply_move_to(state: &mut SearchState, col: usize, row: usize, is_maximizer:
         _ => {}
     }
 
+    state.board.clear(col, row);
+
     // Switch selection (banana keeps same axis)
     if cell.kind != CellKind::Banana {
         state.selection = match state.selection {
@@ -241,19 +1017,155 @@ fn apply_move_to(state: &mut SearchState, col: usize, row: usize, is_maximizer:
             Selection::Column(_) => Selection::Row(row),
         };
     }
+}
 
-    state.board.clear(col, row);
+/// Wildcard bananas: the axes the taker may hand over after taking a banana,
+/// `current` first, then columns, then rows. Axes with nothing but bananas
+/// are left out, as handing them over would end the round.
+fn handover_axes(board: &Board, current: Selection) -> Vec<Selection> {
+    let size = board.size();
+    std::iter::once(current)
+        .chain((0..size).map(Selection::Column))
+        .chain((0..size).map(Selection::Row))
+        .enumerate()
+        .filter(|&(i, axis)| i == 0 || axis != current)
+        .map(|(_, axis)| axis)
+        .filter(|&axis| !board.axis_only_bananas(axis))
+        .collect()
+}
+
+/// Wildcard bananas: the axis to hand the opponent, whose tower is
+/// `tower_opponent`, after taking a banana. Takes the axis whose best reply
+/// does the opponent the least good, a stone that wins at once counting above
+/// everything. Ties prefer `current`, then columns, then rows (the order of
+/// [`handover_axes`]); `None` if no axis can be handed over. A rule of thumb
+/// one ply deep: the search weighs every axis instead, see [`successors`].
+pub fn banana_axis(
+    board: &Board,
+    current: Selection,
+    tower_opponent: i32,
+    target_height: i32,
+    win_rule: WinRule,
+) -> Option<Selection> {
+    const WINNING_REPLY: i32 = 1000;
+    let size = board.size();
+    let best_reply = |axis: Selection| {
+        (0..size)
+            .map(|i| sel_coords(axis, i))
+            .map(|(col, row)| *board.get(col, row))
+            .filter(|cell| cell.kind != CellKind::Empty)
+            .map(|cell| {
+                let wins = cell.kind == CellKind::Stone
+                    && win_rule == WinRule::FirstToTarget
+                    && tower_opponent + cell.value + 1 >= target_height;
+                if wins {
+                    WINNING_REPLY
+                } else {
+                    cell_value(cell.kind, cell.value)
+                }
+            })
+            .max()
+            .unwrap_or(i32::MIN)
+    };
+    // `min_by_key` keeps the first of equal keys.
+    handover_axes(board, current)
+        .into_iter()
+        .min_by_key(|&axis| best_reply(axis))
+}
+
+/// Does the side to move have a move that decides a lot at once: a stone that
+/// completes its tower, or a bomb that knocks it down to the ground?
+fn has_critical_move(state: &SearchState, ai_to_move: bool) -> bool {
+    let tower = if ai_to_move {
+        state.tower_me
+    } else {
+        state.tower_opp
+    };
+    (0..state.board.size()).any(|i| {
+        let (col, row) = sel_coords(state.selection, i);
+        let cell = state.board.get(col, row);
+        match cell.kind {
+            CellKind::Stone => state.reaches_target(tower + cell.value + 1),
+            CellKind::Bomb => tower > 0 && tower - cell.value - 1 <= 0,
+            _ => false,
+        }
+    })
+}
+
+/// Evaluation at the search horizon. A static evaluation would "stand pat"
+/// right before such a critical move, so those positions get one extra ply
+/// whose children are evaluated statically – a single, bounded extension.
+fn evaluate_leaf(state: &SearchState, ai_to_move: bool) -> i32 {
+    if !has_critical_move(state, ai_to_move) {
+        return evaluate(state);
+    }
+    let children = ordered_moves(state)
+        .into_iter()
+        .flat_map(|(col, row)| successors(state, col, row, ai_to_move));
+    let scores = children.map(|(_, child)| {
+        // Scored like minimax one ply below the horizon.
+        if child.reaches_target(child.tower_me) {
+            win_score(&child.board)
+        } else if child.reaches_target(child.tower_opp) {
+            -win_score(&child.board)
+        } else if child.board.round_over(child.selection, child.banana_rule) {
+            evaluate_final(&child)
+        } else {
+            evaluate(&child)
+        }
+    });
+    let best = if ai_to_move {
+        scores.max()
+    } else {
+        scores.min()
+    };
+    best.unwrap_or_else(|| evaluate_final(state))
+}
+
+/// Weights of the terms of [`evaluate`].
+struct EvalWeights {
+    tower: i32,
+    axis: i32,
+    /// In quarters, so the balanced weight of 1 can be lowered.
+    opponent_axis_quarters: i32,
+    mobility: i32,
+}
+
+impl EvalWeights {
+    fn of(personality: AiPersonality) -> Self {
+        match personality {
+            AiPersonality::Balanced => Self {
+                tower: 100,
+                axis: 8,
+                opponent_axis_quarters: 4,
+                mobility: 5,
+            },
+            AiPersonality::Aggressive => Self {
+                tower: 130,
+                axis: 10,
+                opponent_axis_quarters: 2,
+                mobility: 5,
+            },
+            AiPersonality::Defensive => Self {
+                tower: 100,
+                axis: 6,
+                opponent_axis_quarters: 12,
+                mobility: 5,
+            },
+        }
+    }
 }
 
 /// Heuristic evaluation of a non-terminal position.
 /// Positive = good for AI, negative = good for opponent.
 fn evaluate(state: &SearchState) -> i32 {
-    let tower_diff = (state.tower_me - state.tower_opp) * 100;
+    let weights = EvalWeights::of(state.personality);
+    let tower_diff = (state.tower_me - state.tower_opp) * weights.tower;
 
     // Evaluate the available moves for the current player on the active selection
     let mut axis_value = 0i32;
     let mut available_count = 0i32;
-    for i in 0..BOARD_SIZE {
+    for i in 0..state.board.size() {
         let (col, row) = sel_coords(state.selection, i);
         let cell = state.board.get(col, row);
         if cell.kind != CellKind::Empty {
@@ -262,21 +1174,35 @@ fn evaluate(state: &SearchState) -> i32 {
         }
     }
 
-    // Look ahead at what the opponent will have access to
+    // Look ahead at what the opponent will have access to: the line through the
+    // taken cell, perpendicular to the active one (the same line after a
+    // banana). Averaged over the available moves; the taken cell never counts.
     let mut opponent_axis_value = 0i32;
-    for col in 0..BOARD_SIZE {
-        for row in 0..BOARD_SIZE {
-            let cell = state.board.get(col, row);
-            if cell.kind != CellKind::Empty {
-                // Check if this cell is on a potential future selection
-                let val = cell_value(cell.kind, cell.value);
-                opponent_axis_value += val;
+    for i in 0..state.board.size() {
+        let (col, row) = sel_coords(state.selection, i);
+        let cell = state.board.get(col, row);
+        if cell.kind == CellKind::Empty {
+            continue;
+        }
+        let next = match (cell.kind, state.selection) {
+            (CellKind::Banana, selection) => selection,
+            (_, Selection::Row(_)) => Selection::Column(col),
+            (_, Selection::Column(_)) => Selection::Row(row),
+        };
+        for j in 0..state.board.size() {
+            let (c, r) = sel_coords(next, j);
+            let other = state.board.get(c, r);
+            if (c, r) != (col, row) && other.kind != CellKind::Empty {
+                opponent_axis_value += cell_value(other.kind, other.value);
             }
         }
     }
+    opponent_axis_value /= available_count.max(1);
 
     // Weighted combination
-    tower_diff + axis_value * 8 - opponent_axis_value / (BOARD_SIZE as i32) + available_count * 5
+    tower_diff + axis_value * weights.axis
+        - opponent_axis_value * weights.opponent_axis_quarters / 4
+        + available_count * weights.mobility
 }
 
 /// Evaluate a terminal position (game over due to exhaustion or tower reached).
@@ -291,7 +1217,7 @@ fn evaluate_final(state: &SearchState) -> i32 {
 }
 
 /// Convert a selection + index to (col, row) coordinates.
-fn sel_coords(selection: Selection, idx: usize) -> (usize, usize) {
+pub(crate) fn sel_coords(selection: Selection, idx: usize) -> (usize, usize) {
     match selection {
         Selection::Row(r) => (idx, r),
         Selection::Column(c) => (c, idx),
@@ -299,7 +1225,7 @@ fn sel_coords(selection: Selection, idx: usize) -> (usize, usize) {
 }
 
 /// The immediate value of picking a cell. Positive = good for the picker.
-fn cell_value(kind: CellKind, value: i32) -> i32 {
+pub fn cell_value(kind: CellKind, value: i32) -> i32 {
     match kind {
         CellKind::Empty => 0,
         CellKind::Stone => (value + 1) * 10, // stones are great (+1 to +4)
@@ -307,3 +1233,777 @@ fn cell_value(kind: CellKind, value: i32) -> i32 {
         CellKind::Banana => 1,               // banana is near-neutral
     }
 }
+
+/// Summed [`cell_value`] of every column and row.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AxisValues {
+    /// Indexed by column.
+    pub columns: Vec<i32>,
+    /// Indexed by row.
+    pub rows: Vec<i32>,
+}
+
+/// What each axis of `board` is worth to whoever picks along it: the summed
+/// `cell_value` of its pieces, high for stones, negative for bombs.
+pub fn axis_values(board: &Board) -> AxisValues {
+    let size = board.size();
+    let value = |col, row| {
+        let cell = board.get(col, row);
+        cell_value(cell.kind, cell.value)
+    };
+    AxisValues {
+        columns: (0..size)
+            .map(|col| (0..size).map(|row| value(col, row)).sum())
+            .collect(),
+        rows: (0..size)
+            .map(|row| (0..size).map(|col| value(col, row)).sum())
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::types::{Cell, DEFAULT_TARGET_HEIGHT};
+
+    /// Deterministic boards with a mix of stones, bombs and bananas.
+    fn fixed_board(size: usize, seed: usize) -> (Board, Selection) {
+        let cells = (0..size)
+            .map(|col| {
+                (0..size)
+                    .map(|row| {
+                        let kind = match (col * 7 + row * 3 + seed) % 11 {
+                            0 => CellKind::Banana,
+                            1..=6 => CellKind::Stone,
+                            _ => CellKind::Bomb,
+                        };
+                        let value = match kind {
+                            CellKind::Stone | CellKind::Bomb => {
+                                ((col * 5 + row * 2 + seed) % 4) as i32
+                            }
+                            _ => 0,
+                        };
+                        Cell { kind, value }
+                    })
+                    .collect()
+            })
+            .collect();
+        (Board::from_cells(cells), Selection::Row(seed % size))
+    }
+
+    /// Plain minimax without pruning or caching, as a reference for exact values.
+    fn reference(state: &SearchState, depth: i32, maximizing: bool) -> i32 {
+        if state.reaches_target(state.tower_me) {
+            return win_score(&state.board);
+        }
+        if state.reaches_target(state.tower_opp) {
+            return -win_score(&state.board);
+        }
+        if state.board.round_over(state.selection, state.banana_rule) {
+            return evaluate_final(state);
+        }
+        if depth <= 0 {
+            return evaluate_leaf(state, maximizing);
+        }
+        let scores = selection_moves(state)
+            .into_iter()
+            .flat_map(|(col, row)| successors(state, col, row, maximizing))
+            .map(|(_, child)| reference(&child, depth - 1, !maximizing));
+        if maximizing {
+            scores.max().unwrap_or_else(|| evaluate_final(state))
+        } else {
+            scores.min().unwrap_or_else(|| evaluate_final(state))
+        }
+    }
+
+    /// Search every root move of a seeded board and return the scores and the node count.
+    fn search_root(seed: u64, depth: i32, ordering: bool) -> (Vec<i32>, u64) {
+        use rand::SeedableRng;
+        let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+        let (board, selection) =
+            Board::new_random_with(8, &crate::game::field::BoardGenConfig::default(), &mut rng);
+        let root = SearchState {
+            board,
+            selection,
+            tower_me: 0,
+            tower_opp: 0,
+            target: DEFAULT_TARGET_HEIGHT,
+            win_rule: WinRule::FirstToTarget,
+            banana_rule: BananaRule::KeepAxis,
+            personality: AiPersonality::Balanced,
+        };
+        let mut search = Search::new(SearchOptions {
+            move_ordering: ordering,
+            ..SearchOptions::default()
+        });
+        let scores = (0..8)
+            .map(|i| sel_coords(selection, i))
+            .filter(|&(col, row)| root.board.get(col, row).kind != CellKind::Empty)
+            .map(|(col, row)| {
+                let mut child = root.clone();
+                apply_move_to(&mut child, col, row, true);
+                let score = negamax(
+                    &child,
+                    depth - 1,
+                    -INFINITY,
+                    INFINITY,
+                    false,
+                    None,
+                    &mut search,
+                );
+                -score.unwrap()
+            })
+            .collect();
+        (scores, search.nodes)
+    }
+
+    /// A 6×6 board that is empty except for the given cells of row 0.
+    fn sparse_board(row0: &[(usize, CellKind, i32)]) -> Board {
+        let mut cells = vec![vec![Cell::default(); 6]; 6];
+        for &(col, kind, value) in row0 {
+            cells[col][0] = Cell { kind, value };
+        }
+        Board::from_cells(cells)
+    }
+
+    #[test]
+    fn leaf_extension_sees_winning_stone() {
+        // The AI is ahead, but the opponent is to move and can finish its tower.
+        let state = SearchState {
+            board: sparse_board(&[(1, CellKind::Bomb, 0), (4, CellKind::Stone, 3)]),
+            selection: Selection::Row(0),
+            tower_me: 19,
+            tower_opp: 17,
+            target: DEFAULT_TARGET_HEIGHT,
+            win_rule: WinRule::FirstToTarget,
+            banana_rule: BananaRule::KeepAxis,
+            personality: AiPersonality::Balanced,
+        };
+        assert!(evaluate(&state) > 0, "stand-pat looks good for the AI");
+        // The winning stone leaves the bomb on the board.
+        assert_eq!(evaluate_leaf(&state, false), -(10000 + 1));
+        // With the AI to move the same position is simply won.
+        assert_eq!(evaluate_leaf(&state, true), 10000 + 1);
+    }
+
+    #[test]
+    fn leaf_extension_sees_forced_bomb() {
+        // The AI's only move is a bomb that knocks its tower down to zero.
+        let state = SearchState {
+            board: sparse_board(&[(2, CellKind::Bomb, 3)]),
+            selection: Selection::Row(0),
+            tower_me: 4,
+            tower_opp: 0,
+            target: DEFAULT_TARGET_HEIGHT,
+            win_rule: WinRule::FirstToTarget,
+            banana_rule: BananaRule::KeepAxis,
+            personality: AiPersonality::Balanced,
+        };
+        assert!(
+            evaluate(&state) > 0,
+            "stand-pat still counts the taller tower"
+        );
+        // After the bomb both towers are empty and the board is exhausted: a draw.
+        assert_eq!(evaluate_leaf(&state, true), 0);
+    }
+
+    #[test]
+    fn leaf_extension_leaves_quiet_positions_alone() {
+        let state = SearchState {
+            board: sparse_board(&[(0, CellKind::Stone, 1), (3, CellKind::Bomb, 0)]),
+            selection: Selection::Row(0),
+            tower_me: 8,
+            tower_opp: 9,
+            target: DEFAULT_TARGET_HEIGHT,
+            win_rule: WinRule::FirstToTarget,
+            banana_rule: BananaRule::KeepAxis,
+            personality: AiPersonality::Balanced,
+        };
+        assert_eq!(evaluate_leaf(&state, true), evaluate(&state));
+        assert_eq!(evaluate_leaf(&state, false), evaluate(&state));
+    }
+
+    #[test]
+    fn evaluation_only_counts_lines_the_opponent_can_reach() {
+        // The same stones, once on the column the opponent gets after the only
+        // move and once on a column nobody can reach next.
+        let position = |column: usize| {
+            let mut cells = vec![vec![Cell::default(); 6]; 6];
+            cells[0][0] = Cell {
+                kind: CellKind::Stone,
+                value: 0,
+            };
+            for cell in cells[column].iter_mut().skip(1) {
+                *cell = Cell {
+                    kind: CellKind::Stone,
+                    value: 3,
+                };
+            }
+            SearchState {
+                board: Board::from_cells(cells),
+                selection: Selection::Row(0),
+                tower_me: 5,
+                tower_opp: 5,
+                target: DEFAULT_TARGET_HEIGHT,
+                win_rule: WinRule::FirstToTarget,
+                banana_rule: BananaRule::KeepAxis,
+                personality: AiPersonality::Balanced,
+            }
+        };
+        assert!(evaluate(&position(5)) > evaluate(&position(0)));
+    }
+
+    #[test]
+    fn personalities_weigh_the_lead_and_the_opponents_options() {
+        // The AI leads, but its only move opens a column of strong stones.
+        let mut cells = vec![vec![Cell::default(); 6]; 6];
+        cells[0][0] = Cell {
+            kind: CellKind::Stone,
+            value: 0,
+        };
+        for cell in cells[0].iter_mut().skip(1) {
+            *cell = Cell {
+                kind: CellKind::Stone,
+                value: 3,
+            };
+        }
+        let score = |personality| {
+            evaluate(&SearchState {
+                board: Board::from_cells(cells.clone()),
+                selection: Selection::Row(0),
+                tower_me: 10,
+                tower_opp: 5,
+                target: DEFAULT_TARGET_HEIGHT,
+                win_rule: WinRule::FirstToTarget,
+                banana_rule: BananaRule::KeepAxis,
+                personality,
+            })
+        };
+        let balanced = score(AiPersonality::Balanced);
+        assert_eq!(balanced, 5 * 100 + 10 * 8 - 200 + 5, "the original weights");
+        assert!(score(AiPersonality::Aggressive) > balanced);
+        assert!(score(AiPersonality::Defensive) < balanced);
+    }
+
+    #[test]
+    fn weighted_random_prefers_valuable_cells() {
+        use rand::SeedableRng;
+        let board = sparse_board(&[
+            (0, CellKind::Bomb, 3),
+            (2, CellKind::Banana, 0),
+            (4, CellKind::Stone, 3),
+        ]);
+        let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+        let mut counts = [0; 6];
+        for _ in 0..1000 {
+            let (col, row) = weighted_random_move(
+                &board,
+                Selection::Row(0),
+                WEIGHTED_RANDOM_TEMPERATURE,
+                &mut rng,
+            );
+            assert_eq!(row, 0);
+            counts[col] += 1;
+        }
+        assert_eq!(
+            counts[1] + counts[3] + counts[5],
+            0,
+            "empty cells are never picked"
+        );
+        assert!(counts[4] > counts[2] && counts[2] > counts[0], "{counts:?}");
+        assert!(counts[2] > 0, "weaker moves still happen");
+    }
+
+    #[test]
+    fn opening_takes_the_best_cell_nearest_the_middle() {
+        use rand::SeedableRng;
+        let board = sparse_board(&[
+            (0, CellKind::Stone, 3),
+            (2, CellKind::Stone, 1),
+            (3, CellKind::Bomb, 3),
+            (4, CellKind::Stone, 3),
+        ]);
+        let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+        for _ in 0..10 {
+            assert_eq!(opening_move(&board, Selection::Row(0), &mut rng), (4, 0));
+        }
+    }
+
+    #[test]
+    fn target_height_decides_when_a_stone_wins() {
+        use rand::SeedableRng;
+        let board = sparse_board(&[
+            (1, CellKind::Banana, 0),
+            (3, CellKind::Stone, 1),
+            (5, CellKind::Bomb, 0),
+        ]);
+        let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+        let mut search = |target, win_rule| {
+            calculate_move_with_rng(
+                3,
+                AiPersonality::Balanced,
+                &board,
+                Selection::Row(0),
+                10,
+                0,
+                target,
+                win_rule,
+                BananaRule::KeepAxis,
+                &mut rng,
+            )
+        };
+        let (cell, score) = search(12, WinRule::FirstToTarget);
+        assert_eq!(cell, (3, 0));
+        assert!(score > 10000, "reaching 12 wins at once");
+        let (_, score) = search(DEFAULT_TARGET_HEIGHT, WinRule::FirstToTarget);
+        assert!(score < 10000, "a tower of 12 is not finished yet");
+        let (_, score) = search(12, WinRule::TallestAtEnd);
+        assert!(score < 10000, "reaching 12 only caps the tower");
+    }
+
+    #[test]
+    fn exact_solver_finds_forced_win() {
+        // Taking the big stone lets the opponent draw level with another one;
+        // the small stone leaves the opponent nothing but a bomb.
+        let mut cells = vec![vec![Cell::default(); 6]; 6];
+        cells[0][0] = Cell {
+            kind: CellKind::Stone,
+            value: 3,
+        };
+        cells[3][0] = Cell {
+            kind: CellKind::Stone,
+            value: 0,
+        };
+        cells[0][2] = Cell {
+            kind: CellKind::Stone,
+            value: 3,
+        };
+        cells[3][4] = Cell {
+            kind: CellKind::Bomb,
+            value: 3,
+        };
+        let state = SearchState {
+            board: Board::from_cells(cells),
+            selection: Selection::Row(0),
+            tower_me: 0,
+            tower_opp: 0,
+            target: DEFAULT_TARGET_HEIGHT,
+            win_rule: WinRule::FirstToTarget,
+            banana_rule: BananaRule::KeepAxis,
+            personality: AiPersonality::Balanced,
+        };
+        let mut search = Search::new(SearchOptions::default());
+        assert_eq!(solve_exact(&state, true, &mut search), 5000);
+        assert_eq!(reference(&state, 10, true), 5000);
+
+        use rand::SeedableRng;
+        let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+        let decision = fixed_depth_move(&state, 1, &mut rng, &mut |_| {});
+        assert_eq!(decision.chosen, (3, 0));
+        assert_eq!(decision.score, 5000);
+    }
+
+    #[test]
+    fn analysis_scores_every_move_best_first() {
+        let (board, selection) = fixed_board(6, 2);
+        let analysis = analyze_moves(
+            &board,
+            selection,
+            4,
+            7,
+            DEFAULT_TARGET_HEIGHT,
+            WinRule::FirstToTarget,
+            BananaRule::KeepAxis,
+            3,
+        );
+        let occupied = (0..6)
+            .filter(|&i| {
+                let (col, row) = sel_coords(selection, i);
+                board.get(col, row).kind != CellKind::Empty
+            })
+            .count();
+        assert_eq!(analysis.len(), occupied);
+        assert!(analysis.windows(2).all(|w| w[0].1 >= w[1].1));
+
+        use rand::SeedableRng;
+        let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+        let root = SearchState {
+            board,
+            selection,
+            tower_me: 4,
+            tower_opp: 7,
+            target: DEFAULT_TARGET_HEIGHT,
+            win_rule: WinRule::FirstToTarget,
+            banana_rule: BananaRule::KeepAxis,
+            personality: AiPersonality::Balanced,
+        };
+        let decision = fixed_depth_move(&root, 3, &mut rng, &mut |_| {});
+        assert_eq!(analysis[0].1, decision.score);
+        assert_eq!(decision.candidates, analysis);
+        assert_eq!(decision.depth, Some(3));
+    }
+
+    #[test]
+    fn searches_report_their_progress() {
+        use rand::SeedableRng;
+        let (board, selection) = fixed_board(6, 2);
+        let search = |level| {
+            let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+            let mut reports = Vec::new();
+            let decision = decide_move_with_progress(
+                level,
+                AiPersonality::Balanced,
+                &board,
+                selection,
+                4,
+                7,
+                DEFAULT_TARGET_HEIGHT,
+                WinRule::FirstToTarget,
+                BananaRule::KeepAxis,
+                &mut rng,
+                &mut |p| reports.push(p),
+            );
+            (decision, reports)
+        };
+
+        let (decision, reports) = search(4);
+        let done: Vec<usize> = reports
+            .iter()
+            .map(|p| match *p {
+                SearchProgress::RootMoves { done, total } => {
+                    assert!(done <= total);
+                    done
+                }
+                SearchProgress::Depth(_) => panic!("level 4 searches a fixed depth"),
+            })
+            .collect();
+        assert_eq!(done, (0..=decision.candidates.len()).collect::<Vec<_>>());
+
+        let (_, reports) = search(0);
+        assert!(reports.is_empty(), "nothing to report without a search");
+
+        let (decision, reports) = search(MAX_AI_LEVEL);
+        assert_eq!(reports.first(), Some(&SearchProgress::Depth(1)));
+        assert!(reports.windows(2).all(|w| match (w[0], w[1]) {
+            (SearchProgress::Depth(a), SearchProgress::Depth(b)) => b == a + 1,
+            _ => false,
+        }));
+        assert!(reports.contains(&SearchProgress::Depth(decision.depth.unwrap())));
+    }
+
+    #[test]
+    fn decisions_list_every_candidate_best_first() {
+        use rand::SeedableRng;
+        let (board, selection) = fixed_board(6, 2);
+        let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+        for level in 0..=4 {
+            let decision = decide_move(
+                level,
+                AiPersonality::Balanced,
+                &board,
+                selection,
+                4,
+                7,
+                DEFAULT_TARGET_HEIGHT,
+                WinRule::FirstToTarget,
+                BananaRule::KeepAxis,
+                &mut rng,
+            );
+            assert!(decision.candidates.windows(2).all(|w| w[0].1 >= w[1].1));
+            assert!(decision
+                .candidates
+                .contains(&(decision.chosen, decision.score)));
+            assert_eq!(decision.depth.is_some(), level >= FIRST_SEARCH_LEVEL);
+        }
+    }
+
+    #[test]
+    fn axis_values_sum_each_column_and_row() {
+        let stone = Cell {
+            kind: CellKind::Stone,
+            value: 0,
+        };
+        let mut board = Board::from_cells(vec![vec![stone; 4]; 4]);
+        board.clear(0, 0);
+        board.set(
+            1,
+            2,
+            Cell {
+                kind: CellKind::Bomb,
+                value: 3,
+            },
+        );
+        let values = axis_values(&board);
+        assert_eq!(values.columns, vec![30, -10, 40, 40]);
+        assert_eq!(values.rows, vec![30, 40, -10, 40]);
+    }
+
+    #[test]
+    fn wildcard_bananas_hand_over_the_weakest_axis() {
+        // Row 0 holds a banana and a strong stone; row 2 and column 3 a bomb.
+        let mut board = sparse_board(&[(1, CellKind::Banana, 0), (4, CellKind::Stone, 3)]);
+        board.set(
+            3,
+            2,
+            Cell {
+                kind: CellKind::Bomb,
+                value: 1,
+            },
+        );
+        let mut state = SearchState {
+            board,
+            selection: Selection::Row(0),
+            tower_me: 0,
+            tower_opp: 0,
+            target: DEFAULT_TARGET_HEIGHT,
+            win_rule: WinRule::FirstToTarget,
+            banana_rule: BananaRule::KeepAxis,
+            personality: AiPersonality::Balanced,
+        };
+        let kept = successors(&state, 1, 0, true);
+        assert_eq!(kept.len(), 1);
+        assert_eq!(kept[0].1.selection, Selection::Row(0));
+        state.banana_rule = BananaRule::FreeChoice;
+        // The search tries every axis that is not all bananas…
+        let handed: Vec<_> = successors(&state, 1, 0, true)
+            .into_iter()
+            .map(|(axis, child)| {
+                assert_eq!(Some(child.selection), axis);
+                child.selection
+            })
+            .collect();
+        assert_eq!(
+            handed,
+            [
+                Selection::Row(0),
+                Selection::Column(3),
+                Selection::Column(4),
+                Selection::Row(2)
+            ]
+        );
+        // …while the rule of thumb hands over the weakest one.
+        let kept = &kept[0].1;
+        assert_eq!(
+            banana_axis(
+                &kept.board,
+                Selection::Row(0),
+                0,
+                DEFAULT_TARGET_HEIGHT,
+                WinRule::FirstToTarget,
+            ),
+            Some(Selection::Column(3)),
+            "the bomb, columns first"
+        );
+        apply_move_to(&mut state, 1, 0, true);
+
+        // Equal axes keep the current one; only bananas left means no choice.
+        state.board.clear(3, 2);
+        let axis = |board: &Board| {
+            banana_axis(
+                board,
+                Selection::Row(0),
+                0,
+                DEFAULT_TARGET_HEIGHT,
+                WinRule::FirstToTarget,
+            )
+        };
+        assert_eq!(axis(&state.board), Some(Selection::Row(0)));
+        state.board.clear(4, 0);
+        assert_eq!(axis(&state.board), None);
+    }
+
+    #[test]
+    fn search_hands_over_the_axis_that_pays_off_later() {
+        use rand::SeedableRng;
+        // Only a banana to take. Handing over column 1 leaves the opponent
+        // a harmless bomb and ends the round level; column 3 gives it a small
+        // stone, but its reply opens row 3 and the big stone for the AI.
+        let mut cells = vec![vec![Cell::default(); 6]; 6];
+        for (col, row, kind, value) in [
+            (0, 0, CellKind::Banana, 0),
+            (1, 1, CellKind::Bomb, 0),
+            (3, 3, CellKind::Stone, 0),
+            (5, 3, CellKind::Stone, 3),
+        ] {
+            cells[col][row] = Cell { kind, value };
+        }
+        let state = SearchState {
+            board: Board::from_cells(cells),
+            selection: Selection::Row(0),
+            tower_me: 0,
+            tower_opp: 0,
+            target: DEFAULT_TARGET_HEIGHT,
+            win_rule: WinRule::FirstToTarget,
+            banana_rule: BananaRule::FreeChoice,
+            personality: AiPersonality::Balanced,
+        };
+        let mut taken = state.clone();
+        apply_move_to(&mut taken, 0, 0, true);
+        let one_ply = banana_axis(
+            &taken.board,
+            taken.selection,
+            0,
+            DEFAULT_TARGET_HEIGHT,
+            WinRule::FirstToTarget,
+        );
+        assert_eq!(one_ply, Some(Selection::Column(1)));
+
+        let mut search = Search::new(SearchOptions::default());
+        let mut rng = rand::rngs::StdRng::seed_from_u64(1);
+        let decision = minimax_move(&state, 3, None, &mut search, &mut rng, &mut |_| {}).unwrap();
+        assert_eq!(decision.chosen, (0, 0));
+        assert_eq!(decision.axis, Some(Selection::Column(3)));
+        assert_eq!(decision.score, reference(&state, 3, true));
+        assert!(decision.score > 0, "the AI ends up ahead");
+    }
+
+    #[test]
+    fn move_ordering_reduces_nodes() {
+        let (mut ordered_total, mut unordered_total) = (0, 0);
+        for seed in [1, 2, 3] {
+            let (ordered_scores, ordered_nodes) = search_root(seed, 6, true);
+            let (plain_scores, plain_nodes) = search_root(seed, 6, false);
+            assert_eq!(ordered_scores, plain_scores, "seed {seed}");
+            ordered_total += ordered_nodes;
+            unordered_total += plain_nodes;
+        }
+        assert!(
+            ordered_total < unordered_total,
+            "ordered {ordered_total} vs unordered {unordered_total} nodes"
+        );
+    }
+
+    #[test]
+    fn symmetric_boards_search_one_move_per_mirror_image() {
+        use rand::SeedableRng;
+        // Symmetric under all eight symmetries; row 0 maps onto itself by mirroring.
+        let (base, _) = fixed_board(6, 2);
+        let fold = |i: usize| i.min(5 - i);
+        let cells = (0..6)
+            .map(|col| {
+                (0..6)
+                    .map(|row| {
+                        let (a, b) = (fold(col), fold(row));
+                        *base.get(a.min(b), a.max(b))
+                    })
+                    .collect()
+            })
+            .collect();
+        let root = SearchState {
+            board: Board::from_cells(cells),
+            selection: Selection::Row(0),
+            tower_me: 3,
+            tower_opp: 5,
+            target: DEFAULT_TARGET_HEIGHT,
+            win_rule: WinRule::FirstToTarget,
+            banana_rule: BananaRule::KeepAxis,
+            personality: AiPersonality::Balanced,
+        };
+        assert_eq!(root_representatives(&root), vec![0, 1, 2, 2, 1, 0]);
+
+        let search = |symmetry: bool| {
+            let mut search = Search::new(SearchOptions {
+                root_symmetry: symmetry,
+                ..SearchOptions::default()
+            });
+            let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+            let decision =
+                minimax_move(&root, 5, None, &mut search, &mut rng, &mut |_| {}).unwrap();
+            (decision, search.nodes)
+        };
+        let (reduced, reduced_nodes) = search(true);
+        let (full, full_nodes) = search(false);
+        assert_eq!(reduced.chosen, full.chosen);
+        assert_eq!(reduced.score, full.score);
+        assert_eq!(reduced.candidates, full.candidates);
+        assert!(
+            reduced_nodes < full_nodes,
+            "reduced {reduced_nodes} vs full {full_nodes} nodes"
+        );
+    }
+
+    #[test]
+    fn transposition_table_keeps_search_results() {
+        for (size, seed, towers, depth) in [
+            (6, 0, (0, 0), 5),
+            (6, 4, (12, 15), 5),
+            (8, 1, (3, 7), 4),
+            (8, 9, (17, 16), 4),
+            (10, 2, (0, 0), 3),
+        ] {
+            let (board, selection) = fixed_board(size, seed);
+            let root = SearchState {
+                board,
+                selection,
+                tower_me: towers.0,
+                tower_opp: towers.1,
+                target: DEFAULT_TARGET_HEIGHT,
+                win_rule: WinRule::FirstToTarget,
+                banana_rule: BananaRule::KeepAxis,
+                personality: AiPersonality::Balanced,
+            };
+            let mut search = Search::new(SearchOptions::default());
+            for i in 0..size {
+                let (col, row) = sel_coords(selection, i);
+                if root.board.get(col, row).kind == CellKind::Empty {
+                    continue;
+                }
+                let mut child = root.clone();
+                apply_move_to(&mut child, col, row, true);
+                let cached = negamax(
+                    &child,
+                    depth - 1,
+                    -INFINITY,
+                    INFINITY,
+                    false,
+                    None,
+                    &mut search,
+                )
+                .map(|score| -score);
+                assert_eq!(
+                    cached,
+                    Some(reference(&child, depth - 1, false)),
+                    "board {size}/{seed}, move ({col}, {row})"
+                );
+            }
+            assert!(!search.table.entries.is_empty());
+        }
+    }
+
+    #[test]
+    fn searching_levels_keep_their_moves_on_seeded_boards() {
+        use rand::SeedableRng;
+        // Moves and scores recorded on boards of the default mix (drawn as the
+        // generator always drew them), so changes of play show up here.
+        for (seed, towers, level, expected) in [
+            (1, (0, 0), 3, ((3, 3), 1039)),
+            (1, (0, 0), 4, ((3, 0), 452)),
+            (2, (5, 3), 3, ((6, 4), 666)),
+            (2, (5, 3), 4, ((6, 1), 468)),
+            (3, (12, 14), 3, ((6, 0), 198)),
+            (3, (12, 14), 4, ((6, 0), 10059)),
+            (4, (16, 10), 3, ((6, 7), 10061)),
+            (4, (16, 10), 4, ((6, 7), 10061)),
+            (5, (2, 9), 3, ((6, 3), -435)),
+            (5, (2, 9), 4, ((6, 3), -526)),
+        ] {
+            let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+            let (board, selection) =
+                Board::new_random_with(8, &crate::game::field::BoardGenConfig::default(), &mut rng);
+            let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+            let chosen = calculate_move_with_rng(
+                level,
+                AiPersonality::Balanced,
+                &board,
+                selection,
+                towers.0,
+                towers.1,
+                DEFAULT_TARGET_HEIGHT,
+                WinRule::FirstToTarget,
+                BananaRule::KeepAxis,
+                &mut rng,
+            );
+            assert_eq!(chosen, expected, "seed {seed}, level {level}");
+        }
+    }
+}
@@ -1,65 +1,341 @@
-use rand::Rng;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 
-use super::types::{Cell, CellKind, Selection};
+use super::types::{BananaRule, Cell, CellKind, Selection};
 
-pub const BOARD_SIZE: usize = 8;
+/// Board edge length used unless configured otherwise.
+pub const DEFAULT_BOARD_SIZE: usize = 8;
+/// Smallest configurable board edge length.
+pub const MIN_BOARD_SIZE: usize = 6;
+/// Largest configurable board edge length.
+pub const MAX_BOARD_SIZE: usize = 12;
 
-/// The 8×8 game board.
-#[derive(Debug, Clone)]
+/// Boards rolled at most when asking for a balanced one.
+const BALANCE_ATTEMPTS: usize = 20;
+/// A board with a [`Board::balance_score`] up to this counts as balanced.
+pub const BALANCE_THRESHOLD: f64 = 0.33;
+/// Boards rolled at most in search of a playable one (see [`Board::is_playable`]).
+const PLAYABLE_ATTEMPTS: usize = 50;
+
+/// Piece mix of generated boards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct BoardGenConfig {
+    /// Share of bananas, in percent.
+    pub banana_pct: u32,
+    /// Share of stones, in percent.
+    pub stone_pct: u32,
+    /// Share of bombs, in percent.
+    pub bomb_pct: u32,
+    /// Relative weights of the values 0..=3 of stones and bombs.
+    pub value_weights: [u32; 4],
+}
+
+impl Default for BoardGenConfig {
+    fn default() -> Self {
+        Self {
+            banana_pct: 9,
+            stone_pct: 55,
+            bomb_pct: 36,
+            value_weights: [4, 4, 2, 1],
+        }
+    }
+}
+
+/// Banana, stone and bomb weights boards were generated with before the mix
+/// was configurable; the default shares are these, rounded to percent.
+const CLASSIC_KIND_WEIGHTS: [u32; 3] = [1, 6, 4];
+
+/// Everything a generated board follows from: the same recipe always gives
+/// the same board and opening axis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct BoardSeed {
+    pub seed: u64,
+    pub size: usize,
+    pub balanced: bool,
+    pub gen: BoardGenConfig,
+}
+
+impl BoardSeed {
+    /// A recipe with a fresh random seed.
+    pub fn random(size: usize, balanced: bool, gen: BoardGenConfig) -> Self {
+        Self {
+            seed: rand::random(),
+            size,
+            balanced,
+            gen,
+        }
+    }
+
+    /// Generate the board and its opening axis.
+    pub fn board(&self) -> (Board, Selection) {
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        if self.balanced {
+            Board::new_balanced_with(self.size, &self.gen, &mut rng)
+        } else {
+            Board::new_random_with(self.size, &self.gen, &mut rng)
+        }
+    }
+}
+
+impl BoardGenConfig {
+    /// The shares add up to 100% and some value has a weight.
+    pub fn is_valid(&self) -> bool {
+        let shares = [self.banana_pct, self.stone_pct, self.bomb_pct];
+        shares.iter().all(|&pct| pct <= 100)
+            && shares.iter().sum::<u32>() == 100
+            && self.value_weights.iter().all(|&w| w <= 100)
+            && self.value_weights.iter().any(|&w| w > 0)
+    }
+
+    /// This config if it is valid, the default otherwise.
+    pub fn validated(self) -> Self {
+        if self.is_valid() {
+            self
+        } else {
+            Self::default()
+        }
+    }
+
+    /// Weights of banana, stone and bomb. The default shares draw exactly
+    /// like the generator always did, so seeded boards (daily puzzles, saved
+    /// seeds) stay the same.
+    fn kind_weights(&self) -> [u32; 3] {
+        let shares = [self.banana_pct, self.stone_pct, self.bomb_pct];
+        let default = Self::default();
+        if shares == [default.banana_pct, default.stone_pct, default.bomb_pct] {
+            CLASSIC_KIND_WEIGHTS
+        } else {
+            shares
+        }
+    }
+
+    fn roll_kind(&self, rng: &mut impl Rng) -> CellKind {
+        let [banana, stone, bomb] = self.kind_weights();
+        let roll = rng.gen_range(0..banana + stone + bomb);
+        if roll < banana {
+            CellKind::Banana
+        } else if roll < banana + stone {
+            CellKind::Stone
+        } else {
+            CellKind::Bomb
+        }
+    }
+
+    /// Low rolls give high values, as they always have.
+    fn roll_value(&self, rng: &mut impl Rng) -> i32 {
+        let total: u32 = self.value_weights.iter().sum();
+        let mut roll = rng.gen_range(0..total);
+        for (value, &weight) in self.value_weights.iter().enumerate().rev() {
+            if roll < weight {
+                return value as i32;
+            }
+            roll -= weight;
+        }
+        0
+    }
+}
+
+/// What is left on a board, by kind and value.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PieceCounts {
+    /// Stones by value: index 0 holds the +1 stones, index 3 the +4 ones.
+    pub stones: [u32; 4],
+    /// Bombs by value, like `stones`.
+    pub bombs: [u32; 4],
+    pub bananas: u32,
+}
+
+impl PieceCounts {
+    pub fn stones_total(&self) -> u32 {
+        self.stones.iter().sum()
+    }
+
+    pub fn bombs_total(&self) -> u32 {
+        self.bombs.iter().sum()
+    }
+}
+
+/// The square game board (8×8 by default).
+#[derive(Debug, Clone, Hash, serde::Serialize, serde::Deserialize)]
 pub struct Board {
-    cells: [[Cell; BOARD_SIZE]; BOARD_SIZE],
+    size: usize,
+    cells: Vec<Vec<Cell>>,
 }
 
 impl Board {
-    /// Create a new randomly-populated board and an initial selection axis.
-    pub fn new_random() -> (Self, Selection) {
+    /// Create a new randomly-populated board of `size`×`size` cells and an initial selection axis.
+    /// With `balanced`, lopsided boards are re-rolled (see [`Board::new_balanced_with`]).
+    pub fn new_random(size: usize, balanced: bool, gen: &BoardGenConfig) -> (Self, Selection) {
         let mut rng = rand::thread_rng();
-        let mut cells = [[Cell::default(); BOARD_SIZE]; BOARD_SIZE];
-
-        for col in 0..BOARD_SIZE {
-            for row in 0..BOARD_SIZE {
-                // Determine cell kind (same probability distribution as the original)
-                let kind = match rng.gen_range(0..11) {
-                    0 => CellKind::Banana,
-                    1..=6 => CellKind::Stone,
-                    _ => CellKind::Bomb,
-                };
+        if balanced {
+            Self::new_balanced_with(size, gen, &mut rng)
+        } else {
+            Self::new_random_with(size, gen, &mut rng)
+        }
+    }
+
+    /// Roll boards until one scores at most [`BALANCE_THRESHOLD`], giving up after
+    /// [`BALANCE_ATTEMPTS`] rolls with the most balanced board seen so far.
+    pub fn new_balanced_with(
+        size: usize,
+        gen: &BoardGenConfig,
+        rng: &mut impl Rng,
+    ) -> (Self, Selection) {
+        let mut best = Self::new_random_with(size, gen, rng);
+        let mut best_score = best.0.balance_score();
+        for _ in 1..BALANCE_ATTEMPTS {
+            if best_score <= BALANCE_THRESHOLD {
+                break;
+            }
+            let candidate = Self::new_random_with(size, gen, rng);
+            let score = candidate.0.balance_score();
+            if score < best_score {
+                best = candidate;
+                best_score = score;
+            }
+        }
+        best
+    }
+
+    /// The board and axis for `seed`; the same seed always gives the same board
+    /// (always with the default piece mix).
+    pub fn new_seeded(size: usize, seed: u64) -> (Self, Selection) {
+        let gen = BoardGenConfig::default();
+        Self::new_random_with(size, &gen, &mut StdRng::seed_from_u64(seed))
+    }
+
+    /// Like [`Board::new_random`], drawing from `rng` (e.g. a seeded generator).
+    /// An invalid `gen` falls back to the default mix. Unplayable boards are
+    /// re-rolled; a mix that hardly ever gives a playable one (say, nothing but
+    /// bananas) keeps the last roll after [`PLAYABLE_ATTEMPTS`].
+    pub fn new_random_with(
+        size: usize,
+        gen: &BoardGenConfig,
+        rng: &mut impl Rng,
+    ) -> (Self, Selection) {
+        let mut rolled = Self::roll(size, gen, rng);
+        for _ in 1..PLAYABLE_ATTEMPTS {
+            if rolled.0.is_playable(rolled.1) {
+                break;
+            }
+            rolled = Self::roll(size, gen, rng);
+        }
+        rolled
+    }
+
+    /// One random board and opening axis, playable or not.
+    fn roll(size: usize, gen: &BoardGenConfig, rng: &mut impl Rng) -> (Self, Selection) {
+        let gen = gen.validated();
+        let size = size.clamp(MIN_BOARD_SIZE, MAX_BOARD_SIZE);
+        let mut cells = vec![vec![Cell::default(); size]; size];
+
+        for column in cells.iter_mut() {
+            for cell in column.iter_mut() {
+                let kind = gen.roll_kind(rng);
 
                 // Determine value (only relevant for Stone and Bomb)
                 let value = match kind {
-                    CellKind::Stone | CellKind::Bomb => match rng.gen_range(0..11) {
-                        0 => 3,
-                        1..=2 => 2,
-                        3..=6 => 1,
-                        _ => 0,
-                    },
+                    CellKind::Stone | CellKind::Bomb => gen.roll_value(rng),
                     _ => 0,
                 };
 
-                cells[col][row] = Cell { kind, value };
+                *cell = Cell { kind, value };
             }
         }
 
         let selection = if rng.gen_bool(0.5) {
-            Selection::Row(rng.gen_range(0..BOARD_SIZE))
+            Selection::Row(rng.gen_range(0..size))
         } else {
-            Selection::Column(rng.gen_range(0..BOARD_SIZE))
+            Selection::Column(rng.gen_range(0..size))
+        };
+
+        (Self { size, cells }, selection)
+    }
+
+    /// Build a board from explicit columns (`cells[col][row]`), e.g. for fixed
+    /// test positions or shared position codes.
+    pub fn from_cells(cells: Vec<Vec<Cell>>) -> Self {
+        Self {
+            size: cells.len(),
+            cells,
+        }
+    }
+
+    /// How unevenly the piece values are spread over the rows and columns:
+    /// 0.0 for a perfectly even board, higher is more lopsided.
+    ///
+    /// Every move is taken from one row or column, so a board is fair when no
+    /// line is much richer or poorer than the others: otherwise whoever gets
+    /// to pick from the rich lines first wins the race. Each line is summed
+    /// with signed piece values (stones +1..+4, bombs -1..-4, bananas 0); the
+    /// score is the mean absolute deviation of these sums from their average,
+    /// divided by `4 * sqrt(size)`. Random line sums spread with the square root
+    /// of their length, so scores of different board sizes stay comparable
+    /// (about 0.4 for a typical random board).
+    pub fn balance_score(&self) -> f64 {
+        let signed = |cell: &Cell| match cell.kind {
+            CellKind::Stone => (cell.value + 1) as f64,
+            CellKind::Bomb => -(cell.value + 1) as f64,
+            _ => 0.0,
         };
+        let columns = self
+            .cells
+            .iter()
+            .map(|column| column.iter().map(signed).sum::<f64>());
+        let rows = (0..self.size).map(|r| self.cells.iter().map(|c| signed(&c[r])).sum::<f64>());
+        let sums: Vec<f64> = columns.chain(rows).collect();
+        if sums.is_empty() {
+            return 0.0;
+        }
+        let mean = sums.iter().sum::<f64>() / sums.len() as f64;
+        let deviation = sums.iter().map(|s| (s - mean).abs()).sum::<f64>() / sums.len() as f64;
+        deviation / (4.0 * (self.size as f64).sqrt())
+    }
+
+    /// Edge length of the board.
+    pub fn size(&self) -> usize {
+        self.size
+    }
 
-        (Self { cells }, selection)
+    /// Returns `true` if the dimensions are within the supported range and match
+    /// the stored size (relevant for boards read from disk).
+    pub fn is_valid(&self) -> bool {
+        (MIN_BOARD_SIZE..=MAX_BOARD_SIZE).contains(&self.size)
+            && self.cells.len() == self.size
+            && self.cells.iter().all(|c| c.len() == self.size)
     }
 
     pub fn get(&self, col: usize, row: usize) -> &Cell {
         &self.cells[col][row]
     }
 
+    pub fn set(&mut self, col: usize, row: usize, cell: Cell) {
+        self.cells[col][row] = cell;
+    }
+
     pub fn clear(&mut self, col: usize, row: usize) {
         self.cells[col][row] = Cell::default();
     }
 
+    /// Tally of the pieces still on the board.
+    pub fn piece_counts(&self) -> PieceCounts {
+        let mut counts = PieceCounts::default();
+        for cell in self.cells.iter().flatten() {
+            let value = cell.value.clamp(0, 3) as usize;
+            match cell.kind {
+                CellKind::Stone => counts.stones[value] += 1,
+                CellKind::Bomb => counts.bombs[value] += 1,
+                CellKind::Banana => counts.bananas += 1,
+                CellKind::Empty => {}
+            }
+        }
+        counts
+    }
+
     /// Check whether every cell in the given selection axis is empty.
     pub fn selection_exhausted(&self, selection: Selection) -> bool {
-        for i in 0..BOARD_SIZE {
+        for i in 0..self.size {
             let cell = match selection {
                 Selection::Row(r) => &self.cells[i][r],
                 Selection::Column(c) => &self.cells[c][i],
@@ -70,4 +346,237 @@ impl Board {
         }
         true
     }
+
+    /// A fresh board with `selection` as its opening axis can be won or lost:
+    /// the axis offers a stone or bomb, and the board holds at least a line's
+    /// worth of them. Bananas alone change no tower.
+    pub fn is_playable(&self, selection: Selection) -> bool {
+        let counts = self.piece_counts();
+        let scoring = (counts.stones_total() + counts.bombs_total()) as usize;
+        !self.axis_only_bananas(selection) && scoring >= self.size
+    }
+
+    /// Every piece left on the given row/column is a banana (also true if none
+    /// is left). Bananas change no tower and keep the axis, so no move can
+    /// change the outcome any more.
+    pub fn axis_only_bananas(&self, selection: Selection) -> bool {
+        (0..self.size).all(|i| {
+            let cell = match selection {
+                Selection::Row(r) => &self.cells[i][r],
+                Selection::Column(c) => &self.cells[c][i],
+            };
+            matches!(cell.kind, CellKind::Empty | CellKind::Banana)
+        })
+    }
+
+    /// No move on `selection` can change the outcome, so the round ends. With
+    /// wildcard bananas a banana still hands on a fresh axis, so that takes
+    /// an empty axis or a board without a stone or bomb on any axis.
+    pub fn round_over(&self, selection: Selection, banana_rule: BananaRule) -> bool {
+        match banana_rule {
+            BananaRule::KeepAxis => self.axis_only_bananas(selection),
+            BananaRule::FreeChoice => {
+                self.selection_exhausted(selection)
+                    || (0..self.size).all(|row| self.axis_only_bananas(Selection::Row(row)))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    fn uniform_board(size: usize, cell: Cell) -> Board {
+        Board::from_cells(vec![vec![cell; size]; size])
+    }
+
+    #[test]
+    fn even_boards_score_better_than_lopsided_ones() {
+        let stone = Cell {
+            kind: CellKind::Stone,
+            value: 1,
+        };
+        let even = uniform_board(8, stone);
+        assert_eq!(even.balance_score(), 0.0);
+
+        // All the big stones in one row and column, bombs everywhere else.
+        let mut cells = vec![
+            vec![
+                Cell {
+                    kind: CellKind::Bomb,
+                    value: 0,
+                };
+                8
+            ];
+            8
+        ];
+        let big_stone = Cell {
+            kind: CellKind::Stone,
+            value: 3,
+        };
+        cells[0] = vec![big_stone; 8];
+        for column in cells.iter_mut() {
+            column[0] = big_stone;
+        }
+        let lopsided = Board::from_cells(cells);
+        assert!(lopsided.balance_score() > 2.0 * BALANCE_THRESHOLD);
+    }
+
+    #[test]
+    fn balanced_rolls_stay_within_threshold() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+        for size in MIN_BOARD_SIZE..=MAX_BOARD_SIZE {
+            let (board, _) = Board::new_balanced_with(size, &BoardGenConfig::default(), &mut rng);
+            assert_eq!(board.size(), size);
+            assert!(board.balance_score() <= BALANCE_THRESHOLD, "size {size}");
+        }
+    }
+
+    #[test]
+    fn generation_follows_the_configured_mix() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(3);
+        let big_bombs = BoardGenConfig {
+            banana_pct: 0,
+            stone_pct: 0,
+            bomb_pct: 100,
+            value_weights: [0, 0, 0, 1],
+        };
+        let (board, _) = Board::new_random_with(6, &big_bombs, &mut rng);
+        assert!((0..6)
+            .flat_map(|col| (0..6).map(move |row| (col, row)))
+            .all(|(col, row)| {
+                let cell = board.get(col, row);
+                cell.kind == CellKind::Bomb && cell.value == 3
+            }));
+
+        // Shares that do not add up to 100% fall back to the default mix.
+        let broken = BoardGenConfig {
+            bomb_pct: 90,
+            ..big_bombs
+        };
+        assert!(!broken.is_valid());
+        assert_eq!(broken.validated(), BoardGenConfig::default());
+    }
+
+    #[test]
+    fn default_mix_rolls_the_boards_it_always_rolled() {
+        // Seed 42 on 6×6 as the generator drew it before the mix was
+        // configurable, row by row: B banana, s stone, b bomb, then the value.
+        let expected = [
+            "s0 B  B  s1 s0 s3",
+            "b1 b0 s3 s1 b0 b1",
+            "b3 s1 b0 s1 s1 B ",
+            "s0 b1 b1 b0 s1 s0",
+            "s0 s0 s1 s2 s2 s0",
+            "s1 s0 s3 b0 B  b1",
+        ];
+        let seed = BoardSeed {
+            seed: 42,
+            size: 6,
+            balanced: false,
+            gen: BoardGenConfig::default(),
+        };
+        let (board, selection) = seed.board();
+        for (row, line) in expected.iter().enumerate() {
+            let rolled: Vec<String> = (0..6)
+                .map(|col| {
+                    let cell = board.get(col, row);
+                    match cell.kind {
+                        CellKind::Banana => "B ".to_string(),
+                        CellKind::Stone => format!("s{}", cell.value),
+                        CellKind::Bomb => format!("b{}", cell.value),
+                        CellKind::Empty => ". ".to_string(),
+                    }
+                })
+                .collect();
+            assert_eq!(rolled.join(" "), *line, "row {row}");
+        }
+        assert_eq!(selection, Selection::Column(3));
+    }
+
+    #[test]
+    fn piece_counts_follow_the_board() {
+        let stone = |value| Cell {
+            kind: CellKind::Stone,
+            value,
+        };
+        let mut board = uniform_board(6, stone(1));
+        board.set(0, 0, stone(3));
+        board.set(
+            1,
+            0,
+            Cell {
+                kind: CellKind::Bomb,
+                value: 0,
+            },
+        );
+        board.set(
+            2,
+            0,
+            Cell {
+                kind: CellKind::Banana,
+                value: 0,
+            },
+        );
+        let counts = board.piece_counts();
+        assert_eq!(counts.stones, [0, 33, 0, 1]);
+        assert_eq!(counts.bombs, [1, 0, 0, 0]);
+        assert_eq!(counts.bananas, 1);
+
+        board.clear(0, 0);
+        board.clear(2, 0);
+        board.clear(3, 0);
+        let counts = board.piece_counts();
+        assert_eq!((counts.stones_total(), counts.bombs_total()), (32, 1));
+        assert_eq!(counts.bananas, 0);
+    }
+
+    #[test]
+    fn boards_without_scoring_pieces_are_rerolled() {
+        let banana = Cell {
+            kind: CellKind::Banana,
+            value: 0,
+        };
+        let stone = Cell {
+            kind: CellKind::Stone,
+            value: 0,
+        };
+        let mut board = uniform_board(6, banana);
+        assert!(!board.is_playable(Selection::Row(0)));
+        // A line's worth of stones, but none on the opening axis.
+        for col in 0..6 {
+            board.set(col, 5, stone);
+        }
+        assert!(!board.is_playable(Selection::Row(0)));
+        assert!(board.is_playable(Selection::Row(5)));
+        assert!(board.is_playable(Selection::Column(2)));
+        // One stone on the axis is not enough on an otherwise banana board.
+        let mut sparse = uniform_board(6, banana);
+        sparse.set(0, 0, stone);
+        assert!(!sparse.is_playable(Selection::Row(0)));
+
+        let banana_heavy = BoardGenConfig {
+            banana_pct: 80,
+            stone_pct: 10,
+            bomb_pct: 10,
+            ..BoardGenConfig::default()
+        };
+        for seed in 0..50 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let (board, selection) = Board::new_random_with(6, &banana_heavy, &mut rng);
+            assert!(board.is_playable(selection), "seed {seed}");
+        }
+        // Nothing but bananas can never be playable; generation still ends.
+        let only_bananas = BoardGenConfig {
+            banana_pct: 100,
+            stone_pct: 0,
+            bomb_pct: 0,
+            ..BoardGenConfig::default()
+        };
+        let (board, selection) =
+            Board::new_random_with(6, &only_bananas, &mut StdRng::seed_from_u64(0));
+        assert!(!board.is_playable(selection));
+    }
 }
@@ -1,8 +1,38 @@
-use super::field::{Board, BOARD_SIZE};
-use super::types::{CellKind, GameOutcome, Selection, Statistics};
+use std::time::{Duration, Instant};
+
+use rand::rngs::StdRng;
+use rand::seq::IteratorRandom;
+use rand::SeedableRng;
+
+use super::field::{
+    Board, BoardGenConfig, BoardSeed, DEFAULT_BOARD_SIZE, MAX_BOARD_SIZE, MIN_BOARD_SIZE,
+};
+use super::types::{
+    AiPersonality, BananaRule, Cell, CellKind, GameMode, GameOutcome, OpenerMode, Selection,
+    Statistics, WinRule, ADAPTIVE_STREAK, DAILY_PUZZLE_LEVEL, DEFAULT_TARGET_HEIGHT,
+    MAX_TARGET_HEIGHT, MIN_TARGET_HEIGHT,
+};
 use crate::ai;
 
-const MAX_TOWER_HEIGHT: i32 = 20;
+/// One applied move, as recorded in the game log.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct MoveRecord {
+    pub col: usize,
+    pub row: usize,
+    /// The piece that was taken.
+    pub kind: CellKind,
+    pub value: i32,
+    pub by_player: bool,
+    pub selection_before: Selection,
+}
+
+/// The player's moves of a finished round against the computer, with the
+/// board they were played on: the line a ghost opponent replays.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct LastRun {
+    pub board: BoardSeed,
+    pub moves: Vec<(usize, usize)>,
+}
 
 /// Central game state holding everything needed for one round.
 #[derive(Debug, Clone)]
@@ -14,14 +44,115 @@ pub struct GameState {
     pub outcome: GameOutcome,
     pub moves_made: u32,
     pub ai_level: i32,
+    /// Setting: level of the AI in the player's seat while the player watches
+    /// the computer play itself.
+    pub watch_level: i32,
+    /// Setting: follow the player's streaks with `ai_level` (see [`ADAPTIVE_STREAK`]).
+    pub adaptive_difficulty: bool,
+    /// The level adaptive difficulty switched to when the last round ended,
+    /// or a shortcut mid-round (see [`GameState::step_ai_level`]), until the
+    /// UI has announced it.
+    pub adapted_level: Option<i32>,
+    /// How the computer weighs positions; the daily puzzle always plays balanced.
+    pub ai_personality: AiPersonality,
     pub tip: Option<(usize, usize)>,
+    /// Search score of the suggested move (positive = good for the player).
+    pub tip_score: Option<i32>,
     pub hovered: Option<(usize, usize)>,
+    /// Cell highlighted by the keyboard cursor (independent of the mouse hover).
+    pub keyboard_cursor: Option<(usize, usize)>,
+    /// "What-if" axis scrolled to over the board; drawn faintly, never played.
+    pub preview_selection: Option<Selection>,
     pub statistics: Statistics,
+    pub opener: OpenerMode,
+    /// Board edge length used for new rounds (the current board keeps its own size).
+    pub board_size: usize,
+    /// Setting: opponent for new rounds (the current round keeps its own mode).
+    pub game_mode: GameMode,
+    /// Opponent in the current round.
+    pub round_mode: GameMode,
+    /// Setting: re-roll lopsided boards for new rounds.
+    pub balanced_boards: bool,
+    /// Setting: piece mix of new boards (the daily puzzle always uses the default).
+    pub board_gen: BoardGenConfig,
+    /// Winning tower height for new rounds (the current round keeps its own target).
+    pub target_height: i32,
+    /// Tower height that wins the current round.
+    pub round_target: i32,
+    /// Setting: win rule for new rounds (the current round keeps its own rule).
+    pub win_rule: WinRule,
+    /// Whether reaching `round_target` ends the current round.
+    pub round_win_rule: WinRule,
+    /// Setting: banana rule for new rounds (the current round keeps its own rule).
+    pub banana_rule: BananaRule,
+    /// What taking a banana does in the current round.
+    pub round_banana_rule: BananaRule,
+    /// A human took a wildcard banana and picks the next axis before anyone
+    /// moves (see [`GameState::choose_axis`]).
+    pub choosing_axis: bool,
+    /// UTC date (`YYYYMMDD`) if the current round is that day's puzzle.
+    pub round_puzzle: Option<u32>,
+    /// Recipe of the current board; `None` if it was not generated (shared
+    /// positions, saves from older versions).
+    pub round_seed: Option<BoardSeed>,
+    /// Heights (player, computer) the towers of the current round started
+    /// from: 0/0 except in pasted positions. Undo and replays rebuild from them.
+    pub round_start_towers: (i32, i32),
+    /// Earlier run the computer replays in the current round, as far as its
+    /// moves are still possible.
+    pub round_ghost: Option<LastRun>,
+    /// The AI moved for the player in the current round (watching the
+    /// computer play itself). Such a round is not the player's: it leaves
+    /// statistics, the level, daily results and the ghost run alone.
+    pub round_watched: bool,
+    /// The round was pasted from a position code rather than dealt. It can
+    /// start anywhere, even one move from the end, so like a watched round
+    /// it is played but not recorded.
+    pub round_pasted: bool,
+    /// Fixed seed of the session: the computer's random choices, the dealt
+    /// boards and the moves a blitz timeout plays follow it, so a session can
+    /// be replayed. `None` draws new seeds for every round. Rounds still
+    /// differ: the round counter is mixed in.
+    pub ai_seed: Option<u64>,
+    /// Seed of the computer's random choices in the current round, see
+    /// [`GameState::ai_rng`].
+    pub round_ai_seed: u64,
+    /// Rounds started in this session after the first one (drives the alternating opener).
+    pub games_started: u32,
+    /// Write statistics, the last run and the adapted level to the user's
+    /// config directory when a round ends. Off unless the application turns
+    /// it on, so library users and simulated games leave the disk alone.
+    pub persist_statistics: bool,
+    /// Setting: the player chooses the opening row/column of rounds they open.
+    pub player_picks_axis: bool,
+    /// The round waits for the player to choose the opening row/column.
+    pub awaiting_initial_selection: bool,
+    /// Every move applied in the current round, in order.
+    pub move_log: Vec<MoveRecord>,
+    /// When the current round started; moved back by the time already played
+    /// when a saved round is resumed.
+    pub started_at: Instant,
+    /// Length of the current round once it is over.
+    pub round_duration: Option<Duration>,
+}
+
+impl Default for GameState {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl GameState {
+    /// A running round on a default-size board, with default settings.
     pub fn new() -> Self {
-        let (board, selection) = Board::new_random();
+        Self::with_board_size(DEFAULT_BOARD_SIZE)
+    }
+
+    /// Create a state whose first and following rounds use a `size`×`size` board.
+    pub fn with_board_size(size: usize) -> Self {
+        let board_size = size.clamp(MIN_BOARD_SIZE, MAX_BOARD_SIZE);
+        let seed = BoardSeed::random(board_size, false, BoardGenConfig::default());
+        let (board, selection) = seed.board();
         Self {
             board,
             selection,
@@ -29,32 +160,181 @@ impl GameState {
             tower_computer: 0,
             outcome: GameOutcome::Running,
             moves_made: 0,
-            ai_level: 2,
+            ai_level: 3,
+            watch_level: 3,
+            adaptive_difficulty: false,
+            adapted_level: None,
+            ai_personality: AiPersonality::Balanced,
             tip: None,
+            tip_score: None,
             hovered: None,
+            keyboard_cursor: None,
+            preview_selection: None,
             statistics: Statistics::default(),
+            opener: OpenerMode::Player,
+            board_size,
+            game_mode: GameMode::VsComputer,
+            round_mode: GameMode::VsComputer,
+            balanced_boards: false,
+            board_gen: BoardGenConfig::default(),
+            target_height: DEFAULT_TARGET_HEIGHT,
+            round_target: DEFAULT_TARGET_HEIGHT,
+            win_rule: WinRule::FirstToTarget,
+            round_win_rule: WinRule::FirstToTarget,
+            banana_rule: BananaRule::KeepAxis,
+            round_banana_rule: BananaRule::KeepAxis,
+            choosing_axis: false,
+            round_puzzle: None,
+            round_seed: Some(seed),
+            round_start_towers: (0, 0),
+            round_ghost: None,
+            round_watched: false,
+            round_pasted: false,
+            ai_seed: None,
+            round_ai_seed: rand::random(),
+            games_started: 0,
+            persist_statistics: false,
+            player_picks_axis: false,
+            awaiting_initial_selection: false,
+            move_log: Vec::new(),
+            started_at: Instant::now(),
+            round_duration: None,
         }
     }
 
     /// Start a fresh round, keeping statistics and AI level.
     pub fn new_game(&mut self) {
-        let (board, selection) = Board::new_random();
+        // `reset_round` counts the round this board is dealt for.
+        let seed = self.dealt_board_seed(self.games_started + 1);
+        let (board, selection) = seed.board();
+        self.reset_round(board, selection);
+        self.round_seed = Some(seed);
+        self.round_target = self
+            .target_height
+            .clamp(MIN_TARGET_HEIGHT, MAX_TARGET_HEIGHT);
+        self.round_win_rule = self.win_rule;
+        self.round_banana_rule = self.banana_rule;
+        self.round_mode = self.game_mode;
+        self.round_puzzle = None;
+        self.awaiting_initial_selection = self.player_picks_axis && !self.computer_opens();
+    }
+
+    /// The board recipe of session round `round` (0 for the first): drawn
+    /// from the session seed if there is one, otherwise random.
+    pub fn dealt_board_seed(&self, round: u32) -> BoardSeed {
+        let mut seed = BoardSeed::random(self.board_size, self.balanced_boards, self.board_gen);
+        if let Some(session) = self.ai_seed {
+            seed.seed = splitmix64(session.wrapping_add(round.into()));
+        }
+        seed
+    }
+
+    /// Start the puzzle of `date` (`YYYYMMDD`): a board seeded by the date,
+    /// played on default rules against [`DAILY_PUZZLE_LEVEL`]. The player
+    /// always opens, on the seeded axis.
+    pub fn start_daily_puzzle(&mut self, date: u32) {
+        let seed = BoardSeed {
+            seed: date.into(),
+            size: DEFAULT_BOARD_SIZE,
+            balanced: false,
+            gen: BoardGenConfig::default(),
+        };
+        let (board, selection) = seed.board();
+        self.reset_round(board, selection);
+        self.round_seed = Some(seed);
+        self.round_target = DEFAULT_TARGET_HEIGHT;
+        self.round_win_rule = WinRule::FirstToTarget;
+        self.round_banana_rule = BananaRule::KeepAxis;
+        self.round_mode = GameMode::VsComputer;
+        self.round_puzzle = Some(date);
+        self.awaiting_initial_selection = false;
+    }
+
+    /// Race an earlier run: a round on its board against a computer that
+    /// repeats the player's moves of that run (see [`GameState::ghost_move`]).
+    /// The player opens on the board's own axis, as in any seeded round.
+    pub fn start_ghost_round(&mut self, run: LastRun) {
+        let (board, selection) = run.board.board();
+        self.reset_round(board, selection);
+        self.round_seed = Some(run.board);
+        self.round_ghost = Some(run);
+        self.round_target = self
+            .target_height
+            .clamp(MIN_TARGET_HEIGHT, MAX_TARGET_HEIGHT);
+        self.round_win_rule = self.win_rule;
+        self.round_banana_rule = self.banana_rule;
+        self.round_mode = GameMode::VsComputer;
+        self.round_puzzle = None;
+        self.awaiting_initial_selection = false;
+    }
+
+    fn reset_round(&mut self, board: Board, selection: Selection) {
         self.board = board;
         self.selection = selection;
         self.tower_player = 0;
         self.tower_computer = 0;
+        self.round_start_towers = (0, 0);
         self.outcome = GameOutcome::Running;
         self.moves_made = 0;
         self.tip = None;
+        self.tip_score = None;
         self.hovered = None;
+        self.keyboard_cursor = None;
+        self.preview_selection = None;
+        self.move_log.clear();
+        self.choosing_axis = false;
+        self.round_ghost = None;
+        self.round_watched = false;
+        self.round_pasted = false;
+        self.started_at = Instant::now();
+        self.round_duration = None;
+        self.games_started += 1;
+        self.round_ai_seed = self.ai_seed.map_or_else(rand::random, |seed| {
+            seed.wrapping_add(self.games_started.into())
+        });
+    }
+
+    /// Set the opening row/column chosen by the player. Returns `false` if no
+    /// choice is pending or the axis holds no pieces.
+    pub fn choose_initial_selection(&mut self, selection: Selection) -> bool {
+        if !self.awaiting_initial_selection || self.board.selection_exhausted(selection) {
+            return false;
+        }
+        self.selection = selection;
+        self.awaiting_initial_selection = false;
+        self.preview_selection = None;
+        true
+    }
+
+    /// Returns `true` if the computer should make the opening move of the current round.
+    pub fn computer_opens(&self) -> bool {
+        if self.round_mode == GameMode::TwoPlayer
+            || self.round_puzzle.is_some()
+            || self.round_ghost.is_some()
+        {
+            return false;
+        }
+        match self.opener {
+            OpenerMode::Player => false,
+            OpenerMode::Alternate => self.games_started % 2 == 1,
+        }
+    }
+
+    /// In a two-player round: is it player 1's (left tower) turn? Always
+    /// `true` against the computer, where only the left seat is human.
+    pub fn player_one_to_move(&self) -> bool {
+        self.round_mode == GameMode::VsComputer || self.move_log.last().is_none_or(|m| !m.by_player)
     }
 
     /// Returns `true` if the cell at (col, row) is a valid target for the current selection.
     pub fn is_valid_move(&self, col: usize, row: usize) -> bool {
-        if self.outcome != GameOutcome::Running {
+        if self.outcome != GameOutcome::Running
+            || self.awaiting_initial_selection
+            || self.choosing_axis
+        {
             return false;
         }
-        if col >= BOARD_SIZE || row >= BOARD_SIZE {
+        if col >= self.board.size() || row >= self.board.size() {
             return false;
         }
         let in_selection = match self.selection {
@@ -64,6 +344,38 @@ impl GameState {
         in_selection && self.board.get(col, row).kind != CellKind::Empty
     }
 
+    /// Height the tower of the side to move would have after taking (col, row),
+    /// or `None` if that is no valid move or the piece changes no tower.
+    pub fn projected_tower(&self, col: usize, row: usize) -> Option<i32> {
+        if !self.is_valid_move(col, row) {
+            return None;
+        }
+        let cell = *self.board.get(col, row);
+        if cell.kind == CellKind::Banana {
+            return None;
+        }
+        let tower = if self.player_one_to_move() {
+            self.tower_player
+        } else {
+            self.tower_computer
+        };
+        Some(tower_after(tower, cell, self.round_target))
+    }
+
+    /// Taking (col, row) would lower the tower of the side to move: a bomb
+    /// on a tower that has something to lose.
+    pub fn is_harmful_move(&self, col: usize, row: usize) -> bool {
+        let tower = if self.player_one_to_move() {
+            self.tower_player
+        } else {
+            self.tower_computer
+        };
+        self.board.get(col, row).kind == CellKind::Bomb
+            && self
+                .projected_tower(col, row)
+                .is_some_and(|height| height < tower)
+    }
+
     /// Execute a move at (col, row). `is_player` indicates whether the human is acting.
     /// Does NOT automatically trigger the computer's turn – the caller is responsible.
     pub fn make_move(&mut self, col: usize, row: usize, is_player: bool) -> MoveResult {
@@ -80,15 +392,16 @@ impl GameState {
             &mut self.tower_computer
         };
 
-        match cell.kind {
-            CellKind::Stone => {
-                *tower = (*tower + cell.value + 1).min(MAX_TOWER_HEIGHT);
-            }
-            CellKind::Bomb => {
-                *tower = (*tower - cell.value - 1).max(0);
-            }
-            _ => {}
-        }
+        *tower = tower_after(*tower, cell, self.round_target);
+
+        self.move_log.push(MoveRecord {
+            col,
+            row,
+            kind: cell.kind,
+            value: cell.value,
+            by_player: is_player,
+            selection_before: self.selection,
+        });
 
         // Switch selection axis (banana keeps the same axis)
         if cell.kind != CellKind::Banana {
@@ -100,20 +413,54 @@ impl GameState {
 
         self.board.clear(col, row);
         self.moves_made += 1;
+        let wildcard =
+            cell.kind == CellKind::Banana && self.round_banana_rule == BananaRule::FreeChoice;
         self.tip = None;
+        self.tip_score = None;
+        self.preview_selection = None;
 
-        // Check win conditions
-        if self.tower_player >= MAX_TOWER_HEIGHT {
-            self.finish(GameOutcome::Won);
-            return MoveResult::GameOver;
+        // Check win conditions; under `TallestAtEnd` a full tower only stops growing
+        if self.round_win_rule == WinRule::FirstToTarget {
+            if self.tower_player >= self.round_target {
+                self.finish(GameOutcome::Won);
+                return MoveResult::GameOver;
+            }
+            if self.tower_computer >= self.round_target {
+                self.finish(GameOutcome::Lost);
+                return MoveResult::GameOver;
+            }
         }
-        if self.tower_computer >= MAX_TOWER_HEIGHT {
-            self.finish(GameOutcome::Lost);
-            return MoveResult::GameOver;
+
+        // A wildcard banana hands the next axis to its taker; the computer
+        // picks at once, a human with the next click
+        if wildcard {
+            let tower_next = if is_player {
+                self.tower_computer
+            } else {
+                self.tower_player
+            };
+            if let Some(axis) = ai::banana_axis(
+                &self.board,
+                self.selection,
+                tower_next,
+                self.round_target,
+                self.round_win_rule,
+            ) {
+                if self.round_mode == GameMode::VsComputer && !is_player {
+                    self.selection = axis;
+                } else {
+                    self.choosing_axis = true;
+                }
+                return MoveResult::Continue;
+            }
         }
 
-        // Check if all cells in the active selection are empty (no moves left)
-        if self.board.selection_exhausted(self.selection) {
+        // No moves left, or only bananas that can neither change a tower nor
+        // lead to an axis that can: the taller tower wins right away
+        if self
+            .board
+            .round_over(self.selection, self.round_banana_rule)
+        {
             let outcome = if self.tower_player > self.tower_computer {
                 GameOutcome::Won
             } else if self.tower_player < self.tower_computer {
@@ -128,40 +475,317 @@ impl GameState {
         MoveResult::Continue
     }
 
+    /// The computer's move at (col, row). After a wildcard banana it hands over
+    /// `axis`, the one its search settled on, as long as that axis can still
+    /// be played; otherwise [`ai::banana_axis`] picks, as in [`Self::make_move`].
+    pub fn make_computer_move(
+        &mut self,
+        col: usize,
+        row: usize,
+        axis: Option<Selection>,
+    ) -> MoveResult {
+        let wildcard = self.board.get(col, row).kind == CellKind::Banana
+            && self.round_banana_rule == BananaRule::FreeChoice;
+        let result = self.make_move(col, row, false);
+        if let Some(axis) = axis {
+            if wildcard
+                && result == MoveResult::Continue
+                && !self.choosing_axis
+                && !self.board.axis_only_bananas(axis)
+            {
+                self.selection = axis;
+            }
+        }
+        result
+    }
+
+    /// Pick the axis after a wildcard banana. Returns `false` if no choice is
+    /// pending or the axis holds nothing but bananas.
+    pub fn choose_axis(&mut self, selection: Selection) -> bool {
+        if !self.choosing_axis || self.board.axis_only_bananas(selection) {
+            return false;
+        }
+        self.selection = selection;
+        self.choosing_axis = false;
+        self.preview_selection = None;
+        true
+    }
+
+    /// Watching the computer play itself: let the AI hand on the axis after
+    /// the player's seat took a wildcard banana. Returns `false` if no choice
+    /// is pending.
+    pub fn choose_watched_axis(&mut self) -> bool {
+        if !self.choosing_axis {
+            return false;
+        }
+        self.round_watched = true;
+        let axis = ai::banana_axis(
+            &self.board,
+            self.selection,
+            self.tower_computer,
+            self.round_target,
+            self.round_win_rule,
+        );
+        self.choose_axis(axis.unwrap_or(self.selection))
+    }
+
     /// Let the AI pick a move. Returns the chosen (col, row).
     pub fn compute_ai_move(&self) -> (usize, usize) {
-        ai::calculate_move(
-            self.ai_level,
+        self.compute_ai_decision().chosen
+    }
+
+    /// Like [`Self::compute_ai_move`], but with the scores of all candidates.
+    /// Ghost and opening-book moves are not weighed and list none.
+    pub fn compute_ai_decision(&self) -> ai::MoveDecision {
+        self.compute_ai_decision_with_progress(&mut |_| {})
+    }
+
+    /// Like [`Self::compute_ai_decision`], reporting how far the search
+    /// has got to `progress` (see [`ai::decide_move_with_progress`]).
+    pub fn compute_ai_decision_with_progress(
+        &self,
+        progress: &mut dyn FnMut(ai::SearchProgress),
+    ) -> ai::MoveDecision {
+        if let Some(ghost_move) = self.ghost_move() {
+            return ai::MoveDecision::unscored(ghost_move);
+        }
+        self.decide(
+            self.round_ai_level(),
+            (self.tower_computer, self.tower_player),
+            progress,
+        )
+    }
+
+    /// The move of the AI in the player's seat at `watch_level`, when the
+    /// player watches the computer play itself. Reports like
+    /// [`Self::compute_ai_decision_with_progress`].
+    pub fn compute_watched_decision_with_progress(
+        &self,
+        progress: &mut dyn FnMut(ai::SearchProgress),
+    ) -> ai::MoveDecision {
+        self.decide(
+            ai::clamp_level(self.watch_level),
+            (self.tower_player, self.tower_computer),
+            progress,
+        )
+    }
+
+    /// Search a move at `level` for the side whose tower is the first of `towers`.
+    fn decide(
+        &self,
+        level: i32,
+        (tower_self, tower_opponent): (i32, i32),
+        progress: &mut dyn FnMut(ai::SearchProgress),
+    ) -> ai::MoveDecision {
+        if self.moves_made == 0 && level >= ai::FIRST_SEARCH_LEVEL {
+            return ai::MoveDecision::unscored(ai::opening_move(
+                &self.board,
+                self.selection,
+                &mut self.ai_rng(),
+            ));
+        }
+        ai::decide_move_with_progress(
+            level,
+            self.round_ai_personality(),
             &self.board,
             self.selection,
-            self.tower_computer,
-            self.tower_player,
+            tower_self,
+            tower_opponent,
+            self.round_target,
+            self.round_win_rule,
+            self.round_banana_rule,
+            &mut self.ai_rng(),
+            progress,
         )
     }
 
+    /// The generator for the computer's next move. It depends only on the
+    /// round's seed and the moves made, so a search on a copy of the state
+    /// draws the same numbers and a seeded round replays the same way.
+    pub fn ai_rng(&self) -> StdRng {
+        // Mixed, not added: the next round's seed is this one's plus one.
+        StdRng::seed_from_u64(self.round_ai_seed ^ splitmix64(self.moves_made.into()))
+    }
+
+    /// The ghost's next move: the player's move of the replayed run with the
+    /// same number, if the round is on that run's board and the move is
+    /// possible here. `None` leaves the move to the AI.
+    pub fn ghost_move(&self) -> Option<(usize, usize)> {
+        let ghost = self
+            .round_ghost
+            .as_ref()
+            .filter(|ghost| Some(ghost.board) == self.round_seed)?;
+        let replies = self.move_log.iter().filter(|m| !m.by_player).count();
+        ghost
+            .moves
+            .get(replies)
+            .copied()
+            .filter(|&(col, row)| self.is_valid_move(col, row))
+    }
+
+    /// The player's line in the current round for a later ghost, if the
+    /// round is against the computer on a generated board.
+    pub fn last_run(&self) -> Option<LastRun> {
+        if self.round_mode != GameMode::VsComputer {
+            return None;
+        }
+        let moves: Vec<_> = self
+            .move_log
+            .iter()
+            .filter(|m| m.by_player)
+            .map(|m| (m.col, m.row))
+            .collect();
+        if moves.is_empty() {
+            return None;
+        }
+        Some(LastRun {
+            board: self.round_seed?,
+            moves,
+        })
+    }
+
+    /// The computer's level in the current round: fixed for the daily puzzle.
+    pub fn round_ai_level(&self) -> i32 {
+        self.round_puzzle
+            .map_or(self.ai_level, |_| DAILY_PUZZLE_LEVEL)
+    }
+
+    /// The computer's personality in the current round: fixed for the daily puzzle.
+    pub fn round_ai_personality(&self) -> AiPersonality {
+        self.round_puzzle
+            .map_or(self.ai_personality, |_| AiPersonality::Balanced)
+    }
+
     /// Let the AI pick and immediately execute a move.
     #[allow(dead_code)]
     pub fn computer_turn(&mut self) {
         if self.outcome != GameOutcome::Running {
             return;
         }
-        let (col, row) = self.compute_ai_move();
-        self.make_move(col, row, false);
+        let decision = self.compute_ai_decision();
+        let (col, row) = decision.chosen;
+        self.make_computer_move(col, row, decision.axis);
     }
 
     /// Calculate and store a suggested move for the player.
     pub fn get_tip(&mut self) {
-        if self.outcome != GameOutcome::Running {
+        if self.outcome != GameOutcome::Running
+            || self.awaiting_initial_selection
+            || self.choosing_axis
+        {
             return;
         }
-        let (col, row) = ai::calculate_move(
+        let (cell, score) = ai::calculate_move_scored(
             ai::MAX_AI_LEVEL,
+            AiPersonality::Balanced,
             &self.board,
             self.selection,
             self.tower_player,
             self.tower_computer,
+            self.round_target,
+            self.round_win_rule,
+            self.round_banana_rule,
         );
-        self.tip = Some((col, row));
+        self.tip = Some(cell);
+        self.tip_score = Some(score);
+    }
+
+    /// Time spent on the current round so far, or in total once it is over.
+    pub fn elapsed(&self) -> Duration {
+        self.round_duration
+            .unwrap_or_else(|| self.started_at.elapsed())
+    }
+
+    /// Every piece left on the active selection is a bomb: whoever moves has to
+    /// lower their own tower and can only pick the smallest damage.
+    pub fn only_harmful_moves(&self) -> bool {
+        if self.outcome != GameOutcome::Running
+            || self.awaiting_initial_selection
+            || self.choosing_axis
+        {
+            return false;
+        }
+        let mut pieces = selection_cells(self.selection, self.board.size())
+            .map(|(col, row)| self.board.get(col, row).kind)
+            .filter(|&kind| kind != CellKind::Empty)
+            .peekable();
+        pieces.peek().is_some() && pieces.all(|kind| kind == CellKind::Bomb)
+    }
+
+    /// A random valid move of the active selection, e.g. when the move clock
+    /// runs out, drawn from [`Self::ai_rng`] so a seeded session plays it
+    /// again. `None` if there is nothing to take.
+    pub fn random_move(&self) -> Option<(usize, usize)> {
+        selection_cells(self.selection, self.board.size())
+            .filter(|&(col, row)| self.is_valid_move(col, row))
+            .choose(&mut self.ai_rng())
+    }
+
+    /// Cells of the active selection that would hand the opponent an immediate
+    /// win: after taking one, some cell on the next selection finishes the
+    /// opponent's round (one-ply lookahead).
+    pub fn threatening_cells(&self) -> Vec<(usize, usize)> {
+        if self.outcome != GameOutcome::Running
+            || self.awaiting_initial_selection
+            || self.choosing_axis
+        {
+            return Vec::new();
+        }
+        let mover = self.player_one_to_move();
+        let opponent_wins = if mover {
+            GameOutcome::Lost
+        } else {
+            GameOutcome::Won
+        };
+        // A stripped copy keeps the many clones below cheap and off the disk.
+        let mut scratch = self.clone();
+        scratch.persist_statistics = false;
+        scratch.statistics = Statistics::default();
+        scratch.move_log.clear();
+
+        let size = self.board.size();
+        selection_cells(self.selection, size)
+            .filter(|&(col, row)| {
+                let mut after = scratch.clone();
+                if after.make_move(col, row, mover) != MoveResult::Continue {
+                    return false;
+                }
+                selection_cells(after.selection, size).any(|(c, r)| {
+                    let mut reply = after.clone();
+                    reply.make_move(c, r, !mover) == MoveResult::GameOver
+                        && reply.outcome == opponent_wins
+                })
+            })
+            .collect()
+    }
+
+    /// Take back the player's last move together with the computer's replies
+    /// (in a two-player round: the last move). `false` if there is nothing
+    /// to take back, the round is over or it is the daily puzzle.
+    pub fn undo(&mut self) -> bool {
+        if self.outcome != GameOutcome::Running || self.round_puzzle.is_some() {
+            return false;
+        }
+        let keep = match self.round_mode {
+            GameMode::VsComputer => self.move_log.iter().rposition(|m| m.by_player),
+            GameMode::TwoPlayer => self.move_log.len().checked_sub(1),
+        };
+        let Some(keep) = keep else {
+            return false;
+        };
+        let previous = self.replay_to(keep);
+        self.board = previous.board;
+        self.selection = previous.selection;
+        self.choosing_axis = previous.choosing_axis;
+        self.tower_player = previous.tower_player;
+        self.tower_computer = previous.tower_computer;
+        self.moves_made -= (self.move_log.len() - keep) as u32;
+        self.move_log.truncate(keep);
+        self.tip = None;
+        self.tip_score = None;
+        self.keyboard_cursor = None;
+        self.preview_selection = None;
+        true
     }
 
     /// Player resigns the current game.
@@ -169,9 +793,23 @@ impl GameState {
         self.finish(GameOutcome::Lost);
     }
 
+    /// Both players agree to end a two-player round as a draw. Returns `false`
+    /// outside a running two-player round.
+    pub fn agree_draw(&mut self) -> bool {
+        if self.round_mode != GameMode::TwoPlayer || self.outcome != GameOutcome::Running {
+            return false;
+        }
+        self.finish(GameOutcome::Drawn);
+        true
+    }
+
     /// Update the hover position (for highlighting).
     pub fn update_hover(&mut self, col: usize, row: usize) {
-        if col >= BOARD_SIZE || row >= BOARD_SIZE {
+        if col >= self.board.size()
+            || row >= self.board.size()
+            || self.awaiting_initial_selection
+            || self.choosing_axis
+        {
             self.hovered = None;
             return;
         }
@@ -186,12 +824,319 @@ impl GameState {
         self.hovered = None;
     }
 
+    /// Step the "what-if" axis to the next (`forward`) or previous axis the
+    /// coming move could open: any axis while the opening one is chosen,
+    /// otherwise the ones perpendicular to the active selection. The first
+    /// step starts at the axis through `at`, the cell under the pointer.
+    pub fn scroll_preview_selection(&mut self, forward: bool, at: Option<(usize, usize)>) {
+        if self.outcome != GameOutcome::Running {
+            self.preview_selection = None;
+            return;
+        }
+        let size = self.board.size();
+        let columns = (0..size).map(Selection::Column);
+        let rows = (0..size).map(Selection::Row);
+        let any_axis = self.awaiting_initial_selection || self.choosing_axis;
+        let axes: Vec<Selection> = match (any_axis, self.selection) {
+            (true, _) => columns.chain(rows).collect(),
+            (false, Selection::Row(_)) => columns.collect(),
+            (false, Selection::Column(_)) => rows.collect(),
+        };
+        let current = self
+            .preview_selection
+            .and_then(|preview| axes.iter().position(|&axis| axis == preview));
+        let index = match (current, at) {
+            (Some(i), _) if forward => (i + 1) % axes.len(),
+            (Some(i), _) => (i + axes.len() - 1) % axes.len(),
+            (None, Some((col, row))) => axes
+                .iter()
+                .position(|&axis| axis == Selection::Column(col) || axis == Selection::Row(row))
+                .unwrap_or(0),
+            (None, None) if forward => 0,
+            (None, None) => axes.len() - 1,
+        };
+        self.preview_selection = Some(axes[index]);
+    }
+
+    pub fn clear_preview_selection(&mut self) {
+        self.preview_selection = None;
+    }
+
+    /// Move the keyboard cursor to the next (`forward`) or previous non-empty cell
+    /// of the active selection, wrapping at the ends of the axis. A cursor that is
+    /// off the axis starts from the axis' first or last cell.
+    pub fn move_keyboard_cursor(&mut self, forward: bool) {
+        if self.outcome != GameOutcome::Running {
+            return;
+        }
+        let size = self.board.size();
+        let coords = |i: usize| match self.selection {
+            Selection::Column(c) => (c, i),
+            Selection::Row(r) => (i, r),
+        };
+        let current = self
+            .keyboard_cursor
+            .and_then(|(col, row)| match self.selection {
+                Selection::Column(c) if col == c => Some(row),
+                Selection::Row(r) if row == r => Some(col),
+                _ => None,
+            });
+        let start = match (current, forward) {
+            (Some(i), true) => i + 1,
+            (Some(i), false) => i + size - 1,
+            (None, true) => 0,
+            (None, false) => size - 1,
+        };
+        for step in 0..size {
+            let i = if forward {
+                (start + step) % size
+            } else {
+                (start + size - step) % size
+            };
+            let (col, row) = coords(i);
+            if self.board.get(col, row).kind != CellKind::Empty {
+                self.keyboard_cursor = Some((col, row));
+                return;
+            }
+        }
+    }
+
+    /// Continue a saved round in place of the current one. Settings, statistics
+    /// and the session's round counter stay as they are.
+    pub fn resume_round(&mut self, saved: GameState) {
+        self.board = saved.board;
+        self.selection = saved.selection;
+        self.tower_player = saved.tower_player;
+        self.tower_computer = saved.tower_computer;
+        self.round_target = saved.round_target;
+        self.round_win_rule = saved.round_win_rule;
+        self.round_banana_rule = saved.round_banana_rule;
+        self.choosing_axis = saved.choosing_axis;
+        self.round_mode = saved.round_mode;
+        self.round_puzzle = saved.round_puzzle;
+        self.round_seed = saved.round_seed;
+        self.round_start_towers = saved.round_start_towers;
+        self.round_ghost = saved.round_ghost;
+        self.round_watched = saved.round_watched;
+        self.round_pasted = saved.round_pasted;
+        self.round_ai_seed = saved.round_ai_seed;
+        self.outcome = saved.outcome;
+        self.moves_made = saved.moves_made;
+        self.move_log = saved.move_log;
+        self.started_at = saved.started_at;
+        self.round_duration = saved.round_duration;
+        self.tip = None;
+        self.tip_score = None;
+        self.hovered = None;
+        self.keyboard_cursor = None;
+        self.preview_selection = None;
+        self.awaiting_initial_selection = false;
+    }
+
+    /// Rebuild the round as it was after the first `steps` logged moves, starting
+    /// from the initial board (recovered by putting the logged pieces back).
+    /// The result is meant for display only and never touches the disk.
+    pub fn replay_to(&self, steps: usize) -> GameState {
+        let mut board = self.board.clone();
+        for m in &self.move_log {
+            board.set(
+                m.col,
+                m.row,
+                Cell {
+                    kind: m.kind,
+                    value: m.value,
+                },
+            );
+        }
+        let mut replay = GameState::with_board_size(board.size());
+        replay.round_target = self.round_target;
+        replay.round_win_rule = self.round_win_rule;
+        replay.round_banana_rule = self.round_banana_rule;
+        replay.round_mode = self.round_mode;
+        replay.round_puzzle = self.round_puzzle;
+        (replay.tower_player, replay.tower_computer) = self.round_start_towers;
+        replay.selection = self
+            .move_log
+            .first()
+            .map_or(self.selection, |m| m.selection_before);
+        replay.board = board;
+        for m in self.move_log.iter().take(steps) {
+            // Wildcard bananas: the log knows which axis either side picked.
+            if replay.choosing_axis {
+                replay.choose_axis(m.selection_before);
+            } else {
+                replay.selection = m.selection_before;
+            }
+            replay.make_move(m.col, m.row, m.by_player);
+        }
+        // The computer's last hand-off came from its search, not from the
+        // rule of thumb `make_move` applies: the round went on with it.
+        if !replay.choosing_axis {
+            replay.selection = self
+                .move_log
+                .get(steps)
+                .map_or(self.selection, |m| m.selection_before);
+        }
+        replay
+    }
+
+    /// The keyboard cursor cell if it can be played right now.
+    pub fn keyboard_target(&self) -> Option<(usize, usize)> {
+        self.keyboard_cursor
+            .filter(|&(col, row)| self.is_valid_move(col, row))
+    }
+
     fn finish(&mut self, outcome: GameOutcome) {
         self.outcome = outcome;
-        self.statistics.record(outcome);
-        // Persist updated statistics; ignore errors to avoid breaking game flow.
-        let _ = crate::storage::save_statistics(&self.statistics);
+        let duration = self.started_at.elapsed();
+        self.round_duration = Some(duration);
+        if self.round_watched || self.round_pasted {
+            return;
+        }
+        match self.round_mode {
+            GameMode::VsComputer => {
+                self.statistics.record(outcome, self.round_ai_level());
+                self.statistics.record_time(outcome, duration.as_secs());
+            }
+            GameMode::TwoPlayer => self.statistics.record_two_player(outcome),
+        }
+        let adapted = self.round_mode == GameMode::VsComputer && self.adapt_level();
+        if let Some(date) = self.round_puzzle {
+            let margin = self.tower_player - self.tower_computer;
+            self.statistics.record_daily(date, outcome, margin);
+        }
+        if self.persist_statistics {
+            // Persist updated statistics; ignore errors to avoid breaking game flow.
+            let _ = crate::storage::save_statistics(&self.statistics);
+            if let Some(run) = self.last_run() {
+                let _ = crate::storage::save_last_run(&run);
+            }
+            if adapted {
+                let mut settings = crate::storage::load_settings();
+                settings.ai_level = self.ai_level;
+                let _ = crate::storage::save_settings(&settings);
+            }
+        }
     }
+
+    /// Adaptive difficulty: one level up after every [`ADAPTIVE_STREAK`] wins
+    /// in a row, one down after as many losses, within the AI's levels. The
+    /// daily puzzle plays its own level and leaves it alone. Returns whether
+    /// the level changed.
+    fn adapt_level(&mut self) -> bool {
+        let streak = self.statistics.current_streak;
+        if !self.adaptive_difficulty
+            || self.round_puzzle.is_some()
+            || streak == 0
+            || streak % ADAPTIVE_STREAK != 0
+        {
+            return false;
+        }
+        let level = ai::clamp_level(self.ai_level + streak.signum());
+        if level == self.ai_level {
+            return false;
+        }
+        self.ai_level = level;
+        self.adapted_level = Some(level);
+        true
+    }
+
+    /// Raise or lower the computer's level by `delta` within the AI's levels,
+    /// keeping the board as it is: the next computer move plays at the new
+    /// level. Announced like adaptive difficulty. Returns whether the level
+    /// changed.
+    pub fn step_ai_level(&mut self, delta: i32) -> bool {
+        let level = ai::clamp_level(self.ai_level + delta);
+        if level == self.ai_level {
+            return false;
+        }
+        self.ai_level = level;
+        self.adapted_level = Some(level);
+        true
+    }
+}
+
+/// Height of a tower of `tower` rows after taking `cell`: stones add, bombs
+/// subtract, clamped to `0..=target`.
+fn tower_after(tower: i32, cell: Cell, target: i32) -> i32 {
+    match cell.kind {
+        CellKind::Stone => (tower + cell.value + 1).min(target),
+        CellKind::Bomb => (tower - cell.value - 1).max(0),
+        _ => tower,
+    }
+}
+
+/// One step of the SplitMix64 generator: spreads nearby numbers over all
+/// 64 bits, so mixing a move number into a seed leaves no overlap with
+/// neighbouring seeds.
+fn splitmix64(x: u64) -> u64 {
+    let mut z = x.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// All cells of `selection` on a `size`×`size` board, empty or not.
+fn selection_cells(selection: Selection, size: usize) -> impl Iterator<Item = (usize, usize)> {
+    (0..size).map(move |i| ai::sel_coords(selection, i))
+}
+
+/// Let two AI levels play one full round against each other on a default-size
+/// board. `level_a` takes the player's seat and moves first, so the outcome is
+/// from its point of view. Returns the outcome and the number of moves played.
+///
+/// The same `seed` gives the same board and the same moves, except for the
+/// deepest level whose time budget can end its search at different depths.
+pub fn play_out(level_a: i32, level_b: i32, seed: u64) -> (GameOutcome, u32) {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut state = GameState::new();
+    let (board, selection) =
+        Board::new_random_with(state.board_size, &BoardGenConfig::default(), &mut rng);
+    state.board = board;
+    state.selection = selection;
+
+    let mut a_to_move = true;
+    while state.outcome == GameOutcome::Running {
+        let (level, tower_self, tower_opponent) = if a_to_move {
+            (level_a, state.tower_player, state.tower_computer)
+        } else {
+            (level_b, state.tower_computer, state.tower_player)
+        };
+        let (col, row) = if state.moves_made == 0 && level >= ai::FIRST_SEARCH_LEVEL {
+            ai::opening_move(&state.board, state.selection, &mut rng)
+        } else {
+            ai::calculate_move_with_rng(
+                level,
+                AiPersonality::Balanced,
+                &state.board,
+                state.selection,
+                tower_self,
+                tower_opponent,
+                state.round_target,
+                state.round_win_rule,
+                state.round_banana_rule,
+                &mut rng,
+            )
+            .0
+        };
+        if state.make_move(col, row, a_to_move) == MoveResult::Invalid {
+            // Cannot happen while the selection has pieces; guard against looping forever.
+            break;
+        }
+        a_to_move = !a_to_move;
+        // The player's seat is a computer here too: it picks a wildcard axis at once.
+        if state.choosing_axis {
+            let axis = ai::banana_axis(
+                &state.board,
+                state.selection,
+                state.tower_computer,
+                state.round_target,
+                state.round_win_rule,
+            );
+            state.choose_axis(axis.unwrap_or(state.selection));
+        }
+    }
+    (state.outcome, state.moves_made)
 }
 
 /// Result of a move.
@@ -204,3 +1149,792 @@ pub enum MoveResult {
     /// Move applied, game is now over.
     GameOver,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A running round on the board of `cells` (indexed `[col][row]`) with
+    /// `selection` active and both towers empty.
+    fn state_with_board(cells: Vec<Vec<Cell>>, selection: Selection) -> GameState {
+        let mut state = GameState::with_board_size(cells.len());
+        state.board = Board::from_cells(cells);
+        state.selection = selection;
+        state.awaiting_initial_selection = false;
+        state
+    }
+
+    /// A round on a 6×6 board of one-point stones, except for `special` at (0, 0).
+    /// Row 0 is selected and the towers start at the given heights.
+    fn controlled_state(special: Cell, towers: (i32, i32)) -> GameState {
+        let stone = Cell {
+            kind: CellKind::Stone,
+            value: 0,
+        };
+        let mut cells = vec![vec![stone; 6]; 6];
+        cells[0][0] = special;
+        let mut state = state_with_board(cells, Selection::Row(0));
+        (state.tower_player, state.tower_computer) = towers;
+        state
+    }
+
+    #[test]
+    fn invalid_moves_are_rejected() {
+        let stone = Cell {
+            kind: CellKind::Stone,
+            value: 1,
+        };
+        let mut cells = vec![vec![stone; 4]; 4];
+        cells[2][1] = Cell::default();
+        let mut state = state_with_board(cells, Selection::Column(2));
+        assert!(state.is_valid_move(2, 0));
+        assert!(state.is_valid_move(2, 3));
+
+        assert!(!state.is_valid_move(1, 0), "off the active column");
+        assert!(!state.is_valid_move(3, 2), "off the active column");
+        assert!(!state.is_valid_move(2, 1), "empty cell on the axis");
+        assert!(!state.is_valid_move(2, 4), "below the board");
+        assert!(!state.is_valid_move(4, 0), "right of the board");
+        assert!(!state.is_valid_move(usize::MAX, usize::MAX));
+
+        state.selection = Selection::Row(1);
+        assert!(state.is_valid_move(0, 1));
+        assert!(!state.is_valid_move(2, 1), "empty cell on the axis");
+        assert!(!state.is_valid_move(0, 2), "off the active row");
+
+        for outcome in [GameOutcome::Won, GameOutcome::Lost, GameOutcome::Drawn] {
+            state.outcome = outcome;
+            assert!(
+                !state.is_valid_move(0, 1),
+                "after the round ended: {outcome:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn big_stone_clamps_at_target_and_wins() {
+        let mut state = controlled_state(
+            Cell {
+                kind: CellKind::Stone,
+                value: 3,
+            },
+            (18, 0),
+        );
+        assert_eq!(state.make_move(0, 0, true), MoveResult::GameOver);
+        assert_eq!(state.tower_player, DEFAULT_TARGET_HEIGHT);
+        assert_eq!(state.outcome, GameOutcome::Won);
+    }
+
+    #[test]
+    fn tallest_at_end_caps_the_tower_and_plays_on() {
+        let big_stone = Cell {
+            kind: CellKind::Stone,
+            value: 3,
+        };
+        let mut state = controlled_state(big_stone, (18, 0));
+        state.round_win_rule = WinRule::TallestAtEnd;
+        assert_eq!(state.make_move(0, 0, true), MoveResult::Continue);
+        assert_eq!(state.tower_player, DEFAULT_TARGET_HEIGHT);
+        assert_eq!(state.make_move(0, 1, false), MoveResult::Continue);
+        // A full tower stays full, and the round only ends with its axis.
+        assert_eq!(state.make_move(1, 1, true), MoveResult::Continue);
+        assert_eq!(state.tower_player, DEFAULT_TARGET_HEIGHT);
+        for col in 0..6 {
+            for row in 0..6 {
+                state.board.clear(col, row);
+            }
+        }
+        state.board.set(1, 2, big_stone);
+        assert_eq!(state.make_move(1, 2, false), MoveResult::GameOver);
+        assert_eq!(state.outcome, GameOutcome::Won);
+    }
+
+    #[test]
+    fn big_bomb_clamps_at_zero() {
+        let mut state = controlled_state(
+            Cell {
+                kind: CellKind::Bomb,
+                value: 3,
+            },
+            (1, 5),
+        );
+        assert_eq!(state.make_move(0, 0, true), MoveResult::Continue);
+        assert_eq!(state.tower_player, 0);
+        assert_eq!(state.tower_computer, 5);
+    }
+
+    #[test]
+    fn banana_keeps_the_axis_while_stones_and_bombs_flip_it() {
+        let banana = Cell {
+            kind: CellKind::Banana,
+            value: 0,
+        };
+        let mut state = controlled_state(banana, (0, 0));
+        state.make_move(0, 0, true);
+        assert_eq!(state.selection, Selection::Row(0));
+
+        for kind in [CellKind::Stone, CellKind::Bomb] {
+            let mut state = controlled_state(Cell { kind, value: 0 }, (3, 3));
+            state.make_move(0, 0, true);
+            assert_eq!(state.selection, Selection::Column(0), "{kind:?}");
+        }
+    }
+
+    #[test]
+    fn projected_tower_matches_the_move() {
+        let big_stone = Cell {
+            kind: CellKind::Stone,
+            value: 3,
+        };
+        let state = controlled_state(big_stone, (18, 4));
+        assert_eq!(state.projected_tower(0, 0), Some(DEFAULT_TARGET_HEIGHT));
+        assert_eq!(state.projected_tower(1, 0), Some(19));
+        assert_eq!(state.projected_tower(0, 1), None, "not on the active row");
+
+        let bomb = Cell {
+            kind: CellKind::Bomb,
+            value: 1,
+        };
+        let mut state = controlled_state(bomb, (1, 4));
+        assert_eq!(state.projected_tower(0, 0), Some(0));
+        state.board.set(
+            1,
+            0,
+            Cell {
+                kind: CellKind::Banana,
+                value: 0,
+            },
+        );
+        assert_eq!(state.projected_tower(1, 0), None, "bananas change no tower");
+        assert_eq!(state.make_move(0, 0, true), MoveResult::Continue);
+        assert_eq!(state.tower_player, 0);
+    }
+
+    #[test]
+    fn an_axis_of_bananas_only_ends_the_round() {
+        let banana = Cell {
+            kind: CellKind::Banana,
+            value: 0,
+        };
+        let stone = Cell {
+            kind: CellKind::Stone,
+            value: 0,
+        };
+        let mut state = controlled_state(stone, (4, 3));
+        // Column 0, opened by taking the stone of row 0, holds bananas and one stone.
+        for row in 1..5 {
+            state.board.set(0, row, banana);
+        }
+        assert!(!state.board.axis_only_bananas(Selection::Column(0)));
+        let mut only_bananas = state.clone();
+        only_bananas.board.set(0, 5, banana);
+        let mut wildcards = only_bananas.clone();
+        wildcards.round_banana_rule = BananaRule::FreeChoice;
+
+        assert_eq!(state.make_move(0, 0, true), MoveResult::Continue);
+        assert_eq!(only_bananas.make_move(0, 0, true), MoveResult::GameOver);
+        assert_eq!(only_bananas.tower_player, 5);
+        assert_eq!(only_bananas.outcome, GameOutcome::Won);
+
+        // A wildcard banana leads on to the stones of the other columns.
+        assert_eq!(wildcards.make_move(0, 0, true), MoveResult::Continue);
+        assert_eq!(wildcards.make_move(0, 1, false), MoveResult::Continue);
+        assert!(wildcards.selection != Selection::Column(0));
+        // With nothing but bananas left, no axis can change a tower.
+        let mut bare = wildcards.clone();
+        for col in 1..6 {
+            for row in 0..6 {
+                bare.board.set(col, row, banana);
+            }
+        }
+        bare.selection = Selection::Column(0);
+        assert_eq!(bare.make_move(0, 2, true), MoveResult::GameOver);
+    }
+
+    #[test]
+    fn player_chosen_axis_blocks_moves_until_set() {
+        let mut state = GameState::new();
+        state.player_picks_axis = true;
+        state.new_game();
+        assert!(state.awaiting_initial_selection);
+        assert!(!state.is_valid_move(2, 3));
+
+        assert!(state.choose_initial_selection(Selection::Row(3)));
+        assert!(!state.awaiting_initial_selection);
+        assert_eq!(state.selection, Selection::Row(3));
+        assert!(state.is_valid_move(2, 3));
+        // Only one choice per round.
+        assert!(!state.choose_initial_selection(Selection::Column(0)));
+    }
+
+    #[test]
+    fn replay_reconstructs_every_step() {
+        let mut state = GameState::new();
+        let start = state.clone();
+        let mut snapshots = vec![start.clone()];
+        let mut is_player = true;
+        while state.outcome == GameOutcome::Running && state.moves_made < 12 {
+            let (col, row) = state.compute_ai_move();
+            state.make_move(col, row, is_player);
+            snapshots.push(state.clone());
+            is_player = !is_player;
+        }
+        assert_eq!(state.move_log.len(), snapshots.len() - 1);
+
+        for (steps, expected) in snapshots.iter().enumerate() {
+            let replay = state.replay_to(steps);
+            assert_eq!(replay.selection, expected.selection, "step {steps}");
+            assert_eq!(replay.tower_player, expected.tower_player);
+            assert_eq!(replay.tower_computer, expected.tower_computer);
+            for col in 0..expected.board.size() {
+                for row in 0..expected.board.size() {
+                    assert_eq!(
+                        replay.board.get(col, row).kind,
+                        expected.board.get(col, row).kind
+                    );
+                }
+            }
+        }
+
+        state.new_game();
+        assert!(state.move_log.is_empty());
+    }
+
+    #[test]
+    fn two_player_round_alternates_seats_and_keeps_its_own_statistics() {
+        let mut state = GameState::new();
+        state.game_mode = GameMode::TwoPlayer;
+        state.opener = OpenerMode::Alternate;
+        state.new_game();
+        assert!(!state.computer_opens());
+
+        let mut expected_player_one = true;
+        while state.outcome == GameOutcome::Running {
+            assert_eq!(state.player_one_to_move(), expected_player_one);
+            let (col, row) = state.compute_ai_move();
+            state.make_move(col, row, state.player_one_to_move());
+            expected_player_one = !expected_player_one;
+        }
+
+        let tp = state.statistics.two_player;
+        assert_eq!(tp.player_one_wins + tp.player_two_wins + tp.draws, 1);
+        assert_eq!(state.statistics.games_played, 0);
+    }
+
+    #[test]
+    fn daily_puzzle_repeats_its_board_and_keeps_the_best_result() {
+        let mut state = GameState::new();
+        state.ai_level = 0;
+        state.start_daily_puzzle(20261017);
+        let (board, selection) = (state.board.clone(), state.selection);
+        assert_eq!(state.round_ai_level(), DAILY_PUZZLE_LEVEL);
+        assert!(!state.computer_opens());
+        state.surrender();
+
+        state.start_daily_puzzle(20261017);
+        assert_eq!(
+            (format!("{:?}", state.board), state.selection),
+            (format!("{:?}", board), selection)
+        );
+        state.tower_player = 5;
+        state.tower_computer = 2;
+        state.finish(GameOutcome::Drawn);
+
+        let best = state.statistics.daily[&20261017];
+        assert_eq!(
+            (best.outcome, best.margin, best.attempts),
+            (GameOutcome::Drawn, 3, 2)
+        );
+        assert_eq!(
+            state.statistics.per_level[DAILY_PUZZLE_LEVEL as usize].draws,
+            1
+        );
+
+        state.new_game();
+        assert_eq!(state.round_puzzle, None);
+        assert_eq!(state.round_ai_level(), 0);
+    }
+
+    #[test]
+    fn threats_are_cells_that_open_a_winning_line() {
+        let piece = |kind, value| Cell { kind, value };
+        let mut cells = vec![vec![Cell::default(); 6]; 6];
+        cells[0][0] = piece(CellKind::Stone, 0);
+        cells[1][0] = piece(CellKind::Stone, 0);
+        cells[2][0] = piece(CellKind::Banana, 0);
+        // Column 0 offers the computer the stone it needs, column 1 only a bomb.
+        cells[0][3] = piece(CellKind::Stone, 1);
+        cells[1][4] = piece(CellKind::Bomb, 0);
+        let mut state = GameState::with_board_size(6);
+        state.board = Board::from_cells(cells);
+        state.selection = Selection::Row(0);
+        // Level with the computer, the player never loses by running out of moves.
+        state.tower_player = DEFAULT_TARGET_HEIGHT - 2;
+        state.tower_computer = DEFAULT_TARGET_HEIGHT - 2;
+
+        assert_eq!(state.threatening_cells(), vec![(0, 0)]);
+
+        // Out of reach once the computer needs more than one stone.
+        state.tower_computer = DEFAULT_TARGET_HEIGHT - 3;
+        assert!(state.threatening_cells().is_empty());
+    }
+
+    #[test]
+    fn a_line_of_bombs_only_offers_harmful_moves() {
+        let bomb = |value| Cell {
+            kind: CellKind::Bomb,
+            value,
+        };
+        let mut cells = vec![vec![Cell::default(); 6]; 6];
+        cells[1][0] = bomb(3);
+        cells[3][0] = bomb(0);
+        cells[4][0] = bomb(2);
+        let mut state = GameState::with_board_size(6);
+        state.board = Board::from_cells(cells);
+        state.selection = Selection::Row(0);
+        state.tower_computer = 5;
+        assert!(state.only_harmful_moves());
+
+        // The greedy level keeps the damage as small as possible.
+        state.ai_level = 2;
+        assert_eq!(state.compute_ai_move(), (3, 0));
+
+        state.board.set(
+            5,
+            0,
+            Cell {
+                kind: CellKind::Banana,
+                value: 0,
+            },
+        );
+        assert!(!state.only_harmful_moves());
+        state.selection = Selection::Row(1);
+        assert!(
+            !state.only_harmful_moves(),
+            "an empty line offers no moves at all"
+        );
+    }
+
+    #[test]
+    fn random_move_stays_on_the_active_axis() {
+        let mut state = controlled_state(Cell::default(), (0, 0));
+        for _ in 0..20 {
+            let (col, row) = state.random_move().unwrap();
+            assert_eq!(row, 0);
+            assert_ne!(col, 0, "the empty cell is no move");
+        }
+        state.awaiting_initial_selection = true;
+        assert_eq!(state.random_move(), None);
+    }
+
+    #[test]
+    fn ghost_replays_the_last_run_while_it_can() {
+        let mut state = controlled_state(Cell::default(), (0, 0));
+        state.round_ghost = Some(LastRun {
+            board: state.round_seed.unwrap(),
+            moves: vec![(1, 3), (5, 5)],
+        });
+        // The player's stone flips the axis to column 1, where the ghost's
+        // first move is possible.
+        state.make_move(1, 0, true);
+        assert_eq!(state.ghost_move(), Some((1, 3)));
+        assert_eq!(state.compute_ai_move(), (1, 3));
+        state.make_move(1, 3, false);
+        // Row 3, then column 4: the ghost's second move is off the axis.
+        state.make_move(4, 3, true);
+        assert_eq!(state.ghost_move(), None);
+
+        let run = state.last_run().unwrap();
+        assert_eq!(run.moves, vec![(1, 0), (4, 3)]);
+        // On another board the ghost stays silent.
+        state.round_seed = None;
+        assert_eq!(state.last_run(), None);
+        state.round_ghost.as_mut().unwrap().moves[1] = (4, 2);
+        assert_eq!(state.ghost_move(), None);
+    }
+
+    #[test]
+    fn ghost_rounds_replay_the_board_of_the_run() {
+        let mut state = GameState::new();
+        state.new_game();
+        let run = LastRun {
+            board: state.round_seed.unwrap(),
+            moves: vec![(0, 0)],
+        };
+        let mut ghost = GameState::new();
+        ghost.start_ghost_round(run.clone());
+        assert_eq!(ghost.position_code(), state.position_code());
+        assert_eq!(ghost.round_ghost, Some(run));
+        assert!(!ghost.computer_opens());
+        ghost.new_game();
+        assert_eq!(ghost.round_ghost, None);
+    }
+
+    #[test]
+    fn undo_takes_back_the_players_move_and_the_reply() {
+        let mut state = controlled_state(Cell::default(), (0, 0));
+        assert!(!state.undo(), "nothing to take back");
+        let before = state.position_code();
+        state.make_move(1, 0, true);
+        state.make_move(1, 3, false);
+        let after_first = state.position_code();
+        state.make_move(4, 3, true);
+        state.make_move(4, 1, false);
+        assert_eq!(state.moves_made, 4);
+
+        assert!(state.undo());
+        assert_eq!(state.position_code(), after_first);
+        assert_eq!((state.moves_made, state.move_log.len()), (2, 2));
+        assert!(state.undo());
+        assert_eq!(state.position_code(), before);
+        assert!(!state.undo());
+
+        // Two players take back one move at a time.
+        state.round_mode = GameMode::TwoPlayer;
+        state.make_move(1, 0, true);
+        state.make_move(1, 3, false);
+        assert!(state.undo());
+        assert_eq!(state.move_log.len(), 1);
+
+        state.surrender();
+        assert!(!state.undo(), "a finished round stays as it ended");
+    }
+
+    #[test]
+    fn undo_keeps_the_towers_a_pasted_round_started_with() {
+        let code = controlled_state(Cell::default(), (7, 5)).position_code();
+        let mut state = GameState::from_position_code(&code).unwrap();
+        state.make_move(1, 0, true);
+        assert_eq!((state.tower_player, state.tower_computer), (8, 5));
+        assert_eq!(
+            (
+                state.replay_to(0).tower_player,
+                state.replay_to(1).tower_player
+            ),
+            (7, 8)
+        );
+
+        assert!(state.undo());
+        assert_eq!((state.tower_player, state.tower_computer), (7, 5));
+        assert_eq!(state.position_code(), code);
+    }
+
+    #[test]
+    fn only_two_players_can_agree_to_a_draw() {
+        let mut state = controlled_state(Cell::default(), (0, 0));
+        assert!(!state.agree_draw(), "the computer does not take offers");
+        assert_eq!(state.outcome, GameOutcome::Running);
+
+        state.round_mode = GameMode::TwoPlayer;
+        state.make_move(1, 0, true);
+        assert!(state.agree_draw());
+        assert_eq!(state.outcome, GameOutcome::Drawn);
+        assert_eq!(state.statistics.two_player.draws, 1);
+        assert!(!state.is_valid_move(1, 1), "the board is locked");
+        assert!(!state.agree_draw(), "already over");
+        assert_eq!(state.statistics.two_player.draws, 1);
+    }
+
+    #[test]
+    fn the_watched_seat_plays_for_the_player() {
+        let bomb = |value| Cell {
+            kind: CellKind::Bomb,
+            value,
+        };
+        let mut cells = vec![vec![Cell::default(); 6]; 6];
+        cells[1][0] = bomb(3);
+        cells[3][0] = bomb(0);
+        cells[4][0] = bomb(2);
+        cells[2][2] = Cell {
+            kind: CellKind::Stone,
+            value: 0,
+        };
+        let mut state = state_with_board(cells, Selection::Row(0));
+        state.ai_level = 0;
+        state.watch_level = 2;
+        // Only the player's tower can lose anything: the watched seat keeps
+        // its own damage small at its own level.
+        state.tower_player = 5;
+        let decide = |state: &GameState| state.compute_watched_decision_with_progress(&mut |_| {});
+        assert_eq!(decide(&state).chosen, (3, 0));
+
+        assert!(!state.choose_watched_axis(), "no wildcard taken");
+        state.choosing_axis = true;
+        assert!(state.choose_watched_axis());
+        assert!(!state.choosing_axis);
+        assert!(!state.board.axis_only_bananas(state.selection));
+    }
+
+    #[test]
+    fn seeded_rounds_break_ties_the_same_way() {
+        let mut state = controlled_state(Cell::default(), (0, 0));
+        state.ai_level = 2;
+        let mut choices = std::collections::HashSet::new();
+        for seed in 0..20 {
+            state.round_ai_seed = seed;
+            let chosen = state.compute_ai_move();
+            assert_eq!(state.clone().compute_ai_move(), chosen, "seed {seed}");
+            choices.insert(chosen);
+        }
+        assert!(choices.len() > 1, "ties always go the same way");
+
+        // A seeded session deals the same boards and timeout moves again.
+        state.ai_seed = Some(7);
+        state.new_game();
+        let first = state.clone();
+        state.new_game();
+        assert_ne!(state.round_ai_seed, first.round_ai_seed);
+        assert_ne!(state.round_seed, first.round_seed);
+        let mut replay = GameState::with_board_size(6);
+        replay.ai_seed = Some(7);
+        replay.games_started = state.games_started - 2;
+        replay.new_game();
+        assert_eq!(replay.round_ai_seed, first.round_ai_seed);
+        assert_eq!(replay.round_seed, first.round_seed);
+        assert_eq!(replay.random_move(), first.random_move());
+
+        // Consecutive rounds draw from streams of their own.
+        let mut draws = std::collections::HashSet::new();
+        for round_seed in [7, 8] {
+            state.round_ai_seed = round_seed;
+            for moves in 0..20 {
+                state.moves_made = moves;
+                assert!(
+                    draws.insert(rand::RngCore::next_u64(&mut state.ai_rng())),
+                    "{round_seed}/{moves}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn scrolling_previews_axes_without_changing_the_selection() {
+        let mut state = controlled_state(Cell::default(), (0, 0));
+        state.scroll_preview_selection(true, Some((3, 0)));
+        assert_eq!(state.preview_selection, Some(Selection::Column(3)));
+        state.scroll_preview_selection(false, None);
+        state.scroll_preview_selection(false, None);
+        assert_eq!(state.preview_selection, Some(Selection::Column(1)));
+        state.scroll_preview_selection(true, None);
+        assert_eq!(state.selection, Selection::Row(0));
+        state.make_move(2, 0, true);
+        assert_eq!(state.preview_selection, None, "a move ends the preview");
+
+        // The opening axis may be any row or column, wrapping from last to first.
+        state.awaiting_initial_selection = true;
+        state.scroll_preview_selection(false, None);
+        assert_eq!(state.preview_selection, Some(Selection::Row(5)));
+        state.scroll_preview_selection(true, None);
+        assert_eq!(state.preview_selection, Some(Selection::Column(0)));
+        state.clear_preview_selection();
+        assert_eq!(state.preview_selection, None);
+    }
+
+    #[test]
+    fn wildcard_bananas_let_the_taker_pick_the_axis() {
+        let banana = Cell {
+            kind: CellKind::Banana,
+            value: 0,
+        };
+        let mut state = controlled_state(banana, (0, 0));
+        state.board.set(2, 1, banana);
+        state.round_banana_rule = BananaRule::FreeChoice;
+        state.round_mode = GameMode::VsComputer;
+
+        assert_eq!(state.make_move(0, 0, true), MoveResult::Continue);
+        assert!(state.choosing_axis);
+        assert!(
+            !state.is_valid_move(1, 0),
+            "no move before the axis is picked"
+        );
+        assert!(state.replay_to(1).choosing_axis);
+        assert!(state.choose_axis(Selection::Column(2)));
+        assert!(!state.choose_axis(Selection::Row(3)), "only one pick");
+
+        // The computer picks its axis at once, and a replay follows both picks.
+        assert_eq!(state.make_move(2, 1, false), MoveResult::Continue);
+        assert!(!state.choosing_axis);
+        let picked = state.selection;
+        assert_eq!(state.replay_to(2).selection, picked);
+        assert_eq!(state.move_log[1].selection_before, Selection::Column(2));
+
+        state.round_banana_rule = BananaRule::KeepAxis;
+        state.board.set(3, 3, banana);
+        state.selection = Selection::Row(3);
+        state.make_move(3, 3, true);
+        assert_eq!(
+            (state.choosing_axis, state.selection),
+            (false, Selection::Row(3))
+        );
+    }
+
+    #[test]
+    fn the_computer_hands_over_the_axis_its_search_chose() {
+        let banana = Cell {
+            kind: CellKind::Banana,
+            value: 0,
+        };
+        let mut state = controlled_state(banana, (0, 0));
+        state.round_banana_rule = BananaRule::FreeChoice;
+        state.round_mode = GameMode::VsComputer;
+
+        let axis = Some(Selection::Column(4));
+        assert_eq!(state.make_computer_move(0, 0, axis), MoveResult::Continue);
+        assert_eq!(
+            (state.choosing_axis, state.selection),
+            (false, Selection::Column(4))
+        );
+        assert_eq!(state.replay_to(1).selection, Selection::Column(4));
+        assert_eq!(state.make_move(4, 2, true), MoveResult::Continue);
+        assert_eq!(state.replay_to(1).selection, Selection::Column(4));
+        assert_eq!(state.replay_to(2).selection, Selection::Row(2));
+
+        // Without an axis, or with one that cannot be played, the rule of thumb picks.
+        let mut state = controlled_state(banana, (0, 0));
+        state.round_banana_rule = BananaRule::FreeChoice;
+        state.round_mode = GameMode::VsComputer;
+        for row in 0..6 {
+            state.board.set(5, row, banana);
+        }
+        let mut ruled = state.clone();
+        ruled.make_computer_move(0, 0, None);
+        state.make_computer_move(0, 0, Some(Selection::Column(5)));
+        assert_ne!(ruled.selection, Selection::Column(5));
+        assert_eq!(state.selection, ruled.selection);
+    }
+
+    #[test]
+    fn only_bombs_that_cost_height_count_as_harmful() {
+        let bomb = Cell {
+            kind: CellKind::Bomb,
+            value: 1,
+        };
+        let mut state = controlled_state(bomb, (4, 0));
+        state.board.set(
+            2,
+            0,
+            Cell {
+                kind: CellKind::Banana,
+                value: 0,
+            },
+        );
+        assert!(state.is_harmful_move(0, 0));
+        assert!(!state.is_harmful_move(1, 0), "stone");
+        assert!(!state.is_harmful_move(2, 0), "banana");
+        assert!(!state.is_harmful_move(0, 1), "off the active axis");
+        state.tower_player = 0;
+        assert!(!state.is_harmful_move(0, 0), "nothing left to lose");
+    }
+
+    #[test]
+    fn watched_rounds_leave_the_players_records_alone() {
+        let stone = Cell {
+            kind: CellKind::Stone,
+            value: 0,
+        };
+        let mut state = controlled_state(stone, (DEFAULT_TARGET_HEIGHT - 1, 0));
+        state.adaptive_difficulty = true;
+        state.statistics.current_streak = ADAPTIVE_STREAK - 1;
+        state.round_puzzle = Some(20240101);
+        state.round_seed = Some(BoardSeed::random(6, false, BoardGenConfig::default()));
+        state.round_watched = true;
+        let level = state.ai_level;
+        assert_eq!(state.make_move(0, 0, true), MoveResult::GameOver);
+
+        assert_eq!(state.statistics.games_played, 0);
+        assert_eq!(state.statistics.current_streak, ADAPTIVE_STREAK - 1);
+        assert!(state.statistics.daily.is_empty());
+        assert_eq!((state.ai_level, state.adapted_level), (level, None));
+
+        state.new_game();
+        assert!(!state.round_watched, "each round starts as the player's");
+    }
+
+    #[test]
+    fn pasted_rounds_leave_the_players_records_alone() {
+        let stone = Cell {
+            kind: CellKind::Stone,
+            value: 0,
+        };
+        let code = controlled_state(stone, (DEFAULT_TARGET_HEIGHT - 1, 0)).position_code();
+        let mut state = controlled_state(Cell::default(), (0, 0));
+        state.adaptive_difficulty = true;
+        state.statistics.current_streak = ADAPTIVE_STREAK - 1;
+        state.resume_round(GameState::from_position_code(&code).unwrap());
+        let level = state.ai_level;
+        assert_eq!(state.make_move(0, 0, true), MoveResult::GameOver);
+        assert_eq!(state.outcome, GameOutcome::Won);
+
+        assert_eq!(state.statistics.games_played, 0);
+        assert_eq!(state.statistics.current_streak, ADAPTIVE_STREAK - 1);
+        assert_eq!((state.ai_level, state.adapted_level), (level, None));
+
+        state.new_game();
+        assert!(!state.round_pasted, "dealt rounds count again");
+    }
+
+    #[test]
+    fn adaptive_difficulty_follows_the_streak() {
+        let mut state = controlled_state(Cell::default(), (0, 0));
+        state.ai_level = ai::MAX_AI_LEVEL - 1;
+        let play = |state: &mut GameState, outcome| {
+            state.new_game();
+            state.finish(outcome);
+            state.adapted_level.take()
+        };
+        assert_eq!(play(&mut state, GameOutcome::Won), None, "off by default");
+        assert_eq!(play(&mut state, GameOutcome::Won), None);
+        assert_eq!(play(&mut state, GameOutcome::Won), None);
+
+        state.adaptive_difficulty = true;
+        state.statistics.current_streak = 0;
+        assert_eq!(play(&mut state, GameOutcome::Won), None);
+        assert_eq!(play(&mut state, GameOutcome::Won), None);
+        assert_eq!(play(&mut state, GameOutcome::Won), Some(ai::MAX_AI_LEVEL));
+        for _ in 0..3 {
+            assert_eq!(
+                play(&mut state, GameOutcome::Won),
+                None,
+                "already the top level"
+            );
+        }
+        assert_eq!(play(&mut state, GameOutcome::Drawn), None);
+        for _ in 0..2 {
+            assert_eq!(play(&mut state, GameOutcome::Lost), None);
+        }
+        assert_eq!(
+            play(&mut state, GameOutcome::Lost),
+            Some(ai::MAX_AI_LEVEL - 1)
+        );
+        assert_eq!(state.ai_level, ai::MAX_AI_LEVEL - 1);
+    }
+
+    #[test]
+    fn level_steps_stay_within_the_ai_levels_mid_round() {
+        let mut state = controlled_state(Cell::default(), (0, 0));
+        state.ai_level = ai::MAX_AI_LEVEL - 1;
+        let pieces = state.board.piece_counts();
+        assert!(state.step_ai_level(1));
+        assert_eq!(state.adapted_level.take(), Some(ai::MAX_AI_LEVEL));
+        assert!(!state.step_ai_level(1), "already the top level");
+        assert_eq!(state.adapted_level, None);
+        assert_eq!(state.ai_level, ai::MAX_AI_LEVEL);
+        for _ in 0..=ai::MAX_AI_LEVEL {
+            state.step_ai_level(-1);
+        }
+        assert_eq!(state.ai_level, 0);
+        assert_eq!(state.board.piece_counts(), pieces, "the round goes on");
+    }
+
+    #[test]
+    fn play_out_finishes_and_is_reproducible() {
+        for (level_a, level_b) in [(0, 1), (1, 2), (2, 3), (0, 0)] {
+            for seed in 0..3 {
+                let (outcome, moves) = play_out(level_a, level_b, seed);
+                assert_ne!(outcome, GameOutcome::Running);
+                assert!(moves > 0);
+                assert_eq!(play_out(level_a, level_b, seed), (outcome, moves));
+            }
+        }
+    }
+}
@@ -1,3 +1,4 @@
 pub mod field;
 pub mod logic;
+pub mod position;
 pub mod types;
@@ -0,0 +1,263 @@
+use super::field::{Board, MAX_BOARD_SIZE, MIN_BOARD_SIZE};
+use super::logic::GameState;
+use super::types::{
+    BananaRule, Cell, CellKind, Selection, WinRule, MAX_TARGET_HEIGHT, MIN_TARGET_HEIGHT,
+};
+
+/// Format version, the first byte of every position code.
+const CODE_VERSION: u8 = 1;
+
+/// RFC 4648 base32 alphabet; decoding also accepts lower case.
+const ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+impl GameState {
+    /// A short text code of the position for sharing: board, active
+    /// selection, both towers, target height, win rule and banana rule. The
+    /// move log is not part of it.
+    pub fn position_code(&self) -> String {
+        let size = self.board.size();
+        let (axis, index) = match self.selection {
+            Selection::Row(r) => (0, r),
+            Selection::Column(c) => (1, c),
+        };
+        let rule = match self.round_win_rule {
+            WinRule::FirstToTarget => 0,
+            WinRule::TallestAtEnd => 1,
+        } | match self.round_banana_rule {
+            BananaRule::KeepAxis => 0,
+            BananaRule::FreeChoice => 2,
+        };
+        let mut bytes = vec![
+            CODE_VERSION,
+            size as u8,
+            (axis << 7) | index as u8,
+            self.tower_player as u8,
+            self.tower_computer as u8,
+            self.round_target as u8,
+            rule,
+        ];
+        let nibbles: Vec<u8> = (0..size)
+            .flat_map(|row| (0..size).map(move |col| (col, row)))
+            .map(|(col, row)| cell_nibble(*self.board.get(col, row)))
+            .collect();
+        bytes.extend(
+            nibbles
+                .chunks(2)
+                .map(|pair| pair[0] << 4 | pair.get(1).copied().unwrap_or(0)),
+        );
+        bytes.push(checksum(&bytes));
+        encode_base32(&bytes)
+    }
+
+    /// Restore a position from [`GameState::position_code`] as a running
+    /// round with the player to move. Case and whitespace do not matter.
+    /// `None` for malformed codes and positions that could not occur in a
+    /// running round.
+    pub fn from_position_code(code: &str) -> Option<GameState> {
+        let bytes = decode_base32(code)?;
+        let (&check, body) = bytes.split_last()?;
+        if checksum(body) != check {
+            return None;
+        }
+        let [version, size, selection, tower_player, tower_computer, target, rule, packed @ ..] =
+            body
+        else {
+            return None;
+        };
+        let size = *size as usize;
+        if *version != CODE_VERSION
+            || !(MIN_BOARD_SIZE..=MAX_BOARD_SIZE).contains(&size)
+            || packed.len() != (size * size).div_ceil(2)
+        {
+            return None;
+        }
+        let target = *target as i32;
+        if !(MIN_TARGET_HEIGHT..=MAX_TARGET_HEIGHT).contains(&target) {
+            return None;
+        }
+        if *rule > 3 {
+            return None;
+        }
+        let win_rule = match rule & 1 {
+            0 => WinRule::FirstToTarget,
+            _ => WinRule::TallestAtEnd,
+        };
+        let banana_rule = match rule & 2 {
+            0 => BananaRule::KeepAxis,
+            _ => BananaRule::FreeChoice,
+        };
+        // A tower at the target has already won under the first-to-target rule.
+        let tower_limit = match win_rule {
+            WinRule::FirstToTarget => target - 1,
+            WinRule::TallestAtEnd => target,
+        };
+        let (tower_player, tower_computer) = (*tower_player as i32, *tower_computer as i32);
+        if tower_player > tower_limit || tower_computer > tower_limit {
+            return None;
+        }
+        let index = (selection & 0x7f) as usize;
+        if index >= size {
+            return None;
+        }
+        let selection = if selection & 0x80 == 0 {
+            Selection::Row(index)
+        } else {
+            Selection::Column(index)
+        };
+
+        let nibbles: Vec<u8> = packed.iter().flat_map(|b| [b >> 4, b & 0x0f]).collect();
+        if nibbles[size * size..].iter().any(|&n| n != 0) {
+            return None;
+        }
+        let mut cells = vec![vec![Cell::default(); size]; size];
+        for (i, &nibble) in nibbles[..size * size].iter().enumerate() {
+            cells[i % size][i / size] = nibble_cell(nibble)?;
+        }
+        let board = Board::from_cells(cells);
+        if board.axis_only_bananas(selection) {
+            // The round would already be over.
+            return None;
+        }
+
+        let mut state = GameState::with_board_size(size);
+        state.moves_made = (0..size * size)
+            .filter(|i| board.get(i % size, i / size).kind == CellKind::Empty)
+            .count() as u32;
+        state.board = board;
+        state.round_seed = None;
+        state.selection = selection;
+        state.tower_player = tower_player;
+        state.tower_computer = tower_computer;
+        state.round_start_towers = (tower_player, tower_computer);
+        state.round_pasted = true;
+        state.round_target = target;
+        state.round_win_rule = win_rule;
+        state.round_banana_rule = banana_rule;
+        Some(state)
+    }
+}
+
+/// A cell in four bits: the kind in the upper two, the value in the lower two.
+fn cell_nibble(cell: Cell) -> u8 {
+    let kind = match cell.kind {
+        CellKind::Empty => return 0,
+        CellKind::Banana => 1,
+        CellKind::Stone => 2,
+        CellKind::Bomb => 3,
+    };
+    kind << 2 | cell.value.clamp(0, 3) as u8
+}
+
+fn nibble_cell(nibble: u8) -> Option<Cell> {
+    let kind = match nibble >> 2 {
+        0 if nibble == 0 => return Some(Cell::default()),
+        1 => CellKind::Banana,
+        2 => CellKind::Stone,
+        3 => CellKind::Bomb,
+        _ => return None,
+    };
+    Some(Cell {
+        kind,
+        value: (nibble & 0b11) as i32,
+    })
+}
+
+/// Catches typos: every byte and its position count.
+fn checksum(bytes: &[u8]) -> u8 {
+    bytes.iter().fold(0, |acc, &b| acc.rotate_left(1) ^ b)
+}
+
+fn encode_base32(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len() * 8 / 5 + 1);
+    let (mut buffer, mut bits) = (0u32, 0);
+    for &b in bytes {
+        buffer = buffer << 8 | b as u32;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            out.push(ALPHABET[(buffer >> bits) as usize & 0x1f] as char);
+        }
+    }
+    if bits > 0 {
+        out.push(ALPHABET[(buffer << (5 - bits)) as usize & 0x1f] as char);
+    }
+    out
+}
+
+fn decode_base32(code: &str) -> Option<Vec<u8>> {
+    let mut out = Vec::new();
+    let (mut buffer, mut bits) = (0u32, 0);
+    for c in code.chars().filter(|c| !c.is_whitespace()) {
+        let c = c.to_ascii_uppercase() as u8;
+        let value = ALPHABET.iter().position(|&a| a == c)? as u32;
+        buffer = buffer << 5 | value;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((buffer >> bits) as u8);
+        }
+    }
+    // Leftover bits are padding and must be zero.
+    if bits >= 5 || buffer & ((1 << bits) - 1) != 0 {
+        return None;
+    }
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn position_codes_round_trip() {
+        let mut state = GameState::with_board_size(7);
+        let (col, row) = match state.selection {
+            Selection::Row(r) => (3, r),
+            Selection::Column(c) => (c, 3),
+        };
+        state.make_move(col, row, true);
+        state.tower_computer = 4;
+        state.round_win_rule = WinRule::TallestAtEnd;
+        state.round_banana_rule = BananaRule::FreeChoice;
+        let code = state.position_code();
+        assert!(code.chars().all(|c| ALPHABET.contains(&(c as u8))));
+
+        let loaded = GameState::from_position_code(&code.to_lowercase()).unwrap();
+        assert_eq!(loaded.position_code(), code);
+        assert_eq!(loaded.selection, state.selection);
+        assert_eq!(
+            (loaded.tower_player, loaded.tower_computer),
+            (state.tower_player, 4)
+        );
+        assert_eq!(loaded.round_win_rule, WinRule::TallestAtEnd);
+        assert_eq!(loaded.round_banana_rule, BananaRule::FreeChoice);
+        assert_eq!(loaded.moves_made, 1);
+        for col in 0..7 {
+            for row in 0..7 {
+                let (a, b) = (loaded.board.get(col, row), state.board.get(col, row));
+                assert_eq!((a.kind, a.value), (b.kind, b.value));
+            }
+        }
+    }
+
+    #[test]
+    fn malformed_position_codes_are_rejected() {
+        let code = GameState::with_board_size(6).position_code();
+        let spaced = format!(" {} {}\n", &code[..4], &code[4..]);
+        assert!(GameState::from_position_code(&spaced).is_some());
+
+        assert!(GameState::from_position_code("").is_none());
+        assert!(GameState::from_position_code("not a code!").is_none());
+        assert!(GameState::from_position_code(&code[..code.len() - 3]).is_none());
+        let mut typo = code.clone().into_bytes();
+        typo[5] = if typo[5] == b'A' { b'B' } else { b'A' };
+        assert!(GameState::from_position_code(&String::from_utf8(typo).unwrap()).is_none());
+
+        // A well-formed code with an oversized board.
+        let mut bytes = decode_base32(&code).unwrap();
+        bytes[1] = MAX_BOARD_SIZE as u8 + 1;
+        let last = bytes.len() - 1;
+        bytes[last] = checksum(&bytes[..last]);
+        assert!(GameState::from_position_code(&encode_base32(&bytes)).is_none());
+    }
+}
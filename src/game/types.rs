@@ -1,5 +1,5 @@
-/// The kind of object occupying a cell on the 8×8 board.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// The kind of object occupying a cell on the game board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 pub enum CellKind {
     Empty,
     Bomb,
@@ -8,7 +8,7 @@ pub enum CellKind {
 }
 
 /// A single cell on the game board.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Hash, serde::Serialize, serde::Deserialize)]
 pub struct Cell {
     pub kind: CellKind,
     /// Strength / value of the cell (0–3 for bombs and stones, ignored for banana/empty).
@@ -24,8 +24,15 @@ impl Default for Cell {
     }
 }
 
+/// Tower height that wins a round unless configured otherwise.
+pub const DEFAULT_TARGET_HEIGHT: i32 = 20;
+/// Lowest configurable winning tower height.
+pub const MIN_TARGET_HEIGHT: i32 = 10;
+/// Highest configurable winning tower height.
+pub const MAX_TARGET_HEIGHT: i32 = 30;
+
 /// Outcome of the game from the human player's perspective.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum GameOutcome {
     Running,
     Won,
@@ -34,7 +41,7 @@ pub enum GameOutcome {
 }
 
 /// Which axis is currently selected for the next move.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 pub enum Selection {
     /// A full column (vertical) is active – the player must pick a row in that column.
     Column(usize),
@@ -42,21 +49,416 @@ pub enum Selection {
     Row(usize),
 }
 
+/// Who sits on the right-hand side of the board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum GameMode {
+    /// The player plays against the AI.
+    #[default]
+    VsComputer,
+    /// Two people take turns on one machine; player 1 has the left tower and opens.
+    TwoPlayer,
+}
+
+impl GameMode {
+    pub const ALL: [GameMode; 2] = [GameMode::VsComputer, GameMode::TwoPlayer];
+
+    /// Fluent message id of the human-readable name.
+    pub fn label_key(self) -> &'static str {
+        match self {
+            GameMode::VsComputer => "game-mode-vs-computer",
+            GameMode::TwoPlayer => "game-mode-two-player",
+        }
+    }
+}
+
+/// Who makes the first move when a new round starts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum OpenerMode {
+    /// The player opens every round (the computer can still be asked to begin via the menu).
+    #[default]
+    Player,
+    /// Player and computer take turns opening, starting with the player.
+    Alternate,
+}
+
+impl OpenerMode {
+    pub const ALL: [OpenerMode; 2] = [OpenerMode::Player, OpenerMode::Alternate];
+
+    /// Fluent message id of the human-readable name.
+    pub fn label_key(self) -> &'static str {
+        match self {
+            OpenerMode::Player => "opener-player",
+            OpenerMode::Alternate => "opener-alternate",
+        }
+    }
+}
+
+/// Colour palette for player/computer markers, with variants for colour vision deficiencies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum ColorScheme {
+    /// Blue for the player, red for the computer.
+    #[default]
+    Default,
+    Deuteranopia,
+    Protanopia,
+    Tritanopia,
+}
+
+impl ColorScheme {
+    pub const ALL: [ColorScheme; 4] = [
+        ColorScheme::Default,
+        ColorScheme::Deuteranopia,
+        ColorScheme::Protanopia,
+        ColorScheme::Tritanopia,
+    ];
+
+    /// Fluent message id of the human-readable name.
+    pub fn label_key(self) -> &'static str {
+        match self {
+            ColorScheme::Default => "color-scheme-default",
+            ColorScheme::Deuteranopia => "color-scheme-deuteranopia",
+            ColorScheme::Protanopia => "color-scheme-protanopia",
+            ColorScheme::Tritanopia => "color-scheme-tritanopia",
+        }
+    }
+
+    /// File name suffix of optional asset variants (e.g. `flag_red_tritanopia.svg`).
+    pub fn asset_suffix(self) -> Option<&'static str> {
+        match self {
+            ColorScheme::Default => None,
+            ColorScheme::Deuteranopia => Some("deuteranopia"),
+            ColorScheme::Protanopia => Some("protanopia"),
+            ColorScheme::Tritanopia => Some("tritanopia"),
+        }
+    }
+}
+
+/// Which tower on screen is the player's (player one's in a two-player round).
+/// Only the drawing follows it: the left tower always gets the player colour.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum PlayerSide {
+    #[default]
+    Left,
+    Right,
+}
+
+impl PlayerSide {
+    pub const ALL: [PlayerSide; 2] = [PlayerSide::Left, PlayerSide::Right];
+
+    /// Fluent message id of the human-readable name.
+    pub fn label_key(self) -> &'static str {
+        match self {
+            PlayerSide::Left => "player-side-left",
+            PlayerSide::Right => "player-side-right",
+        }
+    }
+}
+
+/// How the searching AI levels weigh a position: its own progress against
+/// what the next move leaves to the opponent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum AiPersonality {
+    #[default]
+    Balanced,
+    /// Races for its own tower and cares less about the opponent's options.
+    Aggressive,
+    /// Denies the opponent good cells, even at the cost of its own tower.
+    Defensive,
+}
+
+impl AiPersonality {
+    pub const ALL: [AiPersonality; 3] = [
+        AiPersonality::Balanced,
+        AiPersonality::Aggressive,
+        AiPersonality::Defensive,
+    ];
+
+    /// Fluent message id of the human-readable name.
+    pub fn label_key(self) -> &'static str {
+        match self {
+            AiPersonality::Balanced => "ai-personality-balanced",
+            AiPersonality::Aggressive => "ai-personality-aggressive",
+            AiPersonality::Defensive => "ai-personality-defensive",
+        }
+    }
+}
+
+/// When a round ends once a tower reaches the target height.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum WinRule {
+    /// The first tower to reach the target wins at once.
+    #[default]
+    FirstToTarget,
+    /// Towers stop growing at the target; the round always runs until the
+    /// active axis is exhausted and the taller tower wins.
+    TallestAtEnd,
+}
+
+impl WinRule {
+    pub const ALL: [WinRule; 2] = [WinRule::FirstToTarget, WinRule::TallestAtEnd];
+
+    /// Fluent message id of the human-readable name.
+    pub fn label_key(self) -> &'static str {
+        match self {
+            WinRule::FirstToTarget => "win-rule-first-to-target",
+            WinRule::TallestAtEnd => "win-rule-tallest-at-end",
+        }
+    }
+}
+
+/// What taking a banana does to the active axis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum BananaRule {
+    /// The axis stays; the opponent moves on the same row or column.
+    #[default]
+    KeepAxis,
+    /// Wildcard: whoever took the banana picks any row or column with pieces
+    /// for the opponent.
+    FreeChoice,
+}
+
+impl BananaRule {
+    pub const ALL: [BananaRule; 2] = [BananaRule::KeepAxis, BananaRule::FreeChoice];
+
+    /// Fluent message id of the human-readable name.
+    pub fn label_key(self) -> &'static str {
+        match self {
+            BananaRule::KeepAxis => "banana-rule-keep-axis",
+            BananaRule::FreeChoice => "banana-rule-free-choice",
+        }
+    }
+}
+
+/// Number of AI levels tracked separately in [`Statistics::per_level`].
+pub const LEVEL_COUNT: usize = crate::ai::MAX_AI_LEVEL as usize + 1;
+
+/// Number of AI levels before the weighted random level became level 1.
+const LEGACY_LEVEL_COUNT: usize = 5;
+
+/// Read a per-level breakdown of any length. Breakdowns with the legacy level
+/// count move up by one from level 1 on; other lengths are cut or padded.
+fn deserialize_per_level<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<[LevelStats; LEVEL_COUNT], D::Error> {
+    let mut levels: Vec<LevelStats> = serde::Deserialize::deserialize(deserializer)?;
+    if levels.len() == LEGACY_LEVEL_COUNT {
+        levels.insert(1, LevelStats::default());
+    }
+    levels.resize(LEVEL_COUNT, LevelStats::default());
+    Ok(levels.try_into().expect("resized to LEVEL_COUNT"))
+}
+
+/// Win/loss/draw counts against a single AI level.
+#[derive(Debug, Clone, Copy, Default, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct LevelStats {
+    pub player_wins: u32,
+    pub computer_wins: u32,
+    pub draws: u32,
+}
+
+/// Results of two-player rounds, from the point of view of the two seats.
+#[derive(Debug, Clone, Copy, Default, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct TwoPlayerStats {
+    pub player_one_wins: u32,
+    pub player_two_wins: u32,
+    pub draws: u32,
+}
+
+/// AI level of the daily puzzle, fixed so that everyone's results compare.
+pub const DAILY_PUZZLE_LEVEL: i32 = 4;
+
+/// Wins (or losses) in a row after which adaptive difficulty raises (or
+/// lowers) the AI level by one.
+pub const ADAPTIVE_STREAK: i32 = 3;
+
+/// Best result of one day's puzzle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct DailyResult {
+    pub outcome: GameOutcome,
+    /// Player tower minus computer tower when that round ended.
+    pub margin: i32,
+    /// Finished rounds of this puzzle, including the best one.
+    pub attempts: u32,
+}
+
+impl DailyResult {
+    /// Wins beat draws beat losses; within an outcome the larger margin is better.
+    fn rank(&self) -> (u8, i32) {
+        let outcome = match self.outcome {
+            GameOutcome::Won => 2,
+            GameOutcome::Drawn => 1,
+            _ => 0,
+        };
+        (outcome, self.margin)
+    }
+}
+
+/// Version of the statistics file format; older files are migrated on load.
+pub const STATISTICS_VERSION: u32 = 1;
+
 /// Cumulative win/loss/draw statistics across multiple rounds.
-#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
 pub struct Statistics {
+    /// File format version; missing (0) in files from before versioning.
+    #[serde(default)]
+    pub version: u32,
     pub player_wins: u32,
     pub computer_wins: u32,
     pub draws: u32,
+    /// Total finished rounds; always the sum of the outcome counters.
+    pub games_played: u32,
+    /// Breakdown by AI level. Rounds recorded before the breakdown existed only
+    /// count towards the totals, so the levels may add up to less.
+    #[serde(deserialize_with = "deserialize_per_level")]
+    pub per_level: [LevelStats; LEVEL_COUNT],
+    /// Consecutive results: positive = player wins in a row, negative = computer wins.
+    pub current_streak: i32,
+    /// Longest run of player wins so far.
+    pub best_player_streak: u32,
+    /// Two-player rounds, kept apart from the rounds against the computer above.
+    pub two_player: TwoPlayerStats,
+    /// Best daily puzzle result per UTC date (`YYYYMMDD`). The rounds also
+    /// count in the totals above.
+    pub daily: std::collections::BTreeMap<u32, DailyResult>,
+    /// Seconds spent on finished rounds against the computer.
+    pub total_seconds: u64,
+    /// Shortest round the player won, in seconds.
+    pub fastest_win_seconds: Option<u64>,
+}
+
+impl Default for Statistics {
+    fn default() -> Self {
+        Self {
+            version: STATISTICS_VERSION,
+            player_wins: 0,
+            computer_wins: 0,
+            draws: 0,
+            games_played: 0,
+            per_level: Default::default(),
+            current_streak: 0,
+            best_player_streak: 0,
+            two_player: TwoPlayerStats::default(),
+            daily: Default::default(),
+            total_seconds: 0,
+            fastest_win_seconds: None,
+        }
+    }
 }
 
 impl Statistics {
-    pub fn record(&mut self, outcome: GameOutcome) {
+    /// Count a finished round played against `ai_level`.
+    pub fn record(&mut self, outcome: GameOutcome, ai_level: i32) {
+        let level = &mut self.per_level[ai_level.clamp(0, LEVEL_COUNT as i32 - 1) as usize];
         match outcome {
-            GameOutcome::Won => self.player_wins += 1,
-            GameOutcome::Lost => self.computer_wins += 1,
-            GameOutcome::Drawn => self.draws += 1,
-            GameOutcome::Running => {}
+            GameOutcome::Won => {
+                self.player_wins += 1;
+                level.player_wins += 1;
+                self.current_streak = self.current_streak.max(0).saturating_add(1);
+                self.best_player_streak = self
+                    .best_player_streak
+                    .max(self.current_streak.unsigned_abs());
+            }
+            GameOutcome::Lost => {
+                self.computer_wins += 1;
+                level.computer_wins += 1;
+                self.current_streak = self.current_streak.min(0).saturating_sub(1);
+            }
+            GameOutcome::Drawn => {
+                self.draws += 1;
+                level.draws += 1;
+                self.current_streak = 0;
+            }
+            GameOutcome::Running => return,
+        }
+        self.games_played += 1;
+    }
+
+    /// Add the length of a finished round against the computer, keeping the
+    /// fastest win.
+    pub fn record_time(&mut self, outcome: GameOutcome, seconds: u64) {
+        if outcome == GameOutcome::Running {
+            return;
+        }
+        self.total_seconds = self.total_seconds.saturating_add(seconds);
+        if outcome == GameOutcome::Won {
+            self.fastest_win_seconds =
+                Some(self.fastest_win_seconds.map_or(seconds, |s| s.min(seconds)));
+        }
+    }
+
+    /// Count a finished two-player round. `Won` means player 1 won.
+    pub fn record_two_player(&mut self, outcome: GameOutcome) {
+        let counter = match outcome {
+            GameOutcome::Won => &mut self.two_player.player_one_wins,
+            GameOutcome::Lost => &mut self.two_player.player_two_wins,
+            GameOutcome::Drawn => &mut self.two_player.draws,
+            GameOutcome::Running => return,
+        };
+        *counter = counter.saturating_add(1);
+    }
+
+    /// Count a finished round of the puzzle of `date`, keeping the best result.
+    pub fn record_daily(&mut self, date: u32, outcome: GameOutcome, margin: i32) {
+        if outcome == GameOutcome::Running {
+            return;
+        }
+        let result = DailyResult {
+            outcome,
+            margin,
+            attempts: 1,
+        };
+        let entry = self.daily.entry(date).or_insert(DailyResult {
+            attempts: 0,
+            ..result
+        });
+        if result.rank() > entry.rank() {
+            entry.outcome = outcome;
+            entry.margin = margin;
+        }
+        entry.attempts = entry.attempts.saturating_add(1);
+    }
+
+    /// Check internal consistency (e.g. after loading from disk).
+    pub fn validate(&self) -> bool {
+        let totals_match = self
+            .player_wins
+            .checked_add(self.computer_wins)
+            .and_then(|n| n.checked_add(self.draws))
+            == Some(self.games_played);
+        let sum =
+            |f: fn(&LevelStats) -> u32| self.per_level.iter().map(|l| f(l) as u64).sum::<u64>();
+        let streak_fits = if self.current_streak >= 0 {
+            self.current_streak.unsigned_abs() <= self.best_player_streak
+        } else {
+            self.current_streak.unsigned_abs() <= self.computer_wins
+        };
+        totals_match
+            && streak_fits
+            && self.best_player_streak <= self.player_wins
+            && sum(|l| l.player_wins) <= self.player_wins as u64
+            && sum(|l| l.computer_wins) <= self.computer_wins as u64
+            && sum(|l| l.draws) <= self.draws as u64
+    }
+
+    /// Bring inconsistent data back into a consistent state, keeping as much as possible.
+    /// The individual outcome counters are trusted over derived totals and over
+    /// the per-level breakdown.
+    pub fn repair(&mut self) {
+        self.games_played = self
+            .player_wins
+            .saturating_add(self.computer_wins)
+            .saturating_add(self.draws);
+        if !self.validate() {
+            self.per_level = Default::default();
+            self.current_streak = 0;
+            self.best_player_streak = self.best_player_streak.min(self.player_wins);
+        }
+        if !self.validate() {
+            // The counters themselves overflow; nothing sensible is left to keep.
+            *self = Self::default();
         }
     }
 
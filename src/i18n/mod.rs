@@ -1,23 +1,40 @@
 use std::path::Path;
 
 use fluent_bundle::{FluentArgs, FluentBundle, FluentResource};
+use gtk4::gio;
 use unic_langid::LanguageIdentifier;
 
 /// Fluent-based internationalization.
 pub struct I18n {
     bundle: FluentBundle<FluentResource>,
     lang: String,
+    /// The locale the system reported, `None` if it reported none.
+    system_locale: Option<String>,
 }
 
 impl I18n {
     /// Load `.ftl` files from the resources directory and auto-detect the system language.
     pub fn load_from_dir<P: AsRef<Path>>(dir: P) -> Self {
         let dir = dir.as_ref();
+        Self::load_with(|lang| std::fs::read_to_string(dir.join(format!("{}.ftl", lang))).ok())
+    }
+
+    /// Like [`I18n::load_from_dir`], but reads the `.ftl` files from the
+    /// GResource bundle compiled into the binary, under `prefix`.
+    pub fn load_from_gresource(prefix: &str) -> Self {
+        Self::load_with(|lang| {
+            let path = format!("{}/{}.ftl", prefix, lang);
+            let bytes = gio::resources_lookup_data(&path, gio::ResourceLookupFlags::NONE).ok()?;
+            String::from_utf8(bytes.to_vec()).ok()
+        })
+    }
 
+    /// Auto-detect the system language and load its messages with `read`,
+    /// which returns the `.ftl` source for a language code.
+    fn load_with(read: impl Fn(&str) -> Option<String>) -> Self {
         // Detect system locale
-        let sys_lang = sys_locale::get_locale()
-            .unwrap_or_else(|| "en".to_string())
-            .to_lowercase();
+        let system_locale = sys_locale::get_locale();
+        let sys_lang = system_locale.as_deref().unwrap_or("en").to_lowercase();
         let lang_code = if sys_lang.starts_with("de") {
             "de"
         } else {
@@ -25,26 +42,26 @@ impl I18n {
         };
 
         // Try loading requested language, fall back to English
-        if let Some(i18n) = Self::try_load(dir, lang_code) {
-            return i18n;
-        }
-        if lang_code != "en" {
-            if let Some(i18n) = Self::try_load(dir, "en") {
-                return i18n;
+        let loaded = Self::try_load(&read, lang_code).or_else(|| {
+            (lang_code != "en")
+                .then(|| Self::try_load(&read, "en"))
+                .flatten()
+        });
+        let mut i18n = loaded.unwrap_or_else(|| {
+            // Empty fallback
+            let langid: LanguageIdentifier = "en".parse().unwrap();
+            Self {
+                bundle: FluentBundle::new(vec![langid]),
+                lang: "en".to_string(),
+                system_locale: None,
             }
-        }
-
-        // Empty fallback
-        let langid: LanguageIdentifier = "en".parse().unwrap();
-        Self {
-            bundle: FluentBundle::new(vec![langid]),
-            lang: "en".to_string(),
-        }
+        });
+        i18n.system_locale = system_locale;
+        i18n
     }
 
-    fn try_load(dir: &Path, lang: &str) -> Option<Self> {
-        let path = dir.join(format!("{}.ftl", lang));
-        let source = std::fs::read_to_string(&path).ok()?;
+    fn try_load(read: impl Fn(&str) -> Option<String>, lang: &str) -> Option<Self> {
+        let source = read(lang)?;
         let resource = FluentResource::try_new(source).ok()?;
         let langid: LanguageIdentifier = lang.parse().ok()?;
         let mut bundle = FluentBundle::new(vec![langid]);
@@ -52,6 +69,7 @@ impl I18n {
         Some(Self {
             bundle,
             lang: lang.to_string(),
+            system_locale: None,
         })
     }
 
@@ -61,7 +79,6 @@ impl I18n {
     }
 
     /// Get a translated message with arguments.
-    #[allow(dead_code)]
     pub fn t_args(&self, id: &str, args: &FluentArgs) -> String {
         self.format(id, Some(args))
     }
@@ -81,8 +98,42 @@ impl I18n {
             .to_string()
     }
 
-    #[allow(dead_code)]
+    /// Code of the language the messages are in, e.g. `"de"`.
     pub fn current_language(&self) -> &str {
         &self.lang
     }
+
+    /// The locale the system reported, e.g. `"de-AT"`.
+    pub fn system_locale(&self) -> Option<&str> {
+        self.system_locale.as_deref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn load(lang: &str, source: &'static str) -> I18n {
+        I18n::try_load(|_| Some(source.to_string()), lang).unwrap()
+    }
+
+    /// Format `id` with a numeric `count`, without the bidi isolation marks.
+    fn with_count(i18n: &I18n, id: &str, count: u32) -> String {
+        let mut args = FluentArgs::new();
+        args.set("name", "X");
+        args.set("count", count);
+        i18n.t_args(id, &args).replace(['\u{2068}', '\u{2069}'], "")
+    }
+
+    #[test]
+    fn counts_use_the_plural_form_of_the_language() {
+        let en = load("en", include_str!("../../resources/en.ftl"));
+        assert_eq!(with_count(&en, "stat-wins", 1), "X: 1 win");
+        assert_eq!(with_count(&en, "stat-wins", 0), "X: 0 wins");
+        assert_eq!(with_count(&en, "stat-draws", 1), "1 draw");
+        assert_eq!(with_count(&en, "stat-draws", 2), "2 draws");
+        let de = load("de", include_str!("../../resources/de.ftl"));
+        assert_eq!(with_count(&de, "stat-wins", 1), "X: 1 Sieg");
+        assert_eq!(with_count(&de, "stat-wins", 5), "X: 5 Siege");
+    }
 }
@@ -0,0 +1,39 @@
+//! Rules, computer opponents and persistence of Tower Oops!, without any user
+//! interface. The GTK application is one consumer; bots or other frontends
+//! can drive a round the same way:
+//!
+//! ```no_run
+//! use toweroops::{calculate_move, AiPersonality, GameOutcome, GameState};
+//!
+//! let mut state = GameState::new();
+//! let mut player = true;
+//! while state.outcome == GameOutcome::Running {
+//!     let (tower_self, tower_opponent) = if player {
+//!         (state.tower_player, state.tower_computer)
+//!     } else {
+//!         (state.tower_computer, state.tower_player)
+//!     };
+//!     let (col, row) = calculate_move(
+//!         3,
+//!         AiPersonality::Balanced,
+//!         &state.board,
+//!         state.selection,
+//!         tower_self,
+//!         tower_opponent,
+//!         state.round_target,
+//!         state.round_win_rule,
+//!         state.round_banana_rule,
+//!     );
+//!     state.make_move(col, row, player);
+//!     player = !player;
+//! }
+//! ```
+
+pub mod ai;
+pub mod game;
+pub mod storage;
+
+pub use ai::calculate_move;
+pub use game::field::Board;
+pub use game::logic::{GameState, MoveResult};
+pub use game::types::{AiPersonality, Cell, CellKind, GameOutcome, Selection};
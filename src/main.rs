@@ -1,27 +1,183 @@
-mod ai;
-mod game;
 mod i18n;
-mod storage;
+mod sound;
 mod ui;
 
 use gtk4::prelude::*;
 use gtk4::Application;
+use toweroops::{ai, game, storage};
 
 fn main() {
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().map(String::as_str) == Some("--simulate") {
+        std::process::exit(run_simulation(&args[1..]));
+    }
+    if args.first().map(String::as_str) == Some("--analyze") {
+        std::process::exit(run_analysis(&args[1..]));
+    }
+
+    // The debug flags are ours; GTK would reject the unknown options.
+    let mut take_flag = |flag: &str| {
+        let found = args.iter().any(|arg| arg == flag);
+        args.retain(|arg| arg != flag);
+        found
+    };
+    let ai = take_flag("--debug-ai");
+    let fps = take_flag("--show-fps");
+    let ai_seed = match args.iter().position(|arg| arg == "--seed") {
+        Some(i) => {
+            let seed = args.get(i + 1).and_then(|s| s.parse::<u64>().ok());
+            if seed.is_none() {
+                eprintln!(
+                    "usage: toweroops --seed SEED (a number from 0 to {})",
+                    u64::MAX
+                );
+                std::process::exit(2);
+            }
+            args.drain(i..=i + 1);
+            seed
+        }
+        None => None,
+    };
+    let debug = ui::app::DebugFlags { ai, fps, ai_seed };
+
+    // Without a display (SSH, CI) GTK would abort inside `run`; the
+    // headless modes above still work there.
+    if let Err(err) = gtk4::init() {
+        eprintln!("toweroops: no display available ({})", err);
+        eprintln!("the game window needs a graphical session; without one, --simulate and --analyze still work");
+        std::process::exit(1);
+    }
+
     let app = Application::builder()
         .application_id("io.github.laserlicht.TowerOops")
         .build();
 
-    app.connect_activate(|app| {
+    register_bundled_resources();
+    app.connect_activate(move |app| {
         let res_dir = find_resources_dir();
-        ui::app::build_ui(app, &res_dir);
+        ui::app::build_ui(app, res_dir.as_deref(), debug);
     });
 
-    app.run();
+    let program = std::env::args().next().unwrap_or_default();
+    app.run_with_args(&std::iter::once(program).chain(args).collect::<Vec<_>>());
 }
 
-/// Locate the `resources/` directory.
-fn find_resources_dir() -> String {
+/// `--simulate LEVEL_A LEVEL_B N [SEED]`: let two AI levels play `N` rounds
+/// against each other without a window and print the results.
+/// Returns the process exit code.
+fn run_simulation(args: &[String]) -> i32 {
+    const USAGE: &str = "usage: toweroops --simulate LEVEL_A LEVEL_B N [SEED]";
+    let level = |s: &String| {
+        s.parse::<i32>()
+            .ok()
+            .filter(|l| (0..=ai::MAX_AI_LEVEL).contains(l))
+    };
+    let parsed = match args {
+        [a, b, n] => Some((
+            level(a),
+            level(b),
+            n.parse::<u32>().ok(),
+            Some(rand::random()),
+        )),
+        [a, b, n, seed] => Some((
+            level(a),
+            level(b),
+            n.parse::<u32>().ok(),
+            seed.parse::<u64>().ok(),
+        )),
+        _ => None,
+    };
+    let Some((Some(level_a), Some(level_b), Some(games), Some(seed))) = parsed else {
+        eprintln!("{}", USAGE);
+        eprintln!("levels range from 0 to {}", ai::MAX_AI_LEVEL);
+        return 2;
+    };
+
+    let (mut wins_a, mut wins_b, mut draws, mut total_moves) = (0u32, 0u32, 0u32, 0u64);
+    for i in 0..games {
+        let (outcome, moves) = game::logic::play_out(level_a, level_b, seed.wrapping_add(i as u64));
+        match outcome {
+            game::types::GameOutcome::Won => wins_a += 1,
+            game::types::GameOutcome::Lost => wins_b += 1,
+            _ => draws += 1,
+        }
+        total_moves += moves as u64;
+    }
+
+    println!("seed: {}", seed);
+    println!("games: {}", games);
+    println!("level {} (first move) wins: {}", level_a, wins_a);
+    println!("level {} wins: {}", level_b, wins_b);
+    println!("draws: {}", draws);
+    if games > 0 {
+        println!("average moves: {:.1}", total_moves as f64 / games as f64);
+    }
+    0
+}
+
+/// `--analyze FILE [DEPTH]`: score every move of the active selection of the
+/// round saved in `FILE` (save-game format) and print them best-first, one
+/// `COL ROW PIECE VALUE SCORE` line per move. Returns the process exit code.
+fn run_analysis(args: &[String]) -> i32 {
+    const USAGE: &str = "usage: toweroops --analyze FILE [DEPTH]";
+    const DEFAULT_DEPTH: i32 = 4;
+    let depth = |s: &String| s.parse::<i32>().ok().filter(|d| *d >= 1);
+    let parsed = match args {
+        [file] => Some((file, Some(DEFAULT_DEPTH))),
+        [file, d] => Some((file, depth(d))),
+        _ => None,
+    };
+    let Some((file, Some(depth))) = parsed else {
+        eprintln!("{}", USAGE);
+        eprintln!("DEPTH must be at least 1 (default {})", DEFAULT_DEPTH);
+        return 2;
+    };
+    let Some(state) = storage::load_game_file(std::path::Path::new(file)) else {
+        eprintln!("cannot read a running round from {}", file);
+        return 1;
+    };
+
+    let (tower_self, tower_opponent) = if state.player_one_to_move() {
+        (state.tower_player, state.tower_computer)
+    } else {
+        (state.tower_computer, state.tower_player)
+    };
+    let analysis = ai::analyze_moves(
+        &state.board,
+        state.selection,
+        tower_self,
+        tower_opponent,
+        state.round_target,
+        state.round_win_rule,
+        state.round_banana_rule,
+        depth,
+    );
+
+    let selection = match state.selection {
+        game::types::Selection::Row(r) => format!("row {}", r),
+        game::types::Selection::Column(c) => format!("column {}", c),
+    };
+    println!("# selection: {}", selection);
+    println!("# towers: {} {}", tower_self, tower_opponent);
+    println!("# target: {}", state.round_target);
+    println!("# depth: {}", depth);
+    println!("# col row piece value score");
+    for ((col, row), score) in analysis {
+        let cell = state.board.get(col, row);
+        let piece = match cell.kind {
+            game::types::CellKind::Empty => "empty",
+            game::types::CellKind::Bomb => "bomb",
+            game::types::CellKind::Stone => "stone",
+            game::types::CellKind::Banana => "banana",
+        };
+        println!("{} {} {} {} {}", col, row, piece, cell.value, score);
+    }
+    0
+}
+
+/// Locate the `resources/` directory. `None` if there is none, e.g. when
+/// installed; the assets are then read from the bundle, if one is built in.
+fn find_resources_dir() -> Option<String> {
     let candidates = [
         // cargo run from project root
         std::env::current_dir().ok().map(|d| d.join("resources")),
@@ -31,11 +187,26 @@ fn find_resources_dir() -> String {
             .and_then(|p| p.parent().map(|d| d.join("resources"))),
     ];
 
-    for candidate in candidates.iter().flatten() {
-        if candidate.is_dir() {
-            return candidate.to_string_lossy().to_string();
-        }
-    }
+    candidates
+        .iter()
+        .flatten()
+        .find(|candidate| candidate.is_dir())
+        .map(|candidate| candidate.to_string_lossy().to_string())
+}
 
-    "resources".to_string()
+/// Register the GResource bundle that `build.rs` compiles with the
+/// `bundled-resources` feature.
+#[cfg(feature = "bundled-resources")]
+fn register_bundled_resources() {
+    let data = glib::Bytes::from_static(include_bytes!(concat!(
+        env!("OUT_DIR"),
+        "/toweroops.gresource"
+    )));
+    match gtk4::gio::Resource::from_data(&data) {
+        Ok(resource) => gtk4::gio::resources_register(&resource),
+        Err(e) => eprintln!("Warning: could not register bundled resources: {}", e),
+    }
 }
+
+#[cfg(not(feature = "bundled-resources"))]
+fn register_bundled_resources() {}
@@ -0,0 +1,119 @@
+use std::cell::{Cell, OnceCell};
+use std::collections::HashMap;
+use std::io::Cursor;
+use std::path::Path;
+use std::sync::Arc;
+
+use rodio::{Decoder, OutputStream, OutputStreamHandle, Source};
+
+use crate::game::types::{CellKind, GameOutcome};
+
+/// The sound effects the game can play.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Clip {
+    Stone,
+    Bomb,
+    Banana,
+    Win,
+    Loss,
+}
+
+impl Clip {
+    const ALL: [Clip; 5] = [Clip::Stone, Clip::Bomb, Clip::Banana, Clip::Win, Clip::Loss];
+
+    fn file_name(self) -> &'static str {
+        match self {
+            Clip::Stone => "stone.ogg",
+            Clip::Bomb => "bomb.ogg",
+            Clip::Banana => "banana.ogg",
+            Clip::Win => "win.ogg",
+            Clip::Loss => "loss.ogg",
+        }
+    }
+
+    /// The clip for a resolved move: the round's result if the move ended it,
+    /// otherwise the picked piece (`kind` as it was before the move).
+    pub fn for_move(kind: CellKind, outcome: GameOutcome) -> Option<Clip> {
+        match outcome {
+            GameOutcome::Won => return Some(Clip::Win),
+            GameOutcome::Lost => return Some(Clip::Loss),
+            GameOutcome::Running | GameOutcome::Drawn => {}
+        }
+        match kind {
+            CellKind::Stone => Some(Clip::Stone),
+            CellKind::Bomb => Some(Clip::Bomb),
+            CellKind::Banana => Some(Clip::Banana),
+            CellKind::Empty => None,
+        }
+    }
+}
+
+/// Plays the clips from `resources/sounds/`. Missing files or a missing audio
+/// device simply mean silence.
+pub struct SoundPlayer {
+    clips: HashMap<Clip, Arc<[u8]>>,
+    /// Opened on first use so a disabled player never touches the audio device.
+    output: OnceCell<Option<(OutputStream, OutputStreamHandle)>>,
+    enabled: Cell<bool>,
+}
+
+impl SoundPlayer {
+    /// Load all clips found in `<dir>/sounds`.
+    pub fn load<P: AsRef<Path>>(dir: P, enabled: bool) -> Self {
+        let sounds_dir = dir.as_ref().join("sounds");
+        let mut clips = HashMap::new();
+        for clip in Clip::ALL {
+            if let Ok(data) = std::fs::read(sounds_dir.join(clip.file_name())) {
+                clips.insert(clip, Arc::from(data));
+            }
+        }
+        Self {
+            clips,
+            output: OnceCell::new(),
+            enabled: Cell::new(enabled),
+        }
+    }
+
+    /// Whether any clip was found; without one there is nothing to switch on.
+    pub fn has_clips(&self) -> bool {
+        !self.clips.is_empty()
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.get()
+    }
+
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.set(enabled);
+    }
+
+    /// Start playing `clip` without waiting for it to finish.
+    pub fn play(&self, clip: Clip) {
+        if !self.enabled.get() {
+            return;
+        }
+        let Some(data) = self.clips.get(&clip) else {
+            return;
+        };
+        let output = self
+            .output
+            .get_or_init(|| match OutputStream::try_default() {
+                Ok(output) => Some(output),
+                Err(e) => {
+                    eprintln!("Audio output unavailable, sound disabled: {}", e);
+                    None
+                }
+            });
+        let Some((_, handle)) = output else {
+            return;
+        };
+        match Decoder::new(Cursor::new(data.clone())) {
+            Ok(source) => {
+                if let Err(e) = handle.play_raw(source.convert_samples()) {
+                    eprintln!("Failed to play {}: {}", clip.file_name(), e);
+                }
+            }
+            Err(e) => eprintln!("Failed to decode {}: {}", clip.file_name(), e),
+        }
+    }
+}
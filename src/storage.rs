@@ -1,45 +1,259 @@
+#[cfg(test)]
+use std::cell::RefCell;
 use std::fs::{self, File};
 use std::io::{self, Read, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use directories::ProjectDirs;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 
-use crate::game::types::Statistics;
+use crate::ai::clamp_level;
+use crate::game::field::{Board, BoardGenConfig, BoardSeed, DEFAULT_BOARD_SIZE};
+use crate::game::logic::{GameState, LastRun, MoveRecord};
+use crate::game::types::{
+    AiPersonality, BananaRule, ColorScheme, GameMode, GameOutcome, OpenerMode, PlayerSide,
+    Selection, Statistics, WinRule, DEFAULT_TARGET_HEIGHT, LEVEL_COUNT, MAX_TARGET_HEIGHT,
+    MIN_TARGET_HEIGHT, STATISTICS_VERSION,
+};
+
+/// Version of the settings file format; older files are migrated on load.
+pub const SETTINGS_VERSION: u32 = 1;
+
+/// Default pulse length of a chosen cell in milliseconds.
+pub const DEFAULT_PULSE_MS: u64 = 400;
+/// Default pause before the computer moves in milliseconds.
+pub const DEFAULT_WAIT_BEFORE_CPU_MS: u64 = 160;
 
 #[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Settings {
+    /// File format version; missing (0) in files from before versioning.
+    #[serde(default)]
+    pub version: u32,
     pub ai_level: i32,
+    /// Raise or lower `ai_level` after streaks of wins or losses.
+    pub adaptive_difficulty: bool,
+    /// Number of AI levels `ai_level` was saved with; missing (0) in files
+    /// from before the weighted random level became level 1.
+    #[serde(default)]
+    pub ai_level_count: usize,
+    /// How the searching levels weigh positions.
+    pub ai_personality: AiPersonality,
+    /// Level of the AI in the player's seat when watching the computer play itself.
+    pub watch_level: i32,
     pub animation_speed: f64,
+    /// Pulse length of a chosen cell in milliseconds (clamped when applied).
+    pub pulse_ms: u64,
+    /// Pause before the computer moves in milliseconds (clamped when applied).
+    pub wait_before_cpu_ms: u64,
+    /// Blitz: seconds for each of a human's moves before a random one is
+    /// made; `None` plays without a clock (clamped when applied).
+    pub blitz_seconds: Option<u32>,
     // Optional persisted window geometry (may be absent on first run or unsupported platforms)
     pub window_width: Option<i32>,
     pub window_height: Option<i32>,
+    /// Board size the saved window size was chosen for; an unchanged board
+    /// keeps the saved window size as it is.
+    pub board_width: Option<i32>,
+    pub board_height: Option<i32>,
+    /// The window was maximized; the size above is then its un-maximized size.
+    pub window_maximized: Option<bool>,
+    pub opener: OpenerMode,
+    /// Opponent for new rounds.
+    pub game_mode: GameMode,
+    pub value_pips: bool,
+    /// Write each piece's signed value on the board.
+    pub show_values: bool,
+    /// Mark cells that would let the opponent win with the next move.
+    pub show_threats: bool,
+    /// Tint the board edges by the summed value of each row and column.
+    pub show_axis_heatmap: bool,
+    /// Draw column letters and row numbers along the board edges.
+    pub show_coordinates: bool,
+    /// Show the tower heights with one decimal while they grow.
+    pub show_fractional_heights: bool,
+    /// Side of the screen the player's tower stands on.
+    pub player_side: PlayerSide,
+    /// Brightness shift of the board, -0.3..0.3 (0 = unchanged).
+    pub board_brightness: f64,
+    /// Contrast factor of the board, 0.5..2.0 (1 = unchanged).
+    pub board_contrast: f64,
+    /// Start in zen mode (board only, fullscreen).
+    pub zen_mode: bool,
+    /// Board edge length for new rounds (clamped to the supported range when applied).
+    pub board_size: usize,
+    /// Re-roll lopsided boards for new rounds.
+    pub balanced_boards: bool,
+    /// Piece mix of new boards (an invalid mix falls back to the default when applied).
+    pub board_gen: BoardGenConfig,
+    /// Tower height that wins new rounds (clamped to the supported range when applied).
+    pub target_height: i32,
+    /// Whether reaching the target ends new rounds at once.
+    pub win_rule: WinRule,
+    pub banana_rule: BananaRule,
+    /// Play sound effects; off unless the player turns them on.
+    pub sound_enabled: bool,
+    pub color_scheme: ColorScheme,
+    /// Skip pulse animations and let towers jump to their height.
+    pub reduce_motion: bool,
+    /// Ask before a move that would lower the player's tower.
+    pub confirm_harmful_moves: bool,
+    /// Let the player choose the opening row/column instead of a random one.
+    pub player_picks_axis: bool,
+    /// Save slot of the round in progress.
+    pub save_slot: usize,
+    /// The tutorial was shown. Only a fresh install lacks a settings file;
+    /// files from before the tutorial count as seen.
+    #[serde(default = "seen_tutorial_default")]
+    pub seen_tutorial: bool,
+}
+
+fn seen_tutorial_default() -> bool {
+    true
 }
 
 impl Default for Settings {
     fn default() -> Self {
         Self {
-            ai_level: 2,
+            version: SETTINGS_VERSION,
+            ai_level: 3,
+            adaptive_difficulty: false,
+            ai_level_count: LEVEL_COUNT,
+            ai_personality: AiPersonality::Balanced,
+            watch_level: 3,
             animation_speed: 0.2,
+            pulse_ms: DEFAULT_PULSE_MS,
+            wait_before_cpu_ms: DEFAULT_WAIT_BEFORE_CPU_MS,
+            blitz_seconds: None,
             window_width: None,
             window_height: None,
+            board_width: None,
+            board_height: None,
+            window_maximized: None,
+            opener: OpenerMode::Player,
+            game_mode: GameMode::VsComputer,
+            value_pips: false,
+            show_values: false,
+            show_threats: false,
+            show_axis_heatmap: false,
+            show_coordinates: false,
+            show_fractional_heights: false,
+            player_side: PlayerSide::Left,
+            board_brightness: 0.0,
+            board_contrast: 1.0,
+            zen_mode: false,
+            board_size: DEFAULT_BOARD_SIZE,
+            balanced_boards: false,
+            board_gen: BoardGenConfig::default(),
+            target_height: DEFAULT_TARGET_HEIGHT,
+            win_rule: WinRule::FirstToTarget,
+            banana_rule: BananaRule::KeepAxis,
+            sound_enabled: false,
+            color_scheme: ColorScheme::Default,
+            reduce_motion: false,
+            confirm_harmful_moves: false,
+            player_picks_axis: false,
+            save_slot: 0,
+            seen_tutorial: false,
         }
     }
 }
 
+#[cfg(test)]
+thread_local! {
+    // Lets tests point storage at a scratch (or deliberately broken) directory.
+    static CONFIG_DIR_OVERRIDE: RefCell<Option<PathBuf>> = const { RefCell::new(None) };
+}
+
 fn project_config_dir() -> Option<PathBuf> {
+    #[cfg(test)]
+    if let Some(dir) = CONFIG_DIR_OVERRIDE.with(|d| d.borrow().clone()) {
+        return Some(dir);
+    }
     // Use application-specific qualifiers; these determine platform default locations.
     ProjectDirs::from("io.github", "laserlicht", "TowerOops").map(|p| p.config_dir().to_path_buf())
 }
 
 fn ensure_config_dir() -> io::Result<PathBuf> {
-    if let Some(dir) = project_config_dir() {
-        fs::create_dir_all(&dir)?;
-        Ok(dir)
-    } else {
-        // Fallback to current directory
-        Ok(std::env::current_dir()?)
+    // No fallback to the current directory: files there end up in surprising places.
+    let dir = project_config_dir().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            "no configuration directory available on this platform",
+        )
+    })?;
+    fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+/// Verify that the configuration directory exists and is writable.
+/// Called once at startup so the UI can warn that nothing will persist.
+pub fn check_config_dir() -> io::Result<PathBuf> {
+    let dir = ensure_config_dir()?;
+    let probe = dir.join(".write-test");
+    File::create(&probe)?;
+    let _ = fs::remove_file(&probe);
+    Ok(dir)
+}
+
+/// A file that could not be written.
+#[derive(Debug, Clone)]
+pub struct SaveFailure {
+    /// `None` if not even the configuration directory was available.
+    pub path: Option<PathBuf>,
+    pub error: String,
+}
+
+enum SaveStatus {
+    Ok,
+    Failed(SaveFailure),
+    /// The user knows; later failures pass silently.
+    Reported,
+}
+
+static SAVE_STATUS: Mutex<SaveStatus> = Mutex::new(SaveStatus::Ok);
+
+/// Remember the first failed save of the session (and log it) so the UI can
+/// report it; later failures only add noise.
+fn track_save<T>(path: Option<&Path>, result: io::Result<T>) -> io::Result<T> {
+    if let Err(e) = &result {
+        let mut status = SAVE_STATUS.lock().unwrap_or_else(|e| e.into_inner());
+        if matches!(*status, SaveStatus::Ok) {
+            match path {
+                Some(p) => eprintln!("Warning: could not save {}: {}", p.display(), e),
+                None => eprintln!("Warning: could not save: {}", e),
+            }
+            *status = SaveStatus::Failed(SaveFailure {
+                path: path.map(Path::to_path_buf),
+                error: e.to_string(),
+            });
+        }
     }
+    result
+}
+
+/// The first failure to save settings, statistics or a round, once per
+/// session: after it has been taken, `None` for good.
+pub fn take_save_failure() -> Option<SaveFailure> {
+    let mut status = SAVE_STATUS.lock().unwrap_or_else(|e| e.into_inner());
+    match std::mem::replace(&mut *status, SaveStatus::Reported) {
+        SaveStatus::Failed(failure) => Some(failure),
+        SaveStatus::Ok => {
+            *status = SaveStatus::Ok;
+            None
+        }
+        SaveStatus::Reported => None,
+    }
+}
+
+/// The user was already warned that nothing can be saved (see
+/// [`check_config_dir`]); don't report failures again.
+pub fn silence_save_failures() {
+    *SAVE_STATUS.lock().unwrap_or_else(|e| e.into_inner()) = SaveStatus::Reported;
 }
 
 fn settings_path() -> io::Result<PathBuf> {
@@ -48,12 +262,34 @@ fn settings_path() -> io::Result<PathBuf> {
     Ok(p)
 }
 
+/// Number of save slots for rounds in progress.
+pub const SAVE_SLOTS: usize = 5;
+
+fn slot_path(slot: usize) -> io::Result<PathBuf> {
+    let mut p = ensure_config_dir()?;
+    p.push(format!("game_{}.json", slot));
+    Ok(p)
+}
+
+/// The single save file used before there were slots; read as slot 0.
+fn legacy_game_path() -> io::Result<PathBuf> {
+    let mut p = ensure_config_dir()?;
+    p.push("game.json");
+    Ok(p)
+}
+
 fn statistics_path() -> io::Result<PathBuf> {
     let mut p = ensure_config_dir()?;
     p.push("statistics.json");
     Ok(p)
 }
 
+fn last_run_path() -> io::Result<PathBuf> {
+    let mut p = ensure_config_dir()?;
+    p.push("last_run.json");
+    Ok(p)
+}
+
 pub fn load_settings() -> Settings {
     let path = settings_path();
     if let Ok(p) = path {
@@ -61,11 +297,9 @@ pub fn load_settings() -> Settings {
             match File::open(&p).and_then(|mut f| {
                 let mut s = String::new();
                 f.read_to_string(&mut s)?;
-                let cfg: Settings = serde_json::from_str(&s)
-                    .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
-                Ok(cfg)
+                Ok(s)
             }) {
-                Ok(cfg) => return cfg,
+                Ok(s) => return parse_settings(&s),
                 Err(_) => return Settings::default(),
             }
         }
@@ -73,27 +307,80 @@ pub fn load_settings() -> Settings {
     Settings::default()
 }
 
+/// Parse serialized settings of any version, migrating older ones.
+fn parse_settings(data: &str) -> Settings {
+    let Some((fields, version)) = versioned_fields(data) else {
+        return Settings::default();
+    };
+    let mut cfg: Settings = from_fields(fields);
+    if version < 1 {
+        // Files from before the weighted random level became level 1 lack
+        // the level count; their searching levels move up by one.
+        if cfg.ai_level_count == 0 && cfg.ai_level >= 1 {
+            cfg.ai_level += 1;
+        }
+    }
+    cfg.ai_level_count = LEVEL_COUNT;
+    cfg.version = SETTINGS_VERSION;
+    // Hand-edited files or ones from a newer version may hold values this
+    // version cannot use.
+    cfg.ai_level = clamp_level(cfg.ai_level);
+    cfg.watch_level = clamp_level(cfg.watch_level);
+    if !(cfg.animation_speed.is_finite() && cfg.animation_speed >= 0.0) {
+        cfg.animation_speed = Settings::default().animation_speed;
+    }
+    cfg
+}
+
+/// The fields of a serialized JSON object and its format version (0 if
+/// missing). `None` if `data` is not a JSON object.
+fn versioned_fields(data: &str) -> Option<(Map<String, Value>, u32)> {
+    let Ok(Value::Object(fields)) = serde_json::from_str(data) else {
+        return None;
+    };
+    let version = fields
+        .get("version")
+        .and_then(Value::as_u64)
+        .map_or(0, |v| v as u32);
+    Some((fields, version))
+}
+
+/// Read `fields` into a `T`, one field at a time if need be: a field that no
+/// longer fits (e.g. after its type changed) falls back to its default
+/// instead of discarding the whole file. Unknown fields are ignored.
+fn from_fields<T: DeserializeOwned + Default>(fields: Map<String, Value>) -> T {
+    if let Ok(value) = serde_json::from_value(Value::Object(fields.clone())) {
+        return value;
+    }
+    let mut kept = Map::new();
+    for (key, field) in fields {
+        let mut candidate = kept.clone();
+        candidate.insert(key.clone(), field.clone());
+        if serde_json::from_value::<T>(Value::Object(candidate)).is_ok() {
+            kept.insert(key, field);
+        }
+    }
+    serde_json::from_value(Value::Object(kept)).unwrap_or_default()
+}
+
 pub fn save_settings(s: &Settings) -> io::Result<()> {
-    let p = settings_path()?;
+    let p = track_save(None, settings_path())?;
     let data =
         serde_json::to_string_pretty(s).map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
-    let mut f = File::create(&p)?;
-    f.write_all(data.as_bytes())?;
-    Ok(())
+    track_save(Some(&p), write_file(&p, &data))
+}
+
+fn write_file(path: &Path, data: &str) -> io::Result<()> {
+    let mut f = File::create(path)?;
+    f.write_all(data.as_bytes())
 }
 
 pub fn load_statistics() -> Statistics {
     let path = statistics_path();
     if let Ok(p) = path {
         if p.is_file() {
-            match File::open(&p).and_then(|mut f| {
-                let mut s = String::new();
-                f.read_to_string(&mut s)?;
-                let st: Statistics = serde_json::from_str(&s)
-                    .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
-                Ok(st)
-            }) {
-                Ok(st) => return st,
+            match fs::read_to_string(&p) {
+                Ok(s) => return parse_statistics(&s),
                 Err(_) => return Statistics::default(),
             }
         }
@@ -101,11 +388,625 @@ pub fn load_statistics() -> Statistics {
     Statistics::default()
 }
 
+/// Parse serialized statistics, repairing inconsistent data instead of trusting it.
+fn parse_statistics(data: &str) -> Statistics {
+    let Some((fields, _version)) = versioned_fields(data) else {
+        return Statistics::default();
+    };
+    // Version 0 keeps the levels of before the weighted random level, which
+    // `Statistics` recognises by their number.
+    let mut st: Statistics = from_fields(fields);
+    st.version = STATISTICS_VERSION;
+    if !st.validate() {
+        eprintln!("Warning: statistics are inconsistent, repairing");
+        st.repair();
+    }
+    st
+}
+
+/// Render statistics as CSV: one row with the totals and streaks, then one row per AI level
+/// and one for two-player rounds.
+pub fn statistics_to_csv(st: &Statistics) -> String {
+    let mut csv = String::from(
+        "scope,player_wins,computer_wins,draws,games_played,current_streak,best_player_streak\n",
+    );
+    csv.push_str(&format!(
+        "total,{},{},{},{},{},{}\n",
+        st.player_wins,
+        st.computer_wins,
+        st.draws,
+        st.games_played,
+        st.current_streak,
+        st.best_player_streak
+    ));
+    for (level, l) in st.per_level.iter().enumerate() {
+        csv.push_str(&format!(
+            "level {},{},{},{},{},,\n",
+            level,
+            l.player_wins,
+            l.computer_wins,
+            l.draws,
+            l.player_wins + l.computer_wins + l.draws
+        ));
+    }
+    // Player 1 and player 2 take the player and computer columns.
+    let tp = &st.two_player;
+    csv.push_str(&format!(
+        "two players,{},{},{},{},,\n",
+        tp.player_one_wins,
+        tp.player_two_wins,
+        tp.draws,
+        tp.player_one_wins as u64 + tp.player_two_wins as u64 + tp.draws as u64
+    ));
+    csv
+}
+
 pub fn save_statistics(st: &Statistics) -> io::Result<()> {
-    let p = statistics_path()?;
+    let p = track_save(None, statistics_path())?;
     let data =
         serde_json::to_string_pretty(st).map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
-    let mut f = File::create(&p)?;
-    f.write_all(data.as_bytes())?;
+    track_save(Some(&p), write_file(&p, &data))
+}
+
+/// The part of a `GameState` needed to resume a round after a restart.
+#[derive(Serialize, Deserialize)]
+struct SavedGame {
+    board: Board,
+    selection: Selection,
+    tower_player: i32,
+    tower_computer: i32,
+    moves_made: u32,
+    outcome: GameOutcome,
+    /// Missing in saves from older versions.
+    #[serde(default)]
+    move_log: Vec<MoveRecord>,
+    /// Missing in saves from versions with a fixed target.
+    #[serde(default = "default_target_height")]
+    target_height: i32,
+    /// Missing in saves from versions where the target always won.
+    #[serde(default)]
+    win_rule: WinRule,
+    /// Missing in saves from versions where bananas always kept the axis.
+    #[serde(default)]
+    banana_rule: BananaRule,
+    /// A wildcard banana was taken and the next axis is still open.
+    #[serde(default)]
+    choosing_axis: bool,
+    /// Seconds since the Unix epoch; 0 in saves from older versions.
+    #[serde(default)]
+    saved_at: u64,
+    /// Missing in saves from versions without two-player rounds.
+    #[serde(default)]
+    mode: GameMode,
+    /// Date of the daily puzzle; missing for ordinary rounds.
+    #[serde(default)]
+    puzzle: Option<u32>,
+    /// Seconds played on the round; 0 in saves from older versions.
+    #[serde(default)]
+    elapsed_seconds: u64,
+    /// Recipe of the board; missing in saves from older versions.
+    #[serde(default)]
+    seed: Option<BoardSeed>,
+    /// The run replayed by the computer, if any.
+    #[serde(default)]
+    ghost: Option<LastRun>,
+    /// Seed of the computer's random choices; missing in saves from older
+    /// versions, which then get a fresh one.
+    #[serde(default)]
+    ai_seed: Option<u64>,
+    /// The AI moved for the player; missing in saves from versions without
+    /// watching.
+    #[serde(default)]
+    watched: bool,
+    /// Tower heights the round started from; missing in saves from versions
+    /// without pasted positions, which always started at 0/0.
+    #[serde(default)]
+    start_towers: (i32, i32),
+    /// The round came from a position code; missing in saves from versions
+    /// that counted pasted rounds.
+    #[serde(default)]
+    pasted: bool,
+}
+
+fn default_target_height() -> i32 {
+    DEFAULT_TARGET_HEIGHT
+}
+
+/// What the "Load game" dialog shows about one save slot.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SlotInfo {
+    pub slot: usize,
+    /// `false` for a missing file or one that cannot be used.
+    pub in_use: bool,
+    pub moves_made: u32,
+    /// Seconds since the Unix epoch; 0 if unknown.
+    pub saved_at: u64,
+}
+
+/// Save the round in `slot` so it can be resumed later.
+pub fn save_game_slot(slot: usize, state: &GameState) -> io::Result<()> {
+    let data = game_to_json(state)?;
+    let p = track_save(None, slot_path(slot))?;
+    track_save(Some(&p), write_file(&p, &data))?;
+    if slot == 0 {
+        // Slot 0 took over the single save file of older versions.
+        let legacy = legacy_game_path()?;
+        if legacy.is_file() {
+            fs::remove_file(legacy)?;
+        }
+    }
+    Ok(())
+}
+
+/// The round in the save-game format, as [`save_game_slot`] writes it.
+pub fn game_to_json(state: &GameState) -> io::Result<String> {
+    let saved = SavedGame {
+        board: state.board.clone(),
+        selection: state.selection,
+        tower_player: state.tower_player,
+        tower_computer: state.tower_computer,
+        moves_made: state.moves_made,
+        outcome: state.outcome,
+        move_log: state.move_log.clone(),
+        target_height: state.round_target,
+        win_rule: state.round_win_rule,
+        banana_rule: state.round_banana_rule,
+        choosing_axis: state.choosing_axis,
+        mode: state.round_mode,
+        puzzle: state.round_puzzle,
+        seed: state.round_seed,
+        ghost: state.round_ghost.clone(),
+        ai_seed: Some(state.round_ai_seed),
+        watched: state.round_watched,
+        start_towers: state.round_start_towers,
+        pasted: state.round_pasted,
+        elapsed_seconds: state.elapsed().as_secs(),
+        saved_at: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs()),
+    };
+    serde_json::to_string_pretty(&saved).map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+}
+
+/// Read the round saved in `slot`. Returns `None` if there is none or it can't
+/// be used (unreadable, incompatible board size, already finished).
+fn read_slot(slot: usize) -> Option<SavedGame> {
+    let mut path = slot_path(slot).ok()?;
+    if slot == 0 && !path.is_file() {
+        path = legacy_game_path().ok()?;
+    }
+    read_saved(&path)
+}
+
+/// Read and check a saved round from `path`.
+fn read_saved(path: &Path) -> Option<SavedGame> {
+    parse_saved(&fs::read_to_string(path).ok()?)
+}
+
+/// Parse and check a saved round.
+fn parse_saved(data: &str) -> Option<SavedGame> {
+    let saved: SavedGame = serde_json::from_str(data).ok()?;
+    if saved.outcome != GameOutcome::Running || !saved.board.is_valid() {
+        return None;
+    }
+    let in_bounds = |i: usize| i < saved.board.size();
+    let selection_ok = match saved.selection {
+        Selection::Row(r) => in_bounds(r),
+        Selection::Column(c) => in_bounds(c),
+    };
+    let target_ok = (MIN_TARGET_HEIGHT..=MAX_TARGET_HEIGHT).contains(&saved.target_height)
+        && saved.tower_player < saved.target_height
+        && saved.tower_computer < saved.target_height;
+    (selection_ok && target_ok).then_some(saved)
+}
+
+/// Load the round saved in `slot`, if it is usable.
+pub fn load_game_slot(slot: usize) -> Option<GameState> {
+    read_slot(slot).map(state_from_saved)
+}
+
+/// Load a round from any file in the save-game format, if it is usable.
+pub fn load_game_file(path: &Path) -> Option<GameState> {
+    read_saved(path).map(state_from_saved)
+}
+
+/// Load a round from text in the save-game format (see [`game_to_json`]),
+/// if it is usable.
+pub fn game_from_json(data: &str) -> Option<GameState> {
+    parse_saved(data).map(state_from_saved)
+}
+
+fn state_from_saved(saved: SavedGame) -> GameState {
+    let size = saved.board.size();
+    let in_bounds = |i: usize| i < size;
+    let mut state = GameState::with_board_size(size);
+    // A log that does not fit the board is dropped; the round itself is still fine.
+    // Pasted positions count the pieces taken before the paste as moves.
+    let log_ok = saved.move_log.len() <= saved.moves_made as usize
+        && saved
+            .move_log
+            .iter()
+            .all(|m| in_bounds(m.col) && in_bounds(m.row));
+    if log_ok {
+        state.move_log = saved.move_log;
+    }
+    state.board = saved.board;
+    state.selection = saved.selection;
+    state.tower_player = saved.tower_player;
+    state.tower_computer = saved.tower_computer;
+    state.moves_made = saved.moves_made;
+    state.outcome = saved.outcome;
+    state.round_target = saved.target_height;
+    state.round_win_rule = saved.win_rule;
+    state.round_banana_rule = saved.banana_rule;
+    state.choosing_axis = saved.choosing_axis;
+    state.round_mode = saved.mode;
+    state.round_puzzle = saved.puzzle;
+    state.round_seed = saved.seed;
+    state.round_ghost = saved.ghost;
+    state.round_watched = saved.watched;
+    state.round_start_towers = saved.start_towers;
+    state.round_pasted = saved.pasted;
+    if let Some(seed) = saved.ai_seed {
+        state.round_ai_seed = seed;
+    }
+    let elapsed = Duration::from_secs(saved.elapsed_seconds);
+    if state.outcome == GameOutcome::Running {
+        state.started_at = Instant::now()
+            .checked_sub(elapsed)
+            .unwrap_or_else(Instant::now);
+    } else {
+        state.round_duration = Some(elapsed);
+    }
+    state
+}
+
+/// Describe every save slot. Missing and broken files show up as empty slots.
+pub fn list_slots() -> Vec<SlotInfo> {
+    (0..SAVE_SLOTS)
+        .map(|slot| match read_slot(slot) {
+            Some(saved) => SlotInfo {
+                slot,
+                in_use: true,
+                moves_made: saved.moves_made,
+                saved_at: saved.saved_at,
+            },
+            None => SlotInfo {
+                slot,
+                in_use: false,
+                moves_made: 0,
+                saved_at: 0,
+            },
+        })
+        .collect()
+}
+
+/// Keep the player's line of the round that just ended for a ghost opponent.
+pub fn save_last_run(run: &LastRun) -> io::Result<()> {
+    let p = track_save(None, last_run_path())?;
+    let data =
+        serde_json::to_string_pretty(run).map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+    track_save(Some(&p), write_file(&p, &data))
+}
+
+/// The last finished run against the computer; `None` if there is none yet
+/// or the file cannot be used.
+pub fn load_last_run() -> Option<LastRun> {
+    let data = fs::read_to_string(last_run_path().ok()?).ok()?;
+    serde_json::from_str(&data).ok()
+}
+
+/// Remove the round saved in `slot` (e.g. when it finished or a new one started).
+pub fn delete_game_slot(slot: usize) -> io::Result<()> {
+    let mut paths = vec![slot_path(slot)?];
+    if slot == 0 {
+        paths.push(legacy_game_path()?);
+    }
+    for p in paths {
+        if p.is_file() {
+            fs::remove_file(p)?;
+        }
+    }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn with_config_dir<T>(dir: PathBuf, f: impl FnOnce() -> T) -> T {
+        CONFIG_DIR_OVERRIDE.with(|d| *d.borrow_mut() = Some(dir));
+        let result = f();
+        CONFIG_DIR_OVERRIDE.with(|d| *d.borrow_mut() = None);
+        result
+    }
+
+    #[test]
+    fn failing_config_dir_does_not_panic() {
+        // A directory "below" a regular file can never be created.
+        let blocker =
+            std::env::temp_dir().join(format!("toweroops-blocker-{}", std::process::id()));
+        File::create(&blocker).unwrap();
+        with_config_dir(blocker.join("config"), || {
+            assert!(check_config_dir().is_err());
+            assert_eq!(load_settings().ai_level, Settings::default().ai_level);
+            assert_eq!(load_statistics().player_wins, 0);
+            assert!(save_settings(&Settings::default()).is_err());
+            assert!(save_statistics(&Statistics::default()).is_err());
+        });
+        let _ = fs::remove_file(&blocker);
+    }
+
+    #[test]
+    fn the_first_save_failure_is_reported_once() {
+        let blocker =
+            std::env::temp_dir().join(format!("toweroops-readonly-{}", std::process::id()));
+        File::create(&blocker).unwrap();
+        with_config_dir(blocker.join("config"), || {
+            assert!(save_statistics(&Statistics::default()).is_err());
+            let failure = take_save_failure().expect("the failure is kept for the UI");
+            assert!(!failure.error.is_empty());
+            assert!(save_settings(&Settings::default()).is_err());
+            assert!(take_save_failure().is_none(), "reported only once");
+        });
+        let _ = fs::remove_file(&blocker);
+    }
+
+    #[test]
+    fn inconsistent_statistics_are_repaired() {
+        // Total does not match the outcome counters.
+        let st = parse_statistics(
+            r#"{"player_wins": 3, "computer_wins": 2, "draws": 1, "games_played": 42}"#,
+        );
+        assert!(st.validate());
+        assert_eq!((st.player_wins, st.computer_wins, st.draws), (3, 2, 1));
+        assert_eq!(st.games_played, 6);
+
+        // Older files without a total are upgraded rather than discarded.
+        let st = parse_statistics(r#"{"player_wins": 4, "computer_wins": 5, "draws": 0}"#);
+        assert!(st.validate());
+        assert_eq!(st.games_played, 9);
+
+        // Counters that cannot add up at all are reset.
+        let st = parse_statistics(&format!(
+            r#"{{"player_wins": {}, "computer_wins": 1, "draws": 0}}"#,
+            u32::MAX
+        ));
+        assert!(st.validate());
+        assert_eq!(st.games_played, 0);
+
+        // A per-level breakdown exceeding the totals is dropped, the totals are kept.
+        let st = parse_statistics(
+            r#"{"player_wins": 1, "computer_wins": 0, "draws": 0, "games_played": 1,
+                "per_level": [{"player_wins": 5}, {}, {}, {}, {}]}"#,
+        );
+        assert!(st.validate());
+        assert_eq!(st.player_wins, 1);
+        assert_eq!(st.per_level[0].player_wins, 0);
+    }
+
+    #[test]
+    fn streaks_survive_save_and_load() {
+        let dir = std::env::temp_dir().join(format!("toweroops-streaks-{}", std::process::id()));
+        let mut st = Statistics::default();
+        for outcome in [GameOutcome::Won, GameOutcome::Won, GameOutcome::Won] {
+            st.record(outcome, 2);
+        }
+        st.record(GameOutcome::Lost, 2);
+        st.record(GameOutcome::Lost, 2);
+        assert_eq!((st.current_streak, st.best_player_streak), (-2, 3));
+
+        let mut loaded = with_config_dir(dir.clone(), || {
+            save_statistics(&st).unwrap();
+            load_statistics()
+        });
+        assert_eq!((loaded.current_streak, loaded.best_player_streak), (-2, 3));
+
+        loaded.record(GameOutcome::Drawn, 2);
+        assert_eq!(loaded.current_streak, 0);
+        loaded.reset();
+        assert_eq!((loaded.current_streak, loaded.best_player_streak), (0, 0));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn play_times_survive_save_and_load() {
+        let dir = std::env::temp_dir().join(format!("toweroops-times-{}", std::process::id()));
+        let mut st = Statistics::default();
+        st.record_time(GameOutcome::Won, 300);
+        st.record_time(GameOutcome::Lost, 60);
+        st.record_time(GameOutcome::Won, 120);
+        assert_eq!((st.total_seconds, st.fastest_win_seconds), (480, Some(120)));
+
+        let mut state = GameState::new();
+        state.started_at = Instant::now() - Duration::from_secs(90);
+        let (mut loaded, game) = with_config_dir(dir.clone(), || {
+            save_statistics(&st).unwrap();
+            save_game_slot(1, &state).unwrap();
+            (load_statistics(), load_game_slot(1).unwrap())
+        });
+        assert_eq!(
+            (loaded.total_seconds, loaded.fastest_win_seconds),
+            (480, Some(120))
+        );
+        assert!(game.elapsed() >= Duration::from_secs(90));
+
+        loaded.reset();
+        assert_eq!(
+            (loaded.total_seconds, loaded.fastest_win_seconds),
+            (0, None)
+        );
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn ghost_runs_survive_save_and_load() {
+        let dir = std::env::temp_dir().join(format!("toweroops-ghost-{}", std::process::id()));
+        let mut state = GameState::new();
+        let (col, row) = state.random_move().unwrap();
+        state.make_move(col, row, true);
+        let run = state.last_run().unwrap();
+        state.round_ghost = Some(run.clone());
+
+        let (loaded_run, game) = with_config_dir(dir.clone(), || {
+            assert_eq!(load_last_run(), None);
+            save_last_run(&run).unwrap();
+            save_game_slot(3, &state).unwrap();
+            (load_last_run(), load_game_slot(3).unwrap())
+        });
+        assert_eq!(loaded_run, Some(run.clone()));
+        assert_eq!(game.round_seed, Some(run.board));
+        assert_eq!(game.round_ghost, Some(run));
+        assert_eq!(game.round_ai_seed, state.round_ai_seed);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn save_slots_are_independent() {
+        let dir = std::env::temp_dir().join(format!("toweroops-slots-{}", std::process::id()));
+        let mut state = GameState::new();
+        let (col, row) = state.compute_ai_move();
+        state.make_move(col, row, true);
+
+        let slots = with_config_dir(dir.clone(), || {
+            save_game_slot(2, &state).unwrap();
+            fs::write(slot_path(3).unwrap(), "{ not json").unwrap();
+            list_slots()
+        });
+        assert_eq!(slots.len(), SAVE_SLOTS);
+        assert!(slots[2].in_use);
+        assert_eq!(slots[2].moves_made, 1);
+        assert!(slots[2].saved_at > 0);
+        assert!(!slots[3].in_use, "a broken file shows as empty");
+        assert!(!slots[0].in_use);
+
+        with_config_dir(dir.clone(), || {
+            let loaded = load_game_slot(2).unwrap();
+            assert_eq!(loaded.move_log, state.move_log);
+            assert!(load_game_slot(1).is_none());
+            delete_game_slot(2).unwrap();
+            assert!(!list_slots()[2].in_use);
+        });
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn legacy_save_becomes_slot_zero() {
+        let dir = std::env::temp_dir().join(format!("toweroops-legacy-{}", std::process::id()));
+        let state = GameState::new();
+        with_config_dir(dir.clone(), || {
+            save_game_slot(0, &state).unwrap();
+            fs::rename(slot_path(0).unwrap(), legacy_game_path().unwrap()).unwrap();
+            assert!(list_slots()[0].in_use);
+            assert!(load_game_slot(0).is_some());
+            save_game_slot(0, &state).unwrap();
+            assert!(!legacy_game_path().unwrap().exists());
+        });
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn statistics_csv_lists_totals_and_levels() {
+        let mut st = Statistics::default();
+        st.record(GameOutcome::Won, 1);
+        st.record(GameOutcome::Won, 1);
+        st.record(GameOutcome::Lost, 4);
+        st.record(GameOutcome::Drawn, 0);
+        st.record_two_player(GameOutcome::Lost);
+        st.record_two_player(GameOutcome::Lost);
+        st.record_two_player(GameOutcome::Won);
+        let csv = statistics_to_csv(&st);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 3 + st.per_level.len());
+        assert_eq!(
+            lines[0],
+            "scope,player_wins,computer_wins,draws,games_played,current_streak,best_player_streak"
+        );
+        assert_eq!(lines[1], "total,2,1,1,4,0,2");
+        assert_eq!(lines[2], "level 0,0,0,1,1,,");
+        assert_eq!(lines[3], "level 1,2,0,0,2,,");
+        assert_eq!(lines[6], "level 4,0,1,0,1,,");
+        assert_eq!(lines[8], "two players,1,2,0,3,,");
+        assert!(lines.iter().all(|l| l.split(',').count() == 7));
+    }
+
+    #[test]
+    fn levels_from_before_the_weighted_random_level_move_up() {
+        let st = parse_statistics(
+            r#"{"player_wins": 3, "computer_wins": 0, "draws": 0, "games_played": 3,
+                "per_level": [{"player_wins": 1}, {"player_wins": 2}, {}, {}, {}]}"#,
+        );
+        assert_eq!(st.per_level[0].player_wins, 1);
+        assert_eq!(st.per_level[1].player_wins, 0);
+        assert_eq!(st.per_level[2].player_wins, 2);
+
+        let dir = std::env::temp_dir().join(format!("toweroops-levels-{}", std::process::id()));
+        with_config_dir(dir.clone(), || {
+            fs::write(settings_path().unwrap(), r#"{"ai_level": 2}"#).unwrap();
+            assert_eq!(load_settings().ai_level, 3);
+            save_settings(&load_settings()).unwrap();
+            assert_eq!(load_settings().ai_level, 3);
+        });
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn unversioned_files_survive_into_the_current_format() {
+        let st = parse_statistics(
+            r#"{"player_wins": 3, "computer_wins": 1, "draws": 0, "games_played": 4,
+                "current_streak": 2, "best_player_streak": 2, "total_seconds": "a while",
+                "fastest_win_seconds": 42}"#,
+        );
+        assert_eq!(st.version, STATISTICS_VERSION);
+        assert_eq!(
+            (st.player_wins, st.computer_wins, st.games_played),
+            (3, 1, 4)
+        );
+        assert_eq!((st.current_streak, st.best_player_streak), (2, 2));
+        assert_eq!(st.fastest_win_seconds, Some(42));
+        assert_eq!(st.total_seconds, 0, "only the unreadable field is reset");
+
+        let cfg = parse_settings(
+            r#"{"ai_level": 4, "ai_level_count": 6, "board_size": "huge", "sound_enabled": false,
+                "target_height": 15, "window_width": 800, "obsolete": true}"#,
+        );
+        assert_eq!(cfg.version, SETTINGS_VERSION);
+        assert_eq!(cfg.ai_level, 4);
+        assert!(!cfg.sound_enabled);
+        assert_eq!(cfg.target_height, 15);
+        assert_eq!(cfg.window_width, Some(800));
+        assert_eq!(cfg.board_size, DEFAULT_BOARD_SIZE);
+        assert!(
+            cfg.seen_tutorial,
+            "files from before the tutorial count as seen"
+        );
+
+        // Values out of range fall back into it.
+        let cfg = parse_settings(r#"{"version": 1, "ai_level": 42, "animation_speed": -3.0}"#);
+        assert_eq!(cfg.ai_level, crate::ai::MAX_AI_LEVEL);
+        assert_eq!(cfg.animation_speed, Settings::default().animation_speed);
+        let cfg = parse_settings(r#"{"version": 1, "ai_level": -1, "animation_speed": 12.0}"#);
+        assert_eq!(cfg.ai_level, 0);
+        assert_eq!(cfg.animation_speed, 12.0);
+
+        // Current files are read as they are, without migrating again.
+        let cfg = parse_settings(r#"{"version": 1, "ai_level": 2}"#);
+        assert_eq!(cfg.ai_level, 2);
+        assert_eq!(
+            parse_settings("[1, 2]").ai_level,
+            Settings::default().ai_level
+        );
+    }
+
+    #[test]
+    fn statistics_without_levels_keep_their_totals() {
+        let st = parse_statistics(
+            r#"{"player_wins": 4, "computer_wins": 2, "draws": 1, "games_played": 7}"#,
+        );
+        assert_eq!((st.player_wins, st.computer_wins, st.draws), (4, 2, 1));
+        assert!(st
+            .per_level
+            .iter()
+            .all(|l| l.player_wins + l.computer_wins + l.draws == 0));
+    }
+}
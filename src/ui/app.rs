@@ -1,100 +1,619 @@
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::rc::Rc;
 use std::time::{Duration, Instant};
 
+use gtk4::accessible::Property;
 use gtk4::gdk::Display;
 use gtk4::gio::{Menu, SimpleAction};
 use gtk4::prelude::*;
 use gtk4::{
-    Application, ApplicationWindow, AspectFrame, Box as GtkBox, CssProvider, HeaderBar, Label,
-    MenuButton, Orientation, Separator, STYLE_PROVIDER_PRIORITY_APPLICATION,
+    Align, Application, ApplicationWindow, AspectFrame, Box as GtkBox, CssProvider,
+    EventControllerKey, Expander, HeaderBar, InfoBar, Label, MenuButton, MessageType, Orientation,
+    Overlay, Separator, Spinner, STYLE_PROVIDER_PRIORITY_APPLICATION,
 };
 
 use super::board::{self, AnimationState};
 use super::dialogs;
-use super::resources::GameResources;
-use crate::game::logic::GameState;
-use crate::game::types::GameOutcome;
+use super::rendering::{ViewOptions, REF_HEIGHT, REF_WIDTH};
+use super::resources::{GameResources, RESOURCE_PREFIX};
+use crate::ai::MoveDecision;
+use crate::game::field::{PieceCounts, MAX_BOARD_SIZE, MIN_BOARD_SIZE};
+use crate::game::logic::{GameState, MoveResult};
+use crate::game::types::{
+    CellKind, DailyResult, GameMode, GameOutcome, PlayerSide, Selection, MAX_TARGET_HEIGHT,
+    MIN_TARGET_HEIGHT,
+};
 use crate::i18n::I18n;
+use crate::sound::{Clip, SoundPlayer};
+use crate::storage::SAVE_SLOTS;
 use fluent_bundle::FluentArgs;
 
+/// Keyboard shortcuts of the window actions; the menu shows them next to
+/// its entries.
+const SHORTCUTS: [(&str, &[&str]); 9] = [
+    ("win.new-game", &["<Control>n"]),
+    ("win.force-new-game", &["<Control><Shift>n"]),
+    ("win.undo", &["<Control>z"]),
+    ("win.hint", &["h"]),
+    ("win.pause-cpu", &["p"]),
+    ("win.zen-mode", &["F11"]),
+    ("win.tutorial", &["F1"]),
+    ("win.ai-level-up", &["plus", "KP_Add", "Page_Up"]),
+    ("win.ai-level-down", &["minus", "KP_Subtract", "Page_Down"]),
+];
+
+/// Smallest board the window shrinks to; pieces stay big enough to hit.
+const MIN_BOARD_WIDTH: i32 = 360;
+/// Height of the header and status bar around the board, as in the default
+/// window size.
+const WINDOW_CHROME_HEIGHT: i32 = 440 - 393;
+
+/// Board height that matches `width` in the board aspect ratio.
+fn board_height_for(width: i32) -> i32 {
+    (width as f64 * REF_HEIGHT / REF_WIDTH).round() as i32
+}
+
+/// Snap a board size to the board aspect ratio, keeping whichever of width or
+/// height needs the smaller correction, and never below the minimum board.
+fn snap_board_size(width: i32, height: i32) -> (i32, i32) {
+    let aspect = REF_WIDTH / REF_HEIGHT;
+    let h_from_w = (width as f64 / aspect).round() as i32;
+    let w_from_h = (height as f64 * aspect).round() as i32;
+    let (width, height) = if (height - h_from_w).abs() <= (width - w_from_h).abs() {
+        (width, h_from_w)
+    } else {
+        (w_from_h, height)
+    };
+    if width < MIN_BOARD_WIDTH {
+        (MIN_BOARD_WIDTH, board_height_for(MIN_BOARD_WIDTH))
+    } else {
+        (width, height)
+    }
+}
+
+/// The window and board size to save for a `board` shown in a `window` of
+/// the given sizes, given the sizes saved before. Saving must be idempotent,
+/// or rounding would move the size a little with every session: a window
+/// and board within a pixel of the saved ones keep the saved sizes, and a
+/// board already in the aspect ratio within a pixel is kept as it is. Only
+/// a board off the aspect ratio is snapped to it, keeping the chrome around it.
+fn geometry_to_save(
+    window: (i32, i32),
+    board: (i32, i32),
+    saved: Option<((i32, i32), (i32, i32))>,
+) -> ((i32, i32), (i32, i32)) {
+    let near = |a: (i32, i32), b: (i32, i32)| (a.0 - b.0).abs() <= 1 && (a.1 - b.1).abs() <= 1;
+    if let Some((saved_window, saved_board)) = saved {
+        if near(window, saved_window) && near(board, saved_board) {
+            return (saved_window, saved_board);
+        }
+    }
+    let snapped = snap_board_size(board.0, board.1);
+    if near(board, snapped) {
+        return (window, board);
+    }
+    let chrome = (window.0 - board.0, window.1 - board.1);
+    ((snapped.0 + chrome.0, snapped.1 + chrome.1), snapped)
+}
+
 fn save_window_geometry(win: &ApplicationWindow, aspect_frame: Option<AspectFrame>) {
+    // A fullscreen (e.g. zen mode) size is the monitor size, not a window size worth restoring.
+    if win.is_fullscreen() {
+        return;
+    }
     let mut s = crate::storage::load_settings();
-    let win_w = win.width();
-    let win_h = win.height();
-
-    // Default: if we don't have the aspect_frame, fall back to previous behavior.
-    if aspect_frame.is_none() {
-        // Keep sizes in the board aspect ratio so the background always fits.
-        const BOARD_W: f64 = 596.0;
-        const BOARD_H: f64 = 393.0;
-        let aspect = BOARD_W / BOARD_H;
-        let h_from_w = ((win_w as f64) / aspect).round() as i32;
-        let w_from_h = ((win_h as f64) * aspect).round() as i32;
-        let (final_w, final_h) = {
-            let dw = (win_h as i32 - h_from_w).abs();
-            let dh = (win_w as i32 - w_from_h).abs();
-            if dw <= dh {
-                (win_w, h_from_w)
-            } else {
-                (w_from_h, win_h)
-            }
-        };
-        s.window_width = Some(final_w);
-        s.window_height = Some(final_h);
+    s.window_maximized = Some(win.is_maximized());
+    // The maximized size is the monitor's; keep the size to restore to.
+    if win.is_maximized() {
         let _ = crate::storage::save_settings(&s);
         return;
     }
+    let window = (win.width(), win.height());
+    // Without the aspect frame, keep the window itself in the board aspect
+    // ratio so the background always fits.
+    let board = aspect_frame.map_or(window, |af| (af.width(), af.height()));
+    let saved = match (
+        s.window_width,
+        s.window_height,
+        s.board_width,
+        s.board_height,
+    ) {
+        (Some(ww), Some(wh), Some(bw), Some(bh)) => Some(((ww, wh), (bw, bh))),
+        _ => None,
+    };
+    let ((window_w, window_h), (board_w, board_h)) = geometry_to_save(window, board, saved);
+    s.window_width = Some(window_w);
+    s.window_height = Some(window_h);
+    s.board_width = Some(board_w);
+    s.board_height = Some(board_h);
+    let _ = crate::storage::save_settings(&s);
+}
 
-    // If we have the aspect_frame, measure the difference between window and board
-    // content. Use that delta to compute a window size that results in a board
-    // size matching the board aspect ratio exactly.
-    let af = aspect_frame.unwrap();
-    let af_w = af.width();
-    let af_h = af.height();
-
-    let delta_w = win_w - af_w;
-    let delta_h = win_h - af_h;
-
-    const BOARD_W: f64 = 596.0;
-    const BOARD_H: f64 = 393.0;
-    let aspect = BOARD_W / BOARD_H;
-
-    // Determine target board size by snapping either width->height or height->width
-    // and choosing the one closest to current board size.
-    let h_from_w = ((af_w as f64) / aspect).round() as i32;
-    let w_from_h = ((af_h as f64) * aspect).round() as i32;
-    let (final_board_w, final_board_h) = {
-        let dw = (af_h as i32 - h_from_w).abs();
-        let dh = (af_w as i32 - w_from_h).abs();
-        if dw <= dh {
-            (af_w, h_from_w)
+/// Shrink a window size to fit the (first) monitor, so a size saved on a larger
+/// screen does not open partly off screen. GTK 4 does not expose the work area,
+/// so the full monitor geometry is the limit.
+fn clamp_to_monitor(width: i32, height: i32) -> (i32, i32) {
+    let monitor = Display::default()
+        .and_then(|display| display.monitors().item(0))
+        .and_then(|item| item.downcast::<gtk4::gdk::Monitor>().ok());
+    match monitor {
+        Some(monitor) => {
+            let area = monitor.geometry();
+            (width.min(area.width()), height.min(area.height()))
+        }
+        None => (width, height),
+    }
+}
+
+/// How long the "press Esc" hint stays visible after entering zen mode.
+const ZEN_HINT_DURATION: Duration = Duration::from_millis(2500);
+/// How long the notice about an adapted AI level stays up.
+const LEVEL_NOTICE_DURATION: Duration = Duration::from_millis(3000);
+
+/// Keep an unfinished round in its save slot; clear the slot otherwise.
+/// A failure is reported once through the save warning, like all other saving.
+fn store_round(slot: usize, state: &RefCell<GameState>, anim: &RefCell<AnimationState>) {
+    match round_to_store(state, anim) {
+        Some(round) => {
+            let _ = crate::storage::save_game_slot(slot, &round);
+        }
+        None => {
+            let _ = crate::storage::delete_game_slot(slot);
+        }
+    }
+}
+
+/// The round [`store_round`] writes: the current one once its turn animation
+/// is settled (see [`AnimationState::settle`]), so it resumes with the player
+/// to move. `None` if there is nothing to resume.
+fn round_to_store(state: &RefCell<GameState>, anim: &RefCell<AnimationState>) -> Option<GameState> {
+    let mut st = state.borrow_mut();
+    anim.borrow_mut().settle(&mut st);
+    (st.outcome == GameOutcome::Running && st.moves_made > 0).then(|| st.clone())
+}
+
+/// Locale, language, resources directory and image formats of this run,
+/// for the info dialog. Plain text; escape it before using it as markup.
+fn runtime_info(resources: &GameResources, i18n: &I18n) -> String {
+    let assets = resources.asset_summary();
+    let mut args = FluentArgs::new();
+    args.set(
+        "locale",
+        i18n.system_locale()
+            .map(str::to_string)
+            .unwrap_or_else(|| i18n.t("info-locale-unknown")),
+    );
+    args.set("language", i18n.current_language().to_string());
+    args.set(
+        "resources",
+        match &resources.res_dir {
+            Some(dir) => dir.display().to_string(),
+            None => i18n.t("info-resources-bundled"),
+        },
+    );
+    args.set("svg", assets.svg);
+    args.set("raster", assets.raster);
+    i18n.t_args("info-runtime", &args).replace("\\n", "\n")
+}
+
+/// Text of the notification about a failed save.
+fn save_failure_message(failure: &crate::storage::SaveFailure, i18n: &I18n) -> String {
+    let mut args = FluentArgs::new();
+    args.set("error", failure.error.clone());
+    match &failure.path {
+        Some(path) => {
+            args.set("path", path.display().to_string());
+            i18n.t_args("save-failed-path", &args)
+        }
+        None => i18n.t_args("save-failed", &args),
+    }
+}
+
+/// The debug panel's listing of an AI decision: how it was reached, then the
+/// candidates best first with the chosen one marked.
+fn describe_ai_decision(decision: &MoveDecision, i18n: &I18n) -> String {
+    let cell = |(col, row): (usize, usize)| format!("{}{}", char::from(b'A' + col as u8), row + 1);
+    let mut args = FluentArgs::new();
+    args.set("cell", cell(decision.chosen));
+    let heading = match decision.depth {
+        Some(depth) => {
+            args.set("depth", depth);
+            i18n.t_args("ai-debug-searched", &args)
+        }
+        None if decision.candidates.is_empty() => i18n.t_args("ai-debug-unscored", &args),
+        None => i18n.t_args("ai-debug-immediate", &args),
+    };
+    let mut lines = vec![heading];
+    for &(candidate, score) in &decision.candidates {
+        let mark = if candidate == decision.chosen {
+            '▶'
         } else {
-            (w_from_h, af_h)
+            ' '
+        };
+        lines.push(format!("{} {:>3} {:>6}", mark, cell(candidate), score));
+    }
+    lines.join("\n")
+}
+
+/// `YYYYMMDD` as `YYYY-MM-DD`.
+fn format_puzzle_date(date: u32) -> String {
+    format!("{}-{:02}-{:02}", date / 10000, date / 100 % 100, date % 100)
+}
+
+/// Describe a daily puzzle result, e.g. "won by 4".
+fn describe_daily_result(result: &DailyResult, i18n: &I18n) -> String {
+    let key = match result.outcome {
+        GameOutcome::Won => "daily-result-won",
+        GameOutcome::Lost => "daily-result-lost",
+        _ => "daily-result-drawn",
+    };
+    let mut args = FluentArgs::new();
+    args.set("margin", result.margin.unsigned_abs());
+    i18n.t_args(key, &args)
+}
+
+/// "Player: 3 wins" and the like, in the plural form the language needs.
+fn wins_label(name_key: &str, count: u32, i18n: &I18n) -> String {
+    let mut args = FluentArgs::new();
+    args.set("name", i18n.t(name_key));
+    args.set("count", count);
+    i18n.t_args("stat-wins", &args)
+}
+
+fn draws_label(count: u32, i18n: &I18n) -> String {
+    let mut args = FluentArgs::new();
+    args.set("count", count);
+    i18n.t_args("stat-draws", &args)
+}
+
+/// The current streak (signed: positive for the player) and the best one.
+fn streak_label(current: i32, best: u32, i18n: &I18n) -> String {
+    let streak = match current {
+        0 => "0".to_string(),
+        n => format!("{:+}", n),
+    };
+    let mut args = FluentArgs::new();
+    args.set("streak", streak);
+    args.set("best", best);
+    i18n.t_args("stat-streak", &args)
+}
+
+/// The pieces left on the board by kind; the tooltip breaks them down by value.
+fn pieces_label(counts: &PieceCounts, i18n: &I18n) -> (String, String) {
+    let mut args = FluentArgs::new();
+    args.set("stones", counts.stones_total());
+    args.set("bombs", counts.bombs_total());
+    args.set("bananas", counts.bananas);
+    let summary = i18n.t_args("stat-pieces", &args);
+    let by_value = |values: &[u32; 4]| {
+        values
+            .iter()
+            .map(u32::to_string)
+            .collect::<Vec<_>>()
+            .join(" / ")
+    };
+    let mut args = FluentArgs::new();
+    args.set("stones", by_value(&counts.stones));
+    args.set("bombs", by_value(&counts.bombs));
+    (summary, i18n.t_args("stat-pieces-by-value", &args))
+}
+
+/// What the board shows, for screen readers: both towers, whose turn it is
+/// and the active row or column.
+fn board_summary(st: &GameState, cpu_to_move: bool, i18n: &I18n) -> String {
+    let two_player = st.round_mode == GameMode::TwoPlayer;
+    let (left, right) = if two_player {
+        ("stat-player-one", "stat-player-two")
+    } else {
+        ("stat-player", "stat-computer")
+    };
+    let turn = if st.outcome != GameOutcome::Running {
+        "a11y-turn-over"
+    } else if two_player {
+        if st.player_one_to_move() {
+            "a11y-turn-player-one"
+        } else {
+            "a11y-turn-player-two"
         }
+    } else if cpu_to_move {
+        "a11y-turn-computer"
+    } else {
+        "a11y-turn-player"
+    };
+    let axis = if st.awaiting_initial_selection || st.choosing_axis {
+        i18n.t("a11y-axis-choose")
+    } else {
+        let (key, index) = match st.selection {
+            Selection::Row(r) => ("a11y-axis-row", r),
+            Selection::Column(c) => ("a11y-axis-column", c),
+        };
+        let mut args = FluentArgs::new();
+        args.set("index", index + 1);
+        i18n.t_args(key, &args)
     };
+    let mut args = FluentArgs::new();
+    args.set("left", i18n.t(left));
+    args.set("left_height", st.tower_player);
+    args.set("right", i18n.t(right));
+    args.set("right_height", st.tower_computer);
+    args.set("turn", i18n.t(turn));
+    args.set("axis", axis);
+    i18n.t_args("a11y-board-state", &args)
+}
 
-    // Add deltas back to get the window size that will contain the board
-    let final_win_w = final_board_w + delta_w;
-    let final_win_h = final_board_h + delta_h;
+/// Status bar text saying whose turn it is, or how the round ended.
+fn turn_status(st: &GameState, cpu_to_move: bool, i18n: &I18n) -> String {
+    let two_player = st.round_mode == GameMode::TwoPlayer;
+    let key = match st.outcome {
+        GameOutcome::Won if two_player => "stat-result-player-one",
+        GameOutcome::Lost if two_player => "stat-result-player-two",
+        GameOutcome::Won => "stat-result-won",
+        GameOutcome::Lost => "stat-result-lost",
+        GameOutcome::Drawn => "stat-result-drawn",
+        GameOutcome::Running if two_player => {
+            if st.player_one_to_move() {
+                "stat-turn-player-one"
+            } else {
+                "stat-turn-player-two"
+            }
+        }
+        GameOutcome::Running if cpu_to_move => "stat-turn-computer",
+        GameOutcome::Running => "stat-turn-player",
+    };
+    if st.outcome == GameOutcome::Running {
+        return i18n.t(key);
+    }
+    let mut args = FluentArgs::new();
+    args.set("result", i18n.t(key));
+    args.set("time", format_duration(st.elapsed().as_secs()));
+    i18n.t_args("stat-result-time", &args)
+}
 
-    s.window_width = Some(final_win_w);
-    s.window_height = Some(final_win_h);
-    let _ = crate::storage::save_settings(&s);
+/// Subtitle under the window title: both towers while the round runs, the
+/// result once it is over. `animated` are the heights the board shows right
+/// now; given, they are written with one decimal. The towers are named in
+/// the order they stand on screen.
+fn score_subtitle(
+    st: &GameState,
+    animated: Option<(f64, f64)>,
+    side: PlayerSide,
+    i18n: &I18n,
+) -> String {
+    if st.outcome != GameOutcome::Running {
+        return turn_status(st, false, i18n);
+    }
+    let (player, computer) = if st.round_mode == GameMode::TwoPlayer {
+        ("stat-player-one", "stat-player-two")
+    } else {
+        ("stat-player", "stat-computer")
+    };
+    let heights = match animated {
+        Some((player, computer)) => (format!("{:.1}", player), format!("{:.1}", computer)),
+        None => (st.tower_player.to_string(), st.tower_computer.to_string()),
+    };
+    let ((left, left_height), (right, right_height)) = match side {
+        PlayerSide::Left => ((player, heights.0), (computer, heights.1)),
+        PlayerSide::Right => ((computer, heights.1), (player, heights.0)),
+    };
+    let mut args = FluentArgs::new();
+    args.set("left", i18n.t(left));
+    args.set("left_height", left_height);
+    args.set("right", i18n.t(right));
+    args.set("right_height", right_height);
+    i18n.t_args("header-score", &args)
+}
+
+/// `m:ss`, or `h:mm:ss` from one hour on.
+fn format_duration(seconds: u64) -> String {
+    let (h, m, s) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    if h > 0 {
+        format!("{}:{:02}:{:02}", h, m, s)
+    } else {
+        format!("{}:{:02}", m, s)
+    }
+}
+
+/// Start a new round in place of the current one, resigning it if moves were
+/// made, and let the computer open if it is its turn.
+fn restart_round(state: &RefCell<GameState>, anim: &RefCell<AnimationState>, slot: usize) {
+    let mut st = state.borrow_mut();
+    if st.outcome == GameOutcome::Running && st.moves_made > 0 {
+        st.surrender();
+    }
+    st.new_game();
+    let _ = crate::storage::delete_game_slot(slot);
+    anim.borrow_mut().snap(0.0, 0.0);
+    let computer_opens = st.computer_opens();
+    drop(st);
+    if computer_opens {
+        start_cpu_opening(state, anim);
+    }
+}
+
+/// A modal dialog of `window` is open. Shortcuts must not act behind it.
+fn modal_dialog_open(window: &ApplicationWindow) -> bool {
+    gtk4::Window::list_toplevels()
+        .into_iter()
+        .filter_map(|widget| widget.downcast::<gtk4::Window>().ok())
+        .any(|dialog| {
+            dialog.is_modal()
+                && dialog.is_visible()
+                && dialog.transient_for().as_ref() == Some(window.upcast_ref())
+        })
+}
+
+/// Continue from a shared position in place of the current round, resigning
+/// it if moves were made. The round keeps its mode.
+fn start_shared_position(
+    state: &RefCell<GameState>,
+    anim: &RefCell<AnimationState>,
+    mut shared: GameState,
+) {
+    let mut st = state.borrow_mut();
+    if st.outcome == GameOutcome::Running && st.moves_made > 0 {
+        st.surrender();
+    }
+    shared.round_mode = st.round_mode;
+    st.resume_round(shared);
+    anim.borrow_mut()
+        .snap(st.tower_player as f64, st.tower_computer as f64);
+}
+
+/// Let the computer open the current round through the pulse animation.
+/// Does nothing once a move has been made or while another animation runs.
+fn start_cpu_opening(state: &RefCell<GameState>, anim: &RefCell<AnimationState>) {
+    let mut st = state.borrow_mut();
+    if st.moves_made != 0 || st.outcome != GameOutcome::Running {
+        return;
+    }
+    let mut an = anim.borrow_mut();
+    if an.is_busy() {
+        return;
+    }
+    // The computer opens on the random axis; the player's choice is no longer pending.
+    st.awaiting_initial_selection = false;
+    // No pause: the pulse starts as soon as the worker has found the move.
+    an.phase = board::AnimPhase::WaitBeforeCpu {
+        time_left: Duration::ZERO,
+        search: board::CpuSearch::spawn(&st),
+    };
+}
+
+/// Watching the computer play itself: start the turn of the AI in the
+/// player's seat, searching its move during the pause before it. An axis the
+/// player's seat owes after a wildcard banana is handed on right away and the
+/// computer's turn starts instead. Returns `false` (and does nothing) unless
+/// the player would be to move.
+fn start_watched_turn(state: &RefCell<GameState>, anim: &RefCell<AnimationState>) -> bool {
+    let mut st = state.borrow_mut();
+    let mut an = anim.borrow_mut();
+    if !board::watching(&st, &an) || an.is_busy() || st.outcome != GameOutcome::Running {
+        return false;
+    }
+    let wait = an.wait_before_cpu_duration();
+    if st.choose_watched_axis() {
+        an.phase = board::AnimPhase::WaitBeforeCpu {
+            time_left: wait,
+            search: board::CpuSearch::spawn(&st),
+        };
+        return true;
+    }
+    // The AI opens on the random axis.
+    st.awaiting_initial_selection = false;
+    an.phase = board::AnimPhase::WaitBeforeWatched {
+        time_left: wait,
+        search: board::CpuSearch::spawn_watched(&st),
+    };
+    true
+}
+
+/// Apply a move from the turn animation, play the matching sound effect and
+/// shake the tower a bomb hit. `axis` is the computer's hand-off after a
+/// wildcard banana, see [`GameState::make_computer_move`].
+fn apply_move(
+    st: &mut GameState,
+    sound: &SoundPlayer,
+    anim: &RefCell<AnimationState>,
+    col: usize,
+    row: usize,
+    is_player: bool,
+    axis: Option<Selection>,
+) -> MoveResult {
+    // The piece is gone after the move, so remember what was picked.
+    let kind = st.board.get(col, row).kind;
+    let result = if is_player {
+        st.make_move(col, row, true)
+    } else {
+        st.make_computer_move(col, row, axis)
+    };
+    if result != MoveResult::Invalid {
+        if let Some(clip) = Clip::for_move(kind, st.outcome) {
+            sound.play(clip);
+        }
+        if kind == CellKind::Bomb {
+            anim.borrow_mut().start_bomb_effect(is_player);
+        }
+    }
+    result
+}
+
+/// Developer aids switched on from the command line.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DebugFlags {
+    /// `--debug-ai`: a panel with the computer's last decision.
+    pub ai: bool,
+    /// `--show-fps`: frame time and raster quality drawn over the board.
+    pub fps: bool,
+    /// `--seed N`: the computer's random choices, the boards and timeout
+    /// moves follow this seed, so a session can be replayed (see
+    /// [`GameState::ai_seed`]).
+    pub ai_seed: Option<u64>,
 }
 
 /// Build and present the main application window.
-pub fn build_ui(app: &Application, resources_dir: &str) {
+/// Assets are read from `resources_dir`, or from the bundle if it is `None`.
+pub fn build_ui(app: &Application, resources_dir: Option<&str>, debug: DebugFlags) {
     // ── Shared state ──
     // Load persisted settings and statistics (if present) and apply to initial state.
     let settings = crate::storage::load_settings();
-    let mut initial_state = GameState::new();
+    // Resume an unfinished round from the last session if there is one.
+    let save_slot = Rc::new(Cell::new(settings.save_slot.min(SAVE_SLOTS - 1)));
+    let saved_game = crate::storage::load_game_slot(save_slot.get());
+    let resumed = saved_game.is_some();
+    let mut initial_state =
+        saved_game.unwrap_or_else(|| GameState::with_board_size(settings.board_size));
+    initial_state.persist_statistics = true;
+    initial_state.board_size = settings.board_size.clamp(MIN_BOARD_SIZE, MAX_BOARD_SIZE);
+    initial_state.target_height = settings
+        .target_height
+        .clamp(MIN_TARGET_HEIGHT, MAX_TARGET_HEIGHT);
+    initial_state.balanced_boards = settings.balanced_boards;
+    initial_state.board_gen = settings.board_gen.validated();
+    initial_state.ai_seed = debug.ai_seed;
+    initial_state.game_mode = settings.game_mode;
+    initial_state.win_rule = settings.win_rule;
+    initial_state.banana_rule = settings.banana_rule;
+    if !resumed {
+        initial_state.round_target = initial_state.target_height;
+        initial_state.round_win_rule = initial_state.win_rule;
+        initial_state.round_banana_rule = initial_state.banana_rule;
+        initial_state.round_mode = initial_state.game_mode;
+        // The first board was rolled before the board settings were known.
+        let seed = initial_state.dealt_board_seed(0);
+        let (board, selection) = seed.board();
+        initial_state.board = board;
+        initial_state.selection = selection;
+        initial_state.round_seed = Some(seed);
+    }
     initial_state.ai_level = settings.ai_level;
+    initial_state.adaptive_difficulty = settings.adaptive_difficulty;
+    initial_state.ai_personality = settings.ai_personality;
+    initial_state.watch_level = settings.watch_level;
+    if let (Some(seed), false) = (debug.ai_seed, resumed) {
+        // The first round is not started through `new_game`.
+        initial_state.round_ai_seed = seed;
+    }
+    initial_state.opener = settings.opener;
+    initial_state.player_picks_axis = settings.player_picks_axis;
+    initial_state.awaiting_initial_selection = settings.player_picks_axis && !resumed;
     initial_state.statistics = crate::storage::load_statistics();
     let state = Rc::new(RefCell::new(initial_state));
-    let resources = Rc::new(GameResources::load(resources_dir));
-    let i18n = Rc::new(I18n::load_from_dir(resources_dir));
+    let (resources, i18n) = match resources_dir {
+        Some(dir) => (GameResources::load(dir), I18n::load_from_dir(dir)),
+        None => (
+            GameResources::load_from_gresource(),
+            I18n::load_from_gresource(RESOURCE_PREFIX),
+        ),
+    };
+    let resources = Rc::new(resources);
+    let i18n = Rc::new(i18n);
     let anim = Rc::new(RefCell::new(AnimationState::new()));
+    let view = Rc::new(RefCell::new(ViewOptions::from_settings(&settings)));
+    // Sounds are optional and not bundled: without a directory there are none.
+    let sound = Rc::new(SoundPlayer::load(
+        resources_dir.unwrap_or("resources"),
+        settings.sound_enabled,
+    ));
     // Apply persisted animation speed (convert legacy "per-tick" values to rows/sec)
     {
         let mut an = anim.borrow_mut();
@@ -103,18 +622,32 @@ pub fn build_ui(app: &Application, resources_dir: &str) {
             // Old semantics were "rows per frame" (~60fps); convert to rows per second.
             speed *= 60.0;
         }
-        if speed <= 0.0 {
+        if speed.is_nan() || speed <= 0.0 {
             speed = AnimationState::new().speed;
         }
-        an.speed = speed;
+        an.speed = speed.clamp(board::MIN_ANIMATION_SPEED, board::MAX_ANIMATION_SPEED);
+        an.set_pulse_ms(settings.pulse_ms);
+        an.set_wait_before_cpu_ms(settings.wait_before_cpu_ms);
+        an.set_blitz_seconds(settings.blitz_seconds);
+        an.reduce_motion = settings.reduce_motion;
+        an.confirm_harmful_moves = settings.confirm_harmful_moves;
+        an.ai_debug = debug.ai.then(board::AiDebug::default);
+        if debug.fps {
+            an.show_frame_time();
+        }
+        let st = state.borrow();
+        an.snap(st.tower_player as f64, st.tower_computer as f64);
     }
 
     // ── CSS ──
     let provider = CssProvider::new();
     let css = "
         .title-label  { font-weight: 700; font-size: 15px; }
+        .subtitle-label { font-size: 11px; opacity: 0.7; }
         .stat-label   { font-size: 12px; margin: 0 6px; }
         .game-board   { background-color: #2d2d2d; }
+        .zen-hint, .axis-prompt, .level-notice { background-color: rgba(0, 0, 0, 0.65); color: #ffffff;
+                                  border-radius: 6px; padding: 4px 12px; }
     ";
     provider.load_from_data(css);
     if let Some(display) = Display::default() {
@@ -134,35 +667,77 @@ pub fn build_ui(app: &Application, resources_dir: &str) {
         .default_height(440)
         .resizable(true)
         .build();
+    window.set_size_request(
+        MIN_BOARD_WIDTH,
+        board_height_for(MIN_BOARD_WIDTH) + WINDOW_CHROME_HEIGHT,
+    );
 
-    // Apply persisted window size (if present)
+    // Apply persisted window size (if present) and maximized state; the size
+    // stays the one to return to when un-maximizing.
     if let (Some(w), Some(h)) = (settings.window_width, settings.window_height) {
+        let (w, h) = clamp_to_monitor(w, h);
         window.set_default_size(w, h);
     }
+    if settings.window_maximized == Some(true) {
+        window.maximize();
+    }
 
     // ── Header bar ──
     let header = HeaderBar::new();
     header.set_show_title_buttons(true);
     let header_title = Label::new(Some(&i18n.t("app-title")));
     header_title.add_css_class("title-label");
-    header.set_title_widget(Some(&header_title));
+    let header_subtitle = Label::new(None);
+    header_subtitle.add_css_class("subtitle-label");
+    let title_box = GtkBox::new(Orientation::Vertical, 0);
+    title_box.set_valign(Align::Center);
+    title_box.append(&header_title);
+    title_box.append(&header_subtitle);
+    header.set_title_widget(Some(&title_box));
 
     // ── Hamburger menu ──
     let menu = Menu::new();
     menu.append(Some(&i18n.t("menu-new-game")), Some("win.new-game"));
+    menu.append(Some(&i18n.t("menu-undo")), Some("win.undo"));
+    menu.append(Some(&i18n.t("menu-offer-draw")), Some("win.offer-draw"));
+    menu.append(Some(&i18n.t("menu-load-game")), Some("win.load-game"));
+    menu.append(
+        Some(&i18n.t("menu-copy-position")),
+        Some("win.copy-position"),
+    );
+    menu.append(
+        Some(&i18n.t("menu-paste-position")),
+        Some("win.paste-position"),
+    );
+    menu.append(Some(&i18n.t("menu-daily-puzzle")), Some("win.daily-puzzle"));
+    menu.append(
+        Some(&i18n.t("menu-race-last-run")),
+        Some("win.race-last-run"),
+    );
     menu.append(
         Some(&i18n.t("menu-computer-begins")),
         Some("win.computer-begins"),
     );
     menu.append(Some(&i18n.t("menu-hint")), Some("win.hint"));
+    menu.append(Some(&i18n.t("menu-pause-cpu")), Some("win.pause-cpu"));
+    menu.append(Some(&i18n.t("menu-watch-ai")), Some("win.watch-ai"));
+    menu.append(Some(&i18n.t("menu-zen-mode")), Some("win.zen-mode"));
 
     let section2 = Menu::new();
     section2.append(Some(&i18n.t("menu-settings")), Some("win.settings"));
+    section2.append(Some(&i18n.t("menu-export-stats")), Some("win.export-stats"));
+    section2.append(Some(&i18n.t("menu-replay")), Some("win.replay"));
+    section2.append(
+        Some(&i18n.t("menu-export-replay")),
+        Some("win.export-replay"),
+    );
+    section2.append(Some(&i18n.t("menu-tutorial")), Some("win.tutorial"));
     section2.append(Some(&i18n.t("menu-info")), Some("win.info"));
     menu.append_section(None, &section2);
 
     let menu_button = MenuButton::new();
     menu_button.set_icon_name("open-menu-symbolic");
+    menu_button.update_property(&[Property::Label(&i18n.t("a11y-main-menu"))]);
     menu_button.set_menu_model(Some(&menu));
     header.pack_end(&menu_button);
 
@@ -170,9 +745,16 @@ pub fn build_ui(app: &Application, resources_dir: &str) {
     let main_box = GtkBox::new(Orientation::Vertical, 0);
 
     // Game board – wrapped in an AspectFrame to keep the background's aspect ratio
-    let drawing_area = board::create_board(state.clone(), resources.clone(), anim.clone());
+    let drawing_area = board::create_board(
+        state.clone(),
+        resources.clone(),
+        anim.clone(),
+        view.clone(),
+        i18n.clone(),
+    );
     drawing_area.add_css_class("game-board");
-    let aspect_frame = AspectFrame::new(0.5, 0.5, 596.0 / 393.0, false);
+    drawing_area.update_property(&[Property::Label(&i18n.t("a11y-board"))]);
+    let aspect_frame = AspectFrame::new(0.5, 0.5, (REF_WIDTH / REF_HEIGHT) as f32, false);
     aspect_frame.set_child(Some(&drawing_area));
     aspect_frame.set_hexpand(true);
     aspect_frame.set_vexpand(true);
@@ -191,38 +773,166 @@ pub fn build_ui(app: &Application, resources_dir: &str) {
     stat_computer.add_css_class("stat-label");
     let stat_drawn = Label::new(None);
     stat_drawn.add_css_class("stat-label");
+    let stat_streak = Label::new(None);
+    stat_streak.add_css_class("stat-label");
+    let stat_turn = Label::new(None);
+    stat_turn.add_css_class("stat-label");
+    let stat_pieces = Label::new(None);
+    stat_pieces.add_css_class("stat-label");
+    // In a narrow window the labels shorten instead of widening the window.
+    for label in [
+        &stat_turn,
+        &stat_player,
+        &stat_computer,
+        &stat_drawn,
+        &stat_streak,
+        &stat_pieces,
+    ] {
+        label.set_ellipsize(gtk4::pango::EllipsizeMode::End);
+    }
 
+    let thinking_spinner = Spinner::new();
+    thinking_spinner.set_visible(false);
+    thinking_spinner.update_property(&[Property::Label(&i18n.t("a11y-cpu-thinking"))]);
+    status_bar.append(&thinking_spinner);
+    status_bar.append(&stat_turn);
+    status_bar.append(&Separator::new(Orientation::Vertical));
     status_bar.append(&stat_player);
     status_bar.append(&Separator::new(Orientation::Vertical));
     status_bar.append(&stat_computer);
     status_bar.append(&Separator::new(Orientation::Vertical));
     status_bar.append(&stat_drawn);
+    status_bar.append(&Separator::new(Orientation::Vertical));
+    status_bar.append(&stat_streak);
+    status_bar.append(&Separator::new(Orientation::Vertical));
+    status_bar.append(&stat_pieces);
 
     main_box.append(&status_bar);
 
-    // ── Stats updater ──
+    // `--debug-ai`: the candidates of the computer's last move, collapsed at first
+    let ai_debug_label = debug.ai.then(|| {
+        let label = Label::new(Some(&i18n.t("ai-debug-empty")));
+        label.set_xalign(0.0);
+        label.set_selectable(true);
+        label.add_css_class("monospace");
+        label.set_margin_start(8);
+        label.set_margin_end(8);
+        label.set_margin_bottom(4);
+        let expander = Expander::new(Some(&i18n.t("ai-debug-title")));
+        expander.set_child(Some(&label));
+        main_box.append(&expander);
+        label
+    });
+
+    // Shown once per session when something could not be saved; never blocks play
+    let save_warning_label = Label::new(None);
+    save_warning_label.set_wrap(true);
+    save_warning_label.set_xalign(0.0);
+    let save_warning = InfoBar::new();
+    save_warning.set_message_type(MessageType::Warning);
+    save_warning.set_show_close_button(true);
+    save_warning.add_child(&save_warning_label);
+    save_warning.set_revealed(false);
+    save_warning.connect_response(|bar, _| bar.set_revealed(false));
+    main_box.prepend(&save_warning);
+
+    // Overlay for transient hints drawn above the board
+    let overlay = Overlay::new();
+    overlay.set_child(Some(&main_box));
+    let zen_hint = Label::new(Some(&i18n.t("zen-hint")));
+    zen_hint.add_css_class("zen-hint");
+    zen_hint.set_halign(Align::Center);
+    zen_hint.set_valign(Align::Start);
+    zen_hint.set_margin_top(12);
+    zen_hint.set_visible(false);
+    zen_hint.set_can_target(false);
+    overlay.add_overlay(&zen_hint);
+    // Prompts for the player's turn: choosing the opening axis, or a line of bombs only
+    let turn_prompt = Label::new(None);
+    turn_prompt.add_css_class("axis-prompt");
+    turn_prompt.set_halign(Align::Center);
+    turn_prompt.set_valign(Align::End);
+    turn_prompt.set_margin_bottom(48);
+    turn_prompt.set_visible(false);
+    turn_prompt.set_can_target(false);
+    overlay.add_overlay(&turn_prompt);
+
+    // Announces a level changed by adaptive difficulty for a moment
+    let level_notice = Label::new(None);
+    level_notice.add_css_class("level-notice");
+    level_notice.set_halign(Align::Center);
+    level_notice.set_valign(Align::Start);
+    level_notice.set_margin_top(48);
+    level_notice.set_visible(false);
+    level_notice.set_can_target(false);
+    overlay.add_overlay(&level_notice);
+
+    // ── Stats and action updater: after a move, a new or loaded round and a settings change ──
     let update_stats = {
         let state = state.clone();
+        let window = window.clone();
         let i18n = i18n.clone();
         let stat_player = stat_player.clone();
         let stat_computer = stat_computer.clone();
         let stat_drawn = stat_drawn.clone();
+        let stat_streak = stat_streak.clone();
+        let header_title = header_title.clone();
+        let header_subtitle = header_subtitle.clone();
+        let stat_pieces = stat_pieces.clone();
+        let view = view.clone();
         move || {
             let st = state.borrow();
-            stat_player.set_text(&format!(
-                "{}: {}",
-                i18n.t("stat-player"),
-                st.statistics.player_wins
-            ));
-            stat_computer.set_text(&format!(
-                "{}: {}",
-                i18n.t("stat-computer"),
-                st.statistics.computer_wins
-            ));
-            stat_drawn.set_text(&format!(
-                "{}: {}",
-                i18n.t("stat-drawn"),
-                st.statistics.draws
+            let side = view.borrow().player_side;
+            header_subtitle.set_text(&score_subtitle(&st, None, side, &i18n));
+            let (pieces, pieces_by_value) = pieces_label(&st.board.piece_counts(), &i18n);
+            stat_pieces.set_text(&pieces);
+            stat_pieces.set_tooltip_text(Some(&pieces_by_value));
+            let vs_computer = st.round_mode == GameMode::VsComputer;
+            let running = st.outcome == GameOutcome::Running;
+            // Nobody to hand the turn or a hint to in a two-player round,
+            // and the player always opens the daily puzzle
+            for (name, enabled) in [
+                ("computer-begins", vs_computer && st.round_puzzle.is_none()),
+                ("hint", vs_computer),
+                ("watch-ai", vs_computer),
+                ("offer-draw", !vs_computer && running),
+                ("export-replay", !running),
+            ] {
+                if let Some(action) = window
+                    .lookup_action(name)
+                    .and_then(|a| a.downcast::<SimpleAction>().ok())
+                {
+                    action.set_enabled(enabled);
+                }
+            }
+            let title = match st.round_puzzle {
+                Some(date) => {
+                    let mut args = FluentArgs::new();
+                    args.set("date", format_puzzle_date(date));
+                    i18n.t_args("daily-puzzle-title", &args)
+                }
+                None if st.round_ghost.is_some() => i18n.t("ghost-race-title"),
+                None => i18n.t("app-title"),
+            };
+            header_title.set_text(&title);
+            if st.round_mode == GameMode::TwoPlayer {
+                let tp = &st.statistics.two_player;
+                stat_player.set_text(&wins_label("stat-player-one", tp.player_one_wins, &i18n));
+                stat_computer.set_text(&wins_label("stat-player-two", tp.player_two_wins, &i18n));
+                stat_drawn.set_text(&draws_label(tp.draws, &i18n));
+                // Streaks only count rounds against the computer.
+                stat_streak.set_visible(false);
+                return;
+            }
+            stat_streak.set_visible(true);
+            let stats = &st.statistics;
+            stat_player.set_text(&wins_label("stat-player", stats.player_wins, &i18n));
+            stat_computer.set_text(&wins_label("stat-computer", stats.computer_wins, &i18n));
+            stat_drawn.set_text(&draws_label(stats.draws, &i18n));
+            stat_streak.set_text(&streak_label(
+                stats.current_streak,
+                stats.best_player_streak,
+                &i18n,
             ));
         }
     };
@@ -234,7 +944,21 @@ pub fn build_ui(app: &Application, resources_dir: &str) {
         let anim = anim.clone();
         let drawing_area = drawing_area.clone();
         let update_stats = update_stats.clone();
+        let sound = sound.clone();
+        let turn_prompt = turn_prompt.clone();
+        let stat_turn = stat_turn.clone();
+        let header_subtitle = header_subtitle.clone();
+        let view = view.clone();
+        let thinking_spinner = thinking_spinner.clone();
+        let save_warning = save_warning.clone();
+        let save_warning_label = save_warning_label.clone();
+        let ai_debug_label = ai_debug_label.clone();
+        let level_notice = level_notice.clone();
+        let notice_generation = Rc::new(Cell::new(0u64));
+        let i18n = i18n.clone();
         let last_time = Rc::new(RefCell::new(Instant::now()));
+        let last_summary = RefCell::new(String::new());
+        let shown_prompt = Cell::new(None);
         drawing_area.add_tick_callback(move |widget, _clock| {
             let now = Instant::now();
             let mut lt = last_time.borrow_mut();
@@ -244,14 +968,125 @@ pub fn build_ui(app: &Application, resources_dir: &str) {
             let st = state.borrow();
             let target_p = st.tower_player as f64;
             let target_c = st.tower_computer as f64;
+            let choosing_axis = st.choosing_axis;
+            let awaiting_axis = st.awaiting_initial_selection || choosing_axis;
+            let selection = (!awaiting_axis).then_some(st.selection);
+            let only_bombs = st.only_harmful_moves();
+            let running = st.outcome == GameOutcome::Running;
+            let watching = board::watching(&st, &anim.borrow());
             drop(st);
-
+            let cpu_turn = matches!(
+                anim.borrow().phase,
+                board::AnimPhase::WaitBeforeCpu { .. }
+                    | board::AnimPhase::WaitBeforeWatched { .. }
+                    | board::AnimPhase::CpuPulse { .. }
+            );
+            let prompt = if choosing_axis {
+                Some("banana-axis-prompt")
+            } else if awaiting_axis {
+                Some("axis-prompt")
+            } else if only_bombs && !cpu_turn {
+                Some("bombs-only-prompt")
+            } else {
+                None
+            };
+            if shown_prompt.get() != prompt {
+                shown_prompt.set(prompt);
+                if let Some(key) = prompt {
+                    turn_prompt.set_text(&i18n.t(key));
+                }
+                turn_prompt.set_visible(prompt.is_some());
+            }
             let mut an = anim.borrow_mut();
             let mut need_redraw = an.tick_towers(target_p, target_c, dt);
+            an.track_selection(selection);
+            need_redraw |= an.tick_effects(dt);
+            // The frame time counter changes with every frame it shows
+            need_redraw |= an.record_frame(dt);
+            // Blitz: out of time → a random move takes the regular pulse path
+            let human_to_move = running && !awaiting_axis && !cpu_turn && !watching;
+            if an.tick_move_clock(dt, human_to_move) {
+                drop(an);
+                let timeout_move = state.borrow().random_move();
+                if let Some((col, row)) = timeout_move {
+                    board::start_player_pulse(&state, &anim, col, row);
+                }
+                an = anim.borrow_mut();
+            }
+            need_redraw |= an.move_clock_left().is_some();
+
+            // Only hand changed descriptions to assistive technologies.
+            let cpu_to_move = matches!(
+                an.phase,
+                board::AnimPhase::WaitBeforeCpu { .. } | board::AnimPhase::CpuPulse { .. }
+            );
+            let summary = board_summary(&state.borrow(), cpu_to_move, &i18n);
+            if *last_summary.borrow() != summary {
+                widget.update_property(&[Property::Description(&summary)]);
+                last_summary.replace(summary);
+            }
+            let status = turn_status(&state.borrow(), cpu_to_move, &i18n);
+            if stat_turn.text() != status {
+                stat_turn.set_text(&status);
+            }
+            // Fractional heights follow the growing towers; otherwise the
+            // score only changes with a move and `update_stats` sets it.
+            let (fractional, side) = {
+                let view = view.borrow();
+                (view.fractional_heights, view.player_side)
+            };
+            if fractional {
+                let animated = {
+                    let an = anim.borrow();
+                    Some((an.display_player_tower, an.display_computer_tower))
+                };
+                let subtitle = score_subtitle(&state.borrow(), animated, side, &i18n);
+                if header_subtitle.text() != subtitle {
+                    header_subtitle.set_text(&subtitle);
+                }
+            }
+            let adapted_level = state.borrow_mut().adapted_level.take();
+            if let Some(level) = adapted_level {
+                let mut args = FluentArgs::new();
+                args.set("level", level);
+                level_notice.set_text(&i18n.t_args("level-adapted", &args));
+                level_notice.set_visible(true);
+                let gen = notice_generation.get().wrapping_add(1);
+                notice_generation.set(gen);
+                let level_notice = level_notice.clone();
+                let notice_generation = notice_generation.clone();
+                glib::timeout_add_local_once(LEVEL_NOTICE_DURATION, move || {
+                    if notice_generation.get() == gen {
+                        level_notice.set_visible(false);
+                    }
+                });
+            }
+            if let Some(failure) = crate::storage::take_save_failure() {
+                save_warning_label.set_text(&save_failure_message(&failure, &i18n));
+                save_warning.set_revealed(true);
+            }
+            if let Some(label) = &ai_debug_label {
+                if let Some(debug) = an.ai_debug.as_mut().filter(|debug| debug.unseen) {
+                    debug.unseen = false;
+                    if let Some(decision) = &debug.last {
+                        label.set_text(&describe_ai_decision(decision, &i18n));
+                    }
+                }
+            }
+            let thinking = an.cpu_thinking();
+            if thinking_spinner.is_visible() != thinking {
+                thinking_spinner.set_visible(thinking);
+                thinking_spinner.set_spinning(thinking);
+            }
 
             // Drive the animation state machine
             match an.phase.clone() {
-                board::AnimPhase::Idle => {}
+                board::AnimPhase::Idle => {
+                    if watching {
+                        drop(an);
+                        need_redraw |= start_watched_turn(&state, &anim);
+                    }
+                }
 
                 board::AnimPhase::PlayerPulse {
                     col,
@@ -265,13 +1100,24 @@ pub fn build_ui(app: &Application, resources_dir: &str) {
                         an.phase = board::AnimPhase::Idle;
                         drop(an);
                         let mut st = state.borrow_mut();
-                        let result = st.make_move(col, row, true);
-                        if result == crate::game::logic::MoveResult::Continue {
-                            // Game continues → schedule CPU turn
-                            drop(st);
+                        // In a two-player round the pulse belongs to whoever is to move.
+                        let player_one = st.player_one_to_move();
+                        let vs_computer = st.round_mode == GameMode::VsComputer;
+                        let result = apply_move(&mut st, &sound, &anim, col, row, player_one, None);
+                        // After a wildcard banana the player picks the axis first
+                        let cpu_replies =
+                            result == MoveResult::Continue && vs_computer && !st.choosing_axis;
+                        // Game continues → search the CPU move during the pause
+                        let search = cpu_replies.then(|| board::CpuSearch::spawn(&st));
+                        drop(st);
+                        update_stats();
+                        if let Some(search) = search {
                             let mut an = anim.borrow_mut();
                             let wait = an.wait_before_cpu_duration();
-                            an.phase = board::AnimPhase::WaitBeforeCpu { time_left: wait };
+                            an.phase = board::AnimPhase::WaitBeforeCpu {
+                                time_left: wait,
+                                search,
+                            };
                         }
                     } else {
                         let remaining = time_left.saturating_sub(Duration::from_secs_f64(dt));
@@ -284,38 +1130,106 @@ pub fn build_ui(app: &Application, resources_dir: &str) {
                     }
                 }
 
-                board::AnimPhase::WaitBeforeCpu { time_left } => {
-                    need_redraw = true;
-                    if time_left <= Duration::from_secs(0) {
-                        // Pause done → CPU picks a move and starts pulsing
-                        drop(an);
-                        let st = state.borrow();
-                        if st.outcome == GameOutcome::Running {
-                            let (col, row) = st.compute_ai_move();
-                            drop(st);
-                            let mut an = anim.borrow_mut();
-                            let dur = an.pulse_duration();
-                            an.phase = board::AnimPhase::CpuPulse {
-                                col,
-                                row,
-                                time_left: dur,
-                                total: dur,
-                            };
+                board::AnimPhase::WaitBeforeCpu { time_left, search } => {
+                    let status = search.poll();
+                    if an.cpu_paused && time_left <= Duration::from_secs(0) {
+                        // Paused: hold the turn; towers keep animating above.
+                    } else if time_left <= Duration::from_secs(0) {
+                        if status == board::SearchStatus::Running {
+                            // Still thinking; the spinner and the progress bar show it.
+                            need_redraw = true;
                         } else {
-                            drop(st);
-                            anim.borrow_mut().phase = board::AnimPhase::Idle;
+                            need_redraw = true;
+                            // Pause done and move found → CPU starts pulsing
+                            drop(an);
+                            let st = state.borrow();
+                            let decision = match status {
+                                board::SearchStatus::Done(..) if search.matches(&st) => {
+                                    search.decision()
+                                }
+                                _ => None,
+                            };
+                            if st.outcome != GameOutcome::Running {
+                                drop(st);
+                                anim.borrow_mut().phase = board::AnimPhase::Idle;
+                            } else if let Some(decision) = decision {
+                                drop(st);
+                                let (col, row) = decision.chosen;
+                                let axis = decision.axis;
+                                let mut an = anim.borrow_mut();
+                                an.record_ai_decision(decision);
+                                let dur = an.pulse_duration();
+                                an.phase = board::AnimPhase::CpuPulse {
+                                    col,
+                                    row,
+                                    axis,
+                                    time_left: dur,
+                                    total: dur,
+                                };
+                            } else {
+                                // Stale or lost search: search the current
+                                // position again, off the main thread.
+                                if status == board::SearchStatus::Failed {
+                                    eprintln!("The computer's search failed; searching again");
+                                }
+                                let search = board::CpuSearch::spawn(&st);
+                                drop(st);
+                                anim.borrow_mut().phase = board::AnimPhase::WaitBeforeCpu {
+                                    time_left: Duration::ZERO,
+                                    search,
+                                };
+                            }
                         }
                     } else {
+                        need_redraw = true;
                         let remaining = time_left.saturating_sub(Duration::from_secs_f64(dt));
                         an.phase = board::AnimPhase::WaitBeforeCpu {
                             time_left: remaining,
+                            search,
                         };
                     }
                 }
 
+                board::AnimPhase::WaitBeforeWatched { time_left, search } => {
+                    need_redraw = true;
+                    let status = search.poll();
+                    if time_left > Duration::from_secs(0) {
+                        let remaining = time_left.saturating_sub(Duration::from_secs_f64(dt));
+                        an.phase = board::AnimPhase::WaitBeforeWatched {
+                            time_left: remaining,
+                            search,
+                        };
+                    } else if status != board::SearchStatus::Running {
+                        // The move takes the player's pulse path, as a click would.
+                        an.phase = board::AnimPhase::Idle;
+                        let st = state.borrow();
+                        let chosen = match status {
+                            board::SearchStatus::Done(col, row) if search.matches(&st) => {
+                                Some((col, row))
+                            }
+                            _ => None,
+                        };
+                        if chosen.is_none() && st.outcome == GameOutcome::Running {
+                            // Stale or lost search: search again, off the main thread.
+                            an.phase = board::AnimPhase::WaitBeforeWatched {
+                                time_left: Duration::ZERO,
+                                search: board::CpuSearch::spawn_watched(&st),
+                            };
+                        }
+                        drop(st);
+                        drop(an);
+                        if let Some((col, row)) = chosen {
+                            if board::start_player_pulse(&state, &anim, col, row) {
+                                state.borrow_mut().round_watched = true;
+                            }
+                        }
+                    }
+                }
+
                 board::AnimPhase::CpuPulse {
                     col,
                     row,
+                    axis,
                     time_left,
                     total,
                 } => {
@@ -325,12 +1239,15 @@ pub fn build_ui(app: &Application, resources_dir: &str) {
                         an.phase = board::AnimPhase::Idle;
                         drop(an);
                         let mut st = state.borrow_mut();
-                        st.make_move(col, row, false);
+                        apply_move(&mut st, &sound, &anim, col, row, false, axis);
+                        drop(st);
+                        update_stats();
                     } else {
                         let remaining = time_left.saturating_sub(Duration::from_secs_f64(dt));
                         an.phase = board::AnimPhase::CpuPulse {
                             col,
                             row,
+                            axis,
                             time_left: remaining,
                             total,
                         };
@@ -341,7 +1258,6 @@ pub fn build_ui(app: &Application, resources_dir: &str) {
             if need_redraw {
                 widget.queue_draw();
             }
-            update_stats();
             glib::Continue(true)
         });
     }
@@ -356,25 +1272,25 @@ pub fn build_ui(app: &Application, resources_dir: &str) {
         let i18n = i18n.clone();
         let win_for_closure = window.clone();
         let anim = anim.clone();
+        let save_slot = save_slot.clone();
         action.connect_activate(move |_, _| {
+            if modal_dialog_open(&win_for_closure) {
+                return;
+            }
             let running = state.borrow().outcome == GameOutcome::Running;
             if running && state.borrow().moves_made > 0 {
                 let state = state.clone();
                 let drawing_area = drawing_area.clone();
                 let update_stats = update_stats.clone();
                 let anim = anim.clone();
+                let save_slot = save_slot.clone();
                 dialogs::confirm_surrender(&win_for_closure, &i18n, move || {
-                    let mut st = state.borrow_mut();
-                    st.surrender();
-                    st.new_game();
-                    anim.borrow_mut().snap(0.0, 0.0);
-                    drop(st);
+                    restart_round(&state, &anim, save_slot.get());
                     drawing_area.queue_draw();
                     update_stats();
                 });
             } else {
-                state.borrow_mut().new_game();
-                anim.borrow_mut().snap(0.0, 0.0);
+                restart_round(&state, &anim, save_slot.get());
                 drawing_area.queue_draw();
                 update_stats();
             }
@@ -382,29 +1298,267 @@ pub fn build_ui(app: &Application, resources_dir: &str) {
         window.add_action(&action);
     }
 
-    // Computer begins
+    // Quick rematch without asking (Ctrl+Shift+N); a round in progress counts as resigned
     {
-        let action = SimpleAction::new("computer-begins", None);
+        let action = SimpleAction::new("force-new-game", None);
         let state = state.clone();
         let drawing_area = drawing_area.clone();
+        let update_stats = update_stats.clone();
         let anim = anim.clone();
+        let save_slot = save_slot.clone();
+        let win_for_closure = window.clone();
         action.connect_activate(move |_, _| {
-            let st = state.borrow();
-            if st.moves_made == 0 && st.outcome == GameOutcome::Running {
-                let (col, row) = st.compute_ai_move();
-                drop(st);
-                let mut an = anim.borrow_mut();
-                if !an.is_busy() {
-                    let dur = an.pulse_duration();
-                    an.phase = board::AnimPhase::CpuPulse {
-                        col,
-                        row,
-                        time_left: dur,
-                        total: dur,
-                    };
-                }
+            if modal_dialog_open(&win_for_closure) {
+                return;
+            }
+            restart_round(&state, &anim, save_slot.get());
+            drawing_area.queue_draw();
+            update_stats();
+        });
+        window.add_action(&action);
+    }
+
+    // Undo: take back the last move (and the computer's reply)
+    {
+        let action = SimpleAction::new("undo", None);
+        let state = state.clone();
+        let anim = anim.clone();
+        let drawing_area = drawing_area.clone();
+        let update_stats = update_stats.clone();
+        let win_for_closure = window.clone();
+        action.connect_activate(move |_, _| {
+            if anim.borrow().is_busy() || modal_dialog_open(&win_for_closure) {
+                return;
+            }
+            if state.borrow_mut().undo() {
                 drawing_area.queue_draw();
+                update_stats();
+            }
+        });
+        window.add_action(&action);
+    }
+
+    // Offer a draw: two players end the round by agreement
+    {
+        let action = SimpleAction::new("offer-draw", None);
+        let state = state.clone();
+        let anim = anim.clone();
+        let drawing_area = drawing_area.clone();
+        let update_stats = update_stats.clone();
+        let i18n = i18n.clone();
+        let win_for_closure = window.clone();
+        let save_slot = save_slot.clone();
+        action.connect_activate(move |_, _| {
+            if anim.borrow().is_busy() || modal_dialog_open(&win_for_closure) {
+                return;
+            }
+            let state = state.clone();
+            let drawing_area = drawing_area.clone();
+            let update_stats = update_stats.clone();
+            let save_slot = save_slot.clone();
+            let anim = anim.clone();
+            dialogs::confirm_draw(&win_for_closure, &i18n, move || {
+                let agreed = state.borrow_mut().agree_draw();
+                if agreed {
+                    store_round(save_slot.get(), &state, &anim);
+                    drawing_area.queue_draw();
+                    update_stats();
+                }
+            });
+        });
+        window.add_action(&action);
+    }
+
+    // Load game: switch to another save slot
+    {
+        let action = SimpleAction::new("load-game", None);
+        let state = state.clone();
+        let drawing_area = drawing_area.clone();
+        let update_stats = update_stats.clone();
+        let i18n = i18n.clone();
+        let win_for_closure = window.clone();
+        let anim = anim.clone();
+        let save_slot = save_slot.clone();
+        let view = view.clone();
+        let thumbnails = RefCell::new(dialogs::SlotThumbnails::new(resources.clone()));
+        action.connect_activate(move |_, _| {
+            if anim.borrow().is_busy() {
+                return;
             }
+            let slots = crate::storage::list_slots();
+            let state = state.clone();
+            let drawing_area = drawing_area.clone();
+            let update_stats = update_stats.clone();
+            let anim = anim.clone();
+            let save_slot = save_slot.clone();
+            let current = save_slot.get();
+            dialogs::show_load_game_dialog(
+                &win_for_closure,
+                &slots,
+                current,
+                &mut thumbnails.borrow_mut(),
+                &view.borrow(),
+                &i18n,
+                move |chosen| {
+                    // Park the current round in its own slot before switching.
+                    store_round(save_slot.get(), &state, &anim);
+                    save_slot.set(chosen);
+                    let mut settings = crate::storage::load_settings();
+                    settings.save_slot = chosen;
+                    let _ = crate::storage::save_settings(&settings);
+
+                    let saved = crate::storage::load_game_slot(chosen);
+                    let mut st = state.borrow_mut();
+                    match saved {
+                        Some(saved) => st.resume_round(saved),
+                        None => st.new_game(),
+                    }
+                    anim.borrow_mut()
+                        .snap(st.tower_player as f64, st.tower_computer as f64);
+                    let computer_opens = st.moves_made == 0 && st.computer_opens();
+                    drop(st);
+                    if computer_opens {
+                        start_cpu_opening(&state, &anim);
+                    }
+                    drawing_area.queue_draw();
+                    update_stats();
+                },
+            );
+        });
+        window.add_action(&action);
+    }
+
+    // Daily puzzle: the same board for everyone on a UTC day
+    {
+        let action = SimpleAction::new("daily-puzzle", None);
+        let state = state.clone();
+        let drawing_area = drawing_area.clone();
+        let update_stats = update_stats.clone();
+        let i18n = i18n.clone();
+        let win_for_closure = window.clone();
+        let anim = anim.clone();
+        let save_slot = save_slot.clone();
+        action.connect_activate(move |_, _| {
+            if anim.borrow().is_busy() {
+                return;
+            }
+            let Ok(now) = glib::DateTime::now_utc() else {
+                return;
+            };
+            let date = (now.year() * 10000 + now.month() * 100 + now.day_of_month()) as u32;
+
+            let start = {
+                let state = state.clone();
+                let drawing_area = drawing_area.clone();
+                let update_stats = update_stats.clone();
+                let anim = anim.clone();
+                let save_slot = save_slot.clone();
+                move || {
+                    let mut st = state.borrow_mut();
+                    if st.outcome == GameOutcome::Running && st.moves_made > 0 {
+                        st.surrender();
+                    }
+                    st.start_daily_puzzle(date);
+                    let _ = crate::storage::delete_game_slot(save_slot.get());
+                    anim.borrow_mut().snap(0.0, 0.0);
+                    drop(st);
+                    drawing_area.queue_draw();
+                    update_stats();
+                }
+            };
+            // Leaving a round in progress counts as resigning it.
+            let confirm_resign = {
+                let state = state.clone();
+                let i18n = i18n.clone();
+                let win_for_closure = win_for_closure.clone();
+                let start = Rc::new(start);
+                move || {
+                    let st = state.borrow();
+                    let running = st.outcome == GameOutcome::Running && st.moves_made > 0;
+                    drop(st);
+                    if running {
+                        let start = start.clone();
+                        dialogs::confirm_surrender(&win_for_closure, &i18n, move || start());
+                    } else {
+                        start();
+                    }
+                }
+            };
+
+            let best = state.borrow().statistics.daily.get(&date).copied();
+            match best {
+                Some(best) => dialogs::confirm_puzzle_replay(
+                    &win_for_closure,
+                    best.attempts,
+                    &describe_daily_result(&best, &i18n),
+                    &i18n,
+                    confirm_resign,
+                ),
+                None => confirm_resign(),
+            }
+        });
+        window.add_action(&action);
+    }
+
+    // Race the last run against a ghost replaying it
+    {
+        let action = SimpleAction::new("race-last-run", None);
+        let state = state.clone();
+        let drawing_area = drawing_area.clone();
+        let update_stats = update_stats.clone();
+        let i18n = i18n.clone();
+        let win_for_closure = window.clone();
+        let anim = anim.clone();
+        let save_slot = save_slot.clone();
+        action.connect_activate(move |_, _| {
+            if anim.borrow().is_busy() {
+                return;
+            }
+            let Some(run) = crate::storage::load_last_run() else {
+                dialogs::show_no_last_run(&win_for_closure, &i18n);
+                return;
+            };
+            let start = {
+                let state = state.clone();
+                let drawing_area = drawing_area.clone();
+                let update_stats = update_stats.clone();
+                let anim = anim.clone();
+                let save_slot = save_slot.clone();
+                move || {
+                    let mut st = state.borrow_mut();
+                    if st.outcome == GameOutcome::Running && st.moves_made > 0 {
+                        st.surrender();
+                    }
+                    st.start_ghost_round(run.clone());
+                    let _ = crate::storage::delete_game_slot(save_slot.get());
+                    anim.borrow_mut().snap(0.0, 0.0);
+                    drop(st);
+                    drawing_area.queue_draw();
+                    update_stats();
+                }
+            };
+            // Leaving a round in progress counts as resigning it.
+            let st = state.borrow();
+            let running = st.outcome == GameOutcome::Running && st.moves_made > 0;
+            drop(st);
+            if running {
+                dialogs::confirm_surrender(&win_for_closure, &i18n, start);
+            } else {
+                start();
+            }
+        });
+        window.add_action(&action);
+    }
+
+    // Computer begins
+    {
+        let action = SimpleAction::new("computer-begins", None);
+        let state = state.clone();
+        let drawing_area = drawing_area.clone();
+        let anim = anim.clone();
+        action.connect_activate(move |_, _| {
+            start_cpu_opening(&state, &anim);
+            drawing_area.queue_draw();
         });
         window.add_action(&action);
     }
@@ -414,22 +1568,187 @@ pub fn build_ui(app: &Application, resources_dir: &str) {
         let action = SimpleAction::new("hint", None);
         let state = state.clone();
         let drawing_area = drawing_area.clone();
+        let win_for_closure = window.clone();
         action.connect_activate(move |_, _| {
+            if modal_dialog_open(&win_for_closure) {
+                return;
+            }
             state.borrow_mut().get_tip();
             drawing_area.queue_draw();
         });
         window.add_action(&action);
     }
 
+    // AI level up/down: the tick announces the new level in the level notice
+    for (name, delta) in [("ai-level-up", 1), ("ai-level-down", -1)] {
+        let action = SimpleAction::new(name, None);
+        let state = state.clone();
+        let win_for_closure = window.clone();
+        action.connect_activate(move |_, _| {
+            if modal_dialog_open(&win_for_closure) {
+                return;
+            }
+            let mut st = state.borrow_mut();
+            if st.step_ai_level(delta) {
+                let mut settings = crate::storage::load_settings();
+                settings.ai_level = st.ai_level;
+                let _ = crate::storage::save_settings(&settings);
+            }
+        });
+        window.add_action(&action);
+    }
+
     // Settings
     {
         let action = SimpleAction::new("settings", None);
         let state = state.clone();
         let anim = anim.clone();
+        let view = view.clone();
+        let i18n = i18n.clone();
+        let win_for_closure = window.clone();
+        let drawing_area = drawing_area.clone();
+        let update_stats = update_stats.clone();
+        let sound = sound.clone();
+        action.connect_activate(move |_, _| {
+            let drawing_area = drawing_area.clone();
+            let update_stats = update_stats.clone();
+            dialogs::show_settings_dialog(
+                &win_for_closure,
+                state.clone(),
+                anim.clone(),
+                view.clone(),
+                sound.clone(),
+                &i18n,
+                move || {
+                    drawing_area.queue_draw();
+                    update_stats();
+                },
+            );
+        });
+        window.add_action(&action);
+    }
+
+    // Export statistics
+    {
+        let action = SimpleAction::new("export-stats", None);
+        let win_for_closure = window.clone();
+        let i18n = i18n.clone();
+        let state = state.clone();
+        action.connect_activate(move |_, _| {
+            let statistics = state.borrow().statistics.clone();
+            dialogs::export_statistics(&win_for_closure, statistics, &i18n);
+        });
+        window.add_action(&action);
+    }
+
+    // Share the position through the clipboard
+    {
+        let action = SimpleAction::new("copy-position", None);
+        let win_for_closure = window.clone();
+        let state = state.clone();
+        action.connect_activate(move |_, _| {
+            let code = state.borrow().position_code();
+            win_for_closure.clipboard().set_text(&code);
+        });
+        window.add_action(&action);
+    }
+    {
+        let action = SimpleAction::new("paste-position", None);
+        let win_for_closure = window.clone();
+        let i18n = i18n.clone();
+        let state = state.clone();
+        let anim = anim.clone();
+        let drawing_area = drawing_area.clone();
+        let update_stats = update_stats.clone();
+        action.connect_activate(move |_, _| {
+            if anim.borrow().is_busy() {
+                return;
+            }
+            let win = win_for_closure.clone();
+            let i18n = i18n.clone();
+            let state = state.clone();
+            let anim = anim.clone();
+            let drawing_area = drawing_area.clone();
+            let update_stats = update_stats.clone();
+            win_for_closure.clipboard().read_text_async(
+                None::<&gtk4::gio::Cancellable>,
+                move |text| {
+                    let shared = text
+                        .ok()
+                        .flatten()
+                        .and_then(|code| GameState::from_position_code(&code));
+                    let Some(shared) = shared else {
+                        dialogs::show_invalid_position_code(&win, &i18n);
+                        return;
+                    };
+                    let running = {
+                        let st = state.borrow();
+                        st.outcome == GameOutcome::Running && st.moves_made > 0
+                    };
+                    let apply = move || {
+                        start_shared_position(&state, &anim, shared.clone());
+                        drawing_area.queue_draw();
+                        update_stats();
+                    };
+                    if running {
+                        dialogs::confirm_surrender(&win, &i18n, apply);
+                    } else {
+                        apply();
+                    }
+                },
+            );
+        });
+        window.add_action(&action);
+    }
+
+    // Replay the moves of the current round
+    {
+        let action = SimpleAction::new("replay", None);
+        let win_for_closure = window.clone();
         let i18n = i18n.clone();
+        let state = state.clone();
+        let resources = resources.clone();
+        let view = view.clone();
+        action.connect_activate(move |_, _| {
+            let game = state.borrow().clone();
+            let view = view.borrow().clone();
+            dialogs::show_replay_dialog(&win_for_closure, game, resources.clone(), view, &i18n);
+        });
+        window.add_action(&action);
+    }
+
+    // Export the round as an animated GIF
+    {
+        let action = SimpleAction::new("export-replay", None);
         let win_for_closure = window.clone();
+        let i18n = i18n.clone();
+        let state = state.clone();
+        let resources = resources.clone();
+        let view = view.clone();
         action.connect_activate(move |_, _| {
-            dialogs::show_settings_dialog(&win_for_closure, state.clone(), anim.clone(), &i18n);
+            if modal_dialog_open(&win_for_closure) {
+                return;
+            }
+            let game = state.borrow().clone();
+            let view = view.borrow().clone();
+            dialogs::export_replay(&win_for_closure, game, resources.clone(), view, &i18n);
+        });
+        window.add_action(&action);
+    }
+
+    // How to play
+    {
+        let action = SimpleAction::new("tutorial", None);
+        let win_for_closure = window.clone();
+        let i18n = i18n.clone();
+        let resources = resources.clone();
+        let view = view.clone();
+        action.connect_activate(move |_, _| {
+            if modal_dialog_open(&win_for_closure) {
+                return;
+            }
+            let view = view.borrow().clone();
+            dialogs::show_tutorial(&win_for_closure, resources.clone(), view, &i18n);
         });
         window.add_action(&action);
     }
@@ -439,17 +1758,54 @@ pub fn build_ui(app: &Application, resources_dir: &str) {
         let action = SimpleAction::new("info", None);
         let win_for_closure = window.clone();
         let i18n = i18n.clone();
+        let state = state.clone();
+        let resources = resources.clone();
         action.connect_activate(move |_, _| {
             let mut args = FluentArgs::new();
             args.set("version", env!("CARGO_PKG_VERSION"));
             let mut body = i18n.t_args("info-body", &args);
             // Fluent stores literal "\n" sequences; convert them to real newlines
             body = body.replace("\\n", "\n");
+            // What a bug report needs to know about this run
+            body.push_str("\n\n");
+            body.push_str(&glib::markup_escape_text(&runtime_info(&resources, &i18n)));
             // Append localized info link (may contain markup)
             let link = i18n.t("info-link");
             body.push_str("\n\n");
             body.push_str(&link);
-            dialogs::show_info(&win_for_closure, &i18n.t("menu-info"), &body, &i18n);
+
+            let st = state.borrow();
+            let mut level_lines: Vec<String> = st
+                .statistics
+                .per_level
+                .iter()
+                .enumerate()
+                .map(|(level, l)| {
+                    let mut args = FluentArgs::new();
+                    args.set("level", level);
+                    args.set("won", l.player_wins);
+                    args.set("lost", l.computer_wins);
+                    args.set("drawn", l.draws);
+                    i18n.t_args("stats-level-line", &args)
+                })
+                .collect();
+            let mut args = FluentArgs::new();
+            args.set("time", format_duration(st.statistics.total_seconds));
+            level_lines.push(i18n.t_args("stats-total-time", &args));
+            if let Some(fastest) = st.statistics.fastest_win_seconds {
+                let mut args = FluentArgs::new();
+                args.set("time", format_duration(fastest));
+                level_lines.push(i18n.t_args("stats-fastest-win", &args));
+            }
+            drop(st);
+            let heading = i18n.t("info-level-stats");
+            dialogs::show_info(
+                &win_for_closure,
+                &i18n.t("menu-info"),
+                &body,
+                Some((&heading, &level_lines.join("\n"))),
+                &i18n,
+            );
         });
         window.add_action(&action);
     }
@@ -483,17 +1839,241 @@ pub fn build_ui(app: &Application, resources_dir: &str) {
         });
     }
 
+    // ── Pause before the computer's move (P toggles) ──
+    {
+        let action = SimpleAction::new_stateful("pause-cpu", None, false.to_variant());
+        let anim = anim.clone();
+        let drawing_area = drawing_area.clone();
+        let win_for_closure = window.clone();
+        action.connect_activate(move |action, _| {
+            if modal_dialog_open(&win_for_closure) {
+                return;
+            }
+            let on = !action
+                .state()
+                .and_then(|v| v.get::<bool>())
+                .unwrap_or(false);
+            action.set_state(on.to_variant());
+            anim.borrow_mut().cpu_paused = on;
+            drawing_area.queue_draw();
+        });
+        window.add_action(&action);
+    }
+
+    // ── Watch AI vs AI: the player's seat moves by itself from its next turn ──
+    {
+        let action = SimpleAction::new_stateful("watch-ai", None, false.to_variant());
+        let anim = anim.clone();
+        let drawing_area = drawing_area.clone();
+        action.connect_activate(move |action, _| {
+            let on = !action
+                .state()
+                .and_then(|v| v.get::<bool>())
+                .unwrap_or(false);
+            action.set_state(on.to_variant());
+            // A move already being searched for the player's seat is still played.
+            anim.borrow_mut().watching = on;
+            drawing_area.queue_draw();
+        });
+        window.add_action(&action);
+    }
+
+    // ── Zen mode (board only, fullscreen) ──
+    {
+        let action = SimpleAction::new_stateful("zen-mode", None, false.to_variant());
+        let win = window.clone();
+        let header = header.clone();
+        let status_bar = status_bar.clone();
+        let zen_hint = zen_hint.clone();
+        let hint_generation = Rc::new(Cell::new(0u64));
+        action.connect_activate(move |action, _| {
+            if modal_dialog_open(&win) {
+                return;
+            }
+            let on = !action
+                .state()
+                .and_then(|v| v.get::<bool>())
+                .unwrap_or(false);
+            action.set_state(on.to_variant());
+            header.set_visible(!on);
+            status_bar.set_visible(!on);
+            if on {
+                win.fullscreen();
+                // Briefly show how to get the menu back
+                let gen = hint_generation.get().wrapping_add(1);
+                hint_generation.set(gen);
+                zen_hint.set_visible(true);
+                let zen_hint = zen_hint.clone();
+                let hint_generation = hint_generation.clone();
+                glib::timeout_add_local_once(ZEN_HINT_DURATION, move || {
+                    if hint_generation.get() == gen {
+                        zen_hint.set_visible(false);
+                    }
+                });
+            } else {
+                win.unfullscreen();
+                zen_hint.set_visible(false);
+            }
+            let mut s = crate::storage::load_settings();
+            s.zen_mode = on;
+            let _ = crate::storage::save_settings(&s);
+        });
+        window.add_action(&action);
+
+        // Esc leaves zen mode (F11 toggles it, see SHORTCUTS)
+        let keys = EventControllerKey::new();
+        let win = window.clone();
+        keys.connect_key_pressed(move |_, key, _, _| {
+            let zen = win
+                .lookup_action("zen-mode")
+                .and_then(|a| a.state())
+                .and_then(|v| v.get::<bool>())
+                .unwrap_or(false);
+            if zen && key == gtk4::gdk::Key::Escape {
+                let _ = WidgetExt::activate_action(&win, "win.zen-mode", None);
+                return gtk4::Inhibit(true);
+            }
+            gtk4::Inhibit(false)
+        });
+        window.add_controller(keys);
+    }
+
+    for (action, accels) in SHORTCUTS {
+        app.set_accels_for_action(action, accels);
+    }
+
     window.set_titlebar(Some(&header));
-    window.set_child(Some(&main_box));
+    window.set_child(Some(&overlay));
 
-    // Persist window size when the window is destroyed so it can be
-    // restored on next startup. Ignore save errors.
+    // Persist window size and an unfinished round when the window is destroyed
+    // so they can be restored on next startup. Ignore save errors.
     {
         let aspect_frame_for_save = aspect_frame.clone();
+        let state = state.clone();
+        let save_slot = save_slot.clone();
         window.connect_destroy(move |win| {
             save_window_geometry(win, Some(aspect_frame_for_save.clone()));
+            store_round(save_slot.get(), &state, &anim);
         });
     }
 
     window.present();
+    // Let the arrow keys reach the board right away
+    drawing_area.grab_focus();
+
+    // Point out a broken installation once, instead of only drawing placeholders
+    let missing = resources.missing();
+    if !missing.is_empty() {
+        dialogs::show_info(
+            &window,
+            &i18n.t("missing-assets-title"),
+            &i18n.t("missing-assets-body"),
+            Some((&i18n.t("missing-assets-list"), &missing.join("\n"))),
+            &i18n,
+        );
+    }
+
+    if settings.zen_mode {
+        let _ = WidgetExt::activate_action(&window, "win.zen-mode", None);
+    }
+
+    // Explain the rules once on the very first start
+    if !settings.seen_tutorial {
+        let _ = WidgetExt::activate_action(&window, "win.tutorial", None);
+        let mut settings = crate::storage::load_settings();
+        settings.seen_tutorial = true;
+        let _ = crate::storage::save_settings(&settings);
+    }
+
+    // Warn once per session if settings and statistics cannot be persisted.
+    if let Err(e) = crate::storage::check_config_dir() {
+        crate::storage::silence_save_failures();
+        dialogs::warn_config_unwritable(&window, &i18n, &e.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_pending_pulse_is_saved_as_its_move() {
+        let mut round = GameState::new();
+        round.ai_level = 2;
+        let (col, row) = round.random_move().unwrap();
+        let anim = AnimationState::new();
+        let state = RefCell::new(round);
+        let anim = RefCell::new(anim);
+        assert!(board::start_player_pulse(&state, &anim, col, row));
+
+        let stored = round_to_store(&state, &anim).expect("a round to resume");
+        assert!(matches!(anim.borrow().phase, board::AnimPhase::Idle));
+        let json = crate::storage::game_to_json(&stored).unwrap();
+        let loaded = crate::storage::game_from_json(&json).expect("a usable save");
+        let first = &loaded.move_log[0];
+        assert!(first.by_player && (first.col, first.row) == (col, row));
+        // The computer's reply was made too: the player is to move.
+        assert_eq!(loaded.move_log.len(), 2);
+        assert!(!loaded.move_log[1].by_player);
+        assert_eq!(loaded.moves_made, 2);
+    }
+
+    #[test]
+    fn window_geometry_snaps_to_the_board_aspect() {
+        // The reference size is already in the aspect ratio.
+        assert_eq!(snap_board_size(596, 393), (596, 393));
+        // The board is wider than tall, so correcting the height is the
+        // smaller change whether the size is too tall or too wide.
+        assert_eq!(snap_board_size(900, 640), (900, 593));
+        assert_eq!(snap_board_size(1200, 600), (1200, 791));
+        assert_eq!(snap_board_size(596, 394), (596, 393));
+        // A tie keeps the width.
+        assert_eq!(snap_board_size(597, 393), (597, 394));
+        // Anything smaller than the minimum board grows to it.
+        assert_eq!(snap_board_size(100, 60), (MIN_BOARD_WIDTH, 237));
+        assert_eq!(
+            snap_board_size(MIN_BOARD_WIDTH - 1, 500),
+            (MIN_BOARD_WIDTH, 237)
+        );
+    }
+
+    /// The board an aspect frame shows in a window of `window`: the largest
+    /// rectangle in the aspect ratio that fits below the chrome.
+    fn framed_board(window: (i32, i32)) -> (i32, i32) {
+        let (w, h) = (window.0, window.1 - WINDOW_CHROME_HEIGHT);
+        if w as f64 * REF_HEIGHT > h as f64 * REF_WIDTH {
+            ((h as f64 * REF_WIDTH / REF_HEIGHT).round() as i32, h)
+        } else {
+            (w, board_height_for(w))
+        }
+    }
+
+    #[test]
+    fn saving_the_same_geometry_again_does_not_drift() {
+        for start in [(596, 440), (597, 441), (900, 640), (1203, 877), (361, 290)] {
+            let mut saved = None;
+            let mut window = start;
+            let mut first = None;
+            for session in 0..50 {
+                let result = geometry_to_save(window, framed_board(window), saved);
+                let settled = *first.get_or_insert(result);
+                assert_eq!(result, settled, "{start:?} drifted in session {session}");
+                saved = Some(result);
+                window = result.0;
+            }
+        }
+        // Saving the same size repeatedly without a frame changes nothing either.
+        let size = (700, 462);
+        let mut saved = None;
+        for _ in 0..10 {
+            let result = geometry_to_save(size, size, saved);
+            assert_eq!(result, (size, size));
+            saved = Some(result);
+        }
+        // A board off the aspect ratio is still snapped, keeping the chrome.
+        assert_eq!(
+            geometry_to_save((900, 687), (900, 640), None),
+            ((900, 640), (900, 593))
+        );
+    }
 }
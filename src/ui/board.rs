@@ -1,15 +1,24 @@
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
+use std::hash::{Hash, Hasher};
 use std::rc::Rc;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
 use std::time::{Duration, Instant};
 
 use gtk4::glib;
 use gtk4::prelude::*;
-use gtk4::{DrawingArea, EventControllerMotion, GestureClick};
+use gtk4::{
+    AccessibleRole, ApplicationWindow, DrawingArea, EventControllerKey, EventControllerMotion,
+    EventControllerScroll, EventControllerScrollFlags, GestureClick, GestureLongPress,
+};
 
-use super::rendering;
+use super::dialogs;
+use super::rendering::{self, Effects, TowerHit, TowerPreview, ViewOptions};
 use super::resources::GameResources;
-use crate::game::logic::GameState;
-use crate::game::types::GameOutcome;
+use crate::ai::{MoveDecision, SearchProgress};
+use crate::game::logic::{GameState, MoveResult};
+use crate::game::types::{CellKind, GameMode, GameOutcome, Selection};
+use crate::i18n::I18n;
+use crate::storage::{DEFAULT_PULSE_MS, DEFAULT_WAIT_BEFORE_CPU_MS};
 
 /// The phases of the turn animation state machine.
 #[derive(Debug, Clone)]
@@ -23,33 +32,244 @@ pub enum AnimPhase {
         time_left: Duration,
         total: Duration,
     },
-    /// Player move was applied. Short pause before CPU thinks.
-    WaitBeforeCpu { time_left: Duration },
+    /// Player move was applied. Short pause before the CPU moves, while
+    /// `search` picks the move in the background.
+    WaitBeforeCpu {
+        time_left: Duration,
+        search: CpuSearch,
+    },
+    /// Watching the computer play itself: short pause before the AI in the
+    /// player's seat moves, while `search` picks its move. Its move then
+    /// pulses like a click.
+    WaitBeforeWatched {
+        time_left: Duration,
+        search: CpuSearch,
+    },
     /// CPU has chosen (col, row) – pulsing highlight for a fixed duration, then apply.
+    /// `axis` is the one to hand over if the move takes a wildcard banana.
     CpuPulse {
         col: usize,
         row: usize,
+        axis: Option<Selection>,
         time_left: Duration,
         total: Duration,
     },
 }
 
+/// Where a [`CpuSearch`] stands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchStatus {
+    Running,
+    Done(usize, usize),
+    /// The worker thread ended without a move (it panicked).
+    Failed,
+}
+
+/// What the search worker sends: progress reports, then the decision.
+#[derive(Debug)]
+enum SearchMessage {
+    Progress(SearchProgress),
+    Decision(MoveDecision),
+}
+
+/// The computer's move, searched on a worker thread so a deep search does not
+/// stall the UI. Dropping every clone abandons the search; the worker then
+/// finishes unseen.
+#[derive(Debug, Clone)]
+pub struct CpuSearch {
+    receiver: Rc<Receiver<SearchMessage>>,
+    status: Rc<Cell<SearchStatus>>,
+    decision: Rc<RefCell<Option<MoveDecision>>>,
+    /// The last progress report of the running search.
+    progress: Rc<Cell<Option<SearchProgress>>>,
+    /// Moves made in the searched position, to recognise a stale result.
+    moves_made: u32,
+}
+
+impl CpuSearch {
+    /// Start searching the computer's move in a copy of `state`.
+    pub fn spawn(state: &GameState) -> Self {
+        Self::start(state, GameState::compute_ai_decision_with_progress)
+    }
+
+    /// Start searching the move of the AI in the player's seat, for watching
+    /// the computer play itself.
+    pub fn spawn_watched(state: &GameState) -> Self {
+        Self::start(state, GameState::compute_watched_decision_with_progress)
+    }
+
+    fn start(
+        state: &GameState,
+        decide: fn(&GameState, &mut dyn FnMut(SearchProgress)) -> MoveDecision,
+    ) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let snapshot = state.clone();
+        std::thread::spawn(move || {
+            // The receiver is gone if the round was abandoned meanwhile.
+            let decision = decide(&snapshot, &mut |progress| {
+                let _ = sender.send(SearchMessage::Progress(progress));
+            });
+            let _ = sender.send(SearchMessage::Decision(decision));
+        });
+        Self {
+            receiver: Rc::new(receiver),
+            status: Rc::new(Cell::new(SearchStatus::Running)),
+            decision: Rc::new(RefCell::new(None)),
+            progress: Rc::new(Cell::new(None)),
+            moves_made: state.moves_made,
+        }
+    }
+
+    /// Check for the result without blocking.
+    pub fn poll(&self) -> SearchStatus {
+        while self.status.get() == SearchStatus::Running {
+            match self.receiver.try_recv() {
+                Ok(SearchMessage::Progress(progress)) => self.progress.set(Some(progress)),
+                Ok(SearchMessage::Decision(decision)) => {
+                    let (col, row) = decision.chosen;
+                    self.status.set(SearchStatus::Done(col, row));
+                    *self.decision.borrow_mut() = Some(decision);
+                }
+                Err(TryRecvError::Disconnected) => self.status.set(SearchStatus::Failed),
+                Err(TryRecvError::Empty) => break,
+            }
+        }
+        self.status.get()
+    }
+
+    /// How far the search has got, while it is still running.
+    pub fn progress(&self) -> Option<SearchProgress> {
+        (self.poll() == SearchStatus::Running)
+            .then(|| self.progress.get())
+            .flatten()
+    }
+
+    /// The full decision behind [`SearchStatus::Done`], with the scores of
+    /// the other candidates.
+    pub fn decision(&self) -> Option<MoveDecision> {
+        self.decision.borrow().clone()
+    }
+
+    /// The search was started for the position `state` is in now.
+    pub fn matches(&self, state: &GameState) -> bool {
+        state.moves_made == self.moves_made && state.outcome == GameOutcome::Running
+    }
+}
+
+/// A tower shaking and flashing red after a bomb hit it. Purely cosmetic.
+#[derive(Debug, Clone, Copy)]
+pub struct BombEffect {
+    /// The player's tower was hit (otherwise the computer's).
+    pub on_player: bool,
+    pub time_left: Duration,
+}
+
+/// The previous selection axis fading out after a move.
+#[derive(Debug, Clone, Copy)]
+pub struct SelectionFade {
+    pub from: Selection,
+    pub time_left: Duration,
+}
+
+/// `--debug-ai`: the computer's last decision, for the debug panel.
+#[derive(Debug, Clone, Default)]
+pub struct AiDebug {
+    pub last: Option<MoveDecision>,
+    /// `last` changed since the panel showed it.
+    pub unseen: bool,
+}
+
 /// Animation state: tower interpolation + turn phase machine.
 pub struct AnimationState {
     pub display_player_tower: f64,
     pub display_computer_tower: f64,
-    /// Tower animation speed: rows per second. Default 12.0 (≈0.2 per 60fps tick).
+    /// Tower animation speed: rows per second while [`EASE_ROWS`] are left to go;
+    /// towers ease out, so they move faster further away. Default 12.0.
     pub speed: f64,
     /// Current phase.
     pub phase: AnimPhase,
+    /// Accessibility: (almost) no pulses, towers jump to their new height.
+    pub reduce_motion: bool,
+    /// Ask before the player takes a bomb that would lower their tower.
+    pub confirm_harmful_moves: bool,
+    /// Hold the turn in `WaitBeforeCpu` until the player resumes.
+    pub cpu_paused: bool,
+    /// Watch the computer play itself: the AI moves for the player too, and
+    /// the board ignores the player (see [`watching`]).
+    pub watching: bool,
+    /// Running bomb effect; it also counts as busy so it ends before the next click.
+    pub bomb_effect: Option<BombEffect>,
+    /// Cross-fade between the old and new selection axis. Never blocks input.
+    pub selection_fade: Option<SelectionFade>,
+    /// Selection seen on the previous tick, to notice when it changes.
+    pub last_selection: Option<Selection>,
+    /// Only with `--debug-ai`: what the computer weighed for its last move.
+    pub ai_debug: Option<AiDebug>,
+    /// Only with `--show-fps`: smoothed time between ticks in seconds.
+    frame_time: Option<f64>,
+    /// Cells of the threat overlay, with the [`position_key`] they were
+    /// computed for; see [`AnimationState::threats`].
+    threats: Option<(u64, Vec<(usize, usize)>)>,
+    /// Pulse length of a chosen cell before its move is applied.
+    pulse: Duration,
+    /// Pause before the CPU acts.
+    wait_before_cpu: Duration,
+    /// Blitz: time allowed for each move of a human; `None` plays without a clock.
+    blitz: Option<Duration>,
+    /// Time left for the move being considered; `None` while the clock stands.
+    move_clock: Option<Duration>,
 }
 
-/// Pulse duration.
-const PULSE_DURATION: Duration = Duration::from_millis(400);
-/// Pause before the CPU acts.
-const WAIT_BEFORE_CPU_DURATION: Duration = Duration::from_millis(160);
+/// Identifies the position of `state` for [`AnimationState::threats`]: it
+/// changes with every move, undo and new round.
+fn position_key(state: &GameState) -> u64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    state.moves_made.hash(&mut hasher);
+    state.board.hash(&mut hasher);
+    state.selection.hash(&mut hasher);
+    (state.tower_player, state.tower_computer).hash(&mut hasher);
+    (
+        state.outcome == GameOutcome::Running,
+        state.awaiting_initial_selection,
+        state.choosing_axis,
+    )
+        .hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Range of the pulse duration offered in the settings; shorter pulses would
+/// hardly be visible.
+pub const MIN_PULSE_MS: u64 = 50;
+pub const MAX_PULSE_MS: u64 = 1500;
+/// Length of the shake and flash after a bomb.
+const BOMB_EFFECT_DURATION: Duration = Duration::from_millis(300);
+/// Largest horizontal shake offset in reference pixels.
+const BOMB_SHAKE_AMPLITUDE: f64 = 4.0;
+/// Shake oscillations per second.
+const BOMB_SHAKE_FREQUENCY: f64 = 25.0;
+/// Length of the selection axis cross-fade.
+const SELECTION_FADE_DURATION: Duration = Duration::from_millis(200);
+/// Range of the pause before the CPU acts offered in the settings.
+pub const MIN_WAIT_BEFORE_CPU_MS: u64 = 0;
+pub const MAX_WAIT_BEFORE_CPU_MS: u64 = 2000;
+/// Pulse and pause length with reduced motion – a single frame.
+const REDUCED_MOTION_DURATION: Duration = Duration::from_millis(16);
+/// Range of the blitz time per move offered in the settings.
+pub const MIN_BLITZ_SECONDS: u32 = 3;
+pub const MAX_BLITZ_SECONDS: u32 = 60;
+/// Time per move offered when blitz is switched on.
+pub const DEFAULT_BLITZ_SECONDS: u32 = 10;
+/// Range of the tower animation speed in rows per second offered in the settings.
+pub const MIN_ANIMATION_SPEED: f64 = 4.0;
+pub const MAX_ANIMATION_SPEED: f64 = 40.0;
 const RESIZE_INTERPOLATION_MS: u64 = 500;
+/// Distance (in rows) at which a tower moves at exactly the configured speed.
+/// The time constant of the easing is `EASE_ROWS / speed`.
+const EASE_ROWS: f64 = 3.0;
 const RESIZE_LOW_QUALITY: f64 = 0.6;
+/// Weight of the newest tick in the smoothed frame time; small enough that
+/// the counter stays readable.
+const FRAME_TIME_SMOOTHING: f64 = 0.1;
 
 struct ResizeState {
     last_size: (i32, i32),
@@ -74,12 +294,143 @@ impl AnimationState {
             display_computer_tower: 0.0,
             speed: 12.0,
             phase: AnimPhase::Idle,
+            reduce_motion: false,
+            confirm_harmful_moves: false,
+            cpu_paused: false,
+            watching: false,
+            bomb_effect: None,
+            selection_fade: None,
+            last_selection: None,
+            ai_debug: None,
+            frame_time: None,
+            threats: None,
+            pulse: Duration::from_millis(DEFAULT_PULSE_MS),
+            wait_before_cpu: Duration::from_millis(DEFAULT_WAIT_BEFORE_CPU_MS),
+            blitz: None,
+            move_clock: None,
+        }
+    }
+
+    /// Start measuring the frame time for the `--show-fps` counter.
+    pub fn show_frame_time(&mut self) {
+        self.frame_time = Some(1.0 / 60.0);
+    }
+
+    /// Fold the tick interval `dt` into the smoothed frame time. Returns
+    /// whether the counter is shown and so needs a redraw.
+    pub fn record_frame(&mut self, dt: f64) -> bool {
+        let Some(frame_time) = &mut self.frame_time else {
+            return false;
+        };
+        *frame_time += (dt - *frame_time) * FRAME_TIME_SMOOTHING;
+        true
+    }
+
+    /// Keep the computer's `decision` for the debug panel, if it is shown.
+    pub fn record_ai_decision(&mut self, decision: MoveDecision) {
+        if let Some(debug) = &mut self.ai_debug {
+            debug.last = Some(decision);
+            debug.unseen = true;
         }
     }
 
-    /// Is a pulse/wait animation running? (blocks clicks)
+    /// Is a pulse/wait animation or a bomb effect running? (blocks clicks)
     pub fn is_busy(&self) -> bool {
-        !matches!(self.phase, AnimPhase::Idle)
+        !matches!(self.phase, AnimPhase::Idle) || self.bomb_effect.is_some()
+    }
+
+    /// Shake and flash the tower that a bomb just hit (not with reduced motion).
+    pub fn start_bomb_effect(&mut self, on_player: bool) {
+        if self.reduce_motion {
+            return;
+        }
+        self.bomb_effect = Some(BombEffect {
+            on_player,
+            time_left: BOMB_EFFECT_DURATION,
+        });
+    }
+
+    /// Advance the bomb effect and the selection fade by `dt` seconds.
+    /// Returns `true` if they need a redraw.
+    pub fn tick_effects(&mut self, dt: f64) -> bool {
+        let step = Duration::from_secs_f64(dt);
+        let mut running = false;
+        if let Some(effect) = &mut self.bomb_effect {
+            effect.time_left = effect.time_left.saturating_sub(step);
+            if effect.time_left.is_zero() {
+                self.bomb_effect = None;
+            }
+            running = true;
+        }
+        if let Some(fade) = &mut self.selection_fade {
+            fade.time_left = fade.time_left.saturating_sub(step);
+            if fade.time_left.is_zero() {
+                self.selection_fade = None;
+            }
+            running = true;
+        }
+        running
+    }
+
+    /// Start a cross-fade when the active axis changed since the last call
+    /// (not with reduced motion, and not when no axis was shown before).
+    pub fn track_selection(&mut self, selection: Option<Selection>) {
+        if selection == self.last_selection {
+            return;
+        }
+        if let (Some(from), Some(_)) = (self.last_selection, selection) {
+            if !self.reduce_motion {
+                self.selection_fade = Some(SelectionFade {
+                    from,
+                    time_left: SELECTION_FADE_DURATION,
+                });
+            }
+        }
+        self.last_selection = selection;
+    }
+
+    /// [`GameState::threatening_cells`] for the threat overlay. The search is
+    /// repeated only once the position changed, not on every frame.
+    pub fn threats(&mut self, state: &GameState) -> &[(usize, usize)] {
+        let key = position_key(state);
+        if self.threats.as_ref().map(|(cached, _)| *cached) != Some(key) {
+            self.threats = Some((key, state.threatening_cells()));
+        }
+        self.threats.as_ref().map_or(&[], |(_, cells)| cells)
+    }
+
+    /// Everything the renderer needs to draw the running effects.
+    pub fn effects(&self) -> Effects<'_> {
+        let selection_fade = self.selection_fade.map(|fade| {
+            let total = SELECTION_FADE_DURATION.as_secs_f64();
+            let progress = 1.0 - fade.time_left.as_secs_f64() / total;
+            (fade.from, progress.clamp(0.0, 1.0))
+        });
+        Effects {
+            tower_hit: self.tower_hit(),
+            // Depends on the hovered cell; filled in by the draw function.
+            tower_preview: None,
+            selection_fade,
+            move_clock: self.move_clock_left(),
+            search_progress: self.search_progress(),
+            frame_time: self.frame_time,
+            // Filled in by the draw function, see [`AnimationState::threats`].
+            threats: &[],
+        }
+    }
+
+    /// Offset and tint of the tower hit by the running bomb effect, fading out.
+    pub fn tower_hit(&self) -> Option<TowerHit> {
+        let effect = self.bomb_effect?;
+        let total = BOMB_EFFECT_DURATION.as_secs_f64();
+        let elapsed = total - effect.time_left.as_secs_f64();
+        let fade = (1.0 - elapsed / total).clamp(0.0, 1.0);
+        let wave = (elapsed * BOMB_SHAKE_FREQUENCY * std::f64::consts::TAU).sin();
+        Some(TowerHit {
+            on_player: effect.on_player,
+            offset_x: wave * BOMB_SHAKE_AMPLITUDE * fade,
+            flash: 0.6 * fade,
+        })
     }
 
     /// Get the currently pulsing cell (if any) and its progress 0.0..1.0.
@@ -99,6 +450,7 @@ impl AnimationState {
                 row,
                 time_left,
                 total,
+                ..
             } => {
                 let elapsed = (*total - *time_left).as_secs_f64();
                 Some((*col, *row, (elapsed / total.as_secs_f64()).clamp(0.0, 1.0)))
@@ -107,6 +459,56 @@ impl AnimationState {
         }
     }
 
+    /// How far the computer's search has got while it is thinking; `None`
+    /// as soon as the move is chosen.
+    pub fn search_progress(&self) -> Option<SearchProgress> {
+        match &self.phase {
+            AnimPhase::WaitBeforeCpu { search, .. } => search.progress(),
+            _ => None,
+        }
+    }
+
+    /// The pause before an AI's move is over, but its search is not.
+    pub fn cpu_thinking(&self) -> bool {
+        match &self.phase {
+            AnimPhase::WaitBeforeCpu { time_left, search }
+            | AnimPhase::WaitBeforeWatched { time_left, search } => {
+                time_left.is_zero() && search.poll() == SearchStatus::Running
+            }
+            _ => false,
+        }
+    }
+
+    /// Run the blitz clock for `dt` seconds while a human is to move; it
+    /// stands (and starts afresh) during animations, the computer's turn and
+    /// after the round. Returns `true` once the time for the move ran out.
+    pub fn tick_move_clock(&mut self, dt: f64, human_to_move: bool) -> bool {
+        let limit = match self.blitz {
+            Some(limit) if human_to_move && !self.is_busy() => limit,
+            _ => {
+                self.move_clock = None;
+                return false;
+            }
+        };
+        let left = self
+            .move_clock
+            .unwrap_or(limit)
+            .saturating_sub(Duration::from_secs_f64(dt));
+        if left.is_zero() {
+            self.move_clock = None;
+            return true;
+        }
+        self.move_clock = Some(left);
+        false
+    }
+
+    /// Share of the move's time that is left (1.0 down to 0.0), while the
+    /// blitz clock runs.
+    pub fn move_clock_left(&self) -> Option<f64> {
+        let (left, limit) = (self.move_clock?, self.blitz?);
+        Some((left.as_secs_f64() / limit.as_secs_f64()).clamp(0.0, 1.0))
+    }
+
     /// Is the current pulse for the CPU?
     pub fn is_cpu_pulse(&self) -> bool {
         matches!(self.phase, AnimPhase::CpuPulse { .. })
@@ -115,23 +517,33 @@ impl AnimationState {
     /// Step toward the target tower values. Returns `true` if still animating.
     /// Advance tower interpolation by `dt` seconds.
     pub fn tick_towers(&mut self, target_player: f64, target_computer: f64, dt: f64) -> bool {
+        if self.reduce_motion {
+            let changed = self.display_player_tower != target_player
+                || self.display_computer_tower != target_computer;
+            self.display_player_tower = target_player;
+            self.display_computer_tower = target_computer;
+            return changed;
+        }
+        let time_constant = EASE_ROWS / self.speed.max(MIN_ANIMATION_SPEED);
         let mut changed = false;
         changed |= Self::step(
             &mut self.display_player_tower,
             target_player,
-            self.speed,
+            time_constant,
             dt,
         );
         changed |= Self::step(
             &mut self.display_computer_tower,
             target_computer,
-            self.speed,
+            time_constant,
             dt,
         );
         changed
     }
 
-    fn step(current: &mut f64, target: f64, speed: f64, dt: f64) -> bool {
+    /// Ease out: cover the same share of the remaining distance in equal time
+    /// (exponential smoothing), snapping onto the target once close enough.
+    fn step(current: &mut f64, target: f64, time_constant: f64, dt: f64) -> bool {
         let diff = target - *current;
         if diff.abs() < 0.01 {
             if (*current - target).abs() > f64::EPSILON {
@@ -140,8 +552,7 @@ impl AnimationState {
             }
             return false;
         }
-        let step = speed * dt;
-        *current += diff.signum() * step.min(diff.abs());
+        *current += diff * (1.0 - (-dt / time_constant).exp());
         true
     }
 
@@ -150,38 +561,194 @@ impl AnimationState {
         self.display_player_tower = player;
         self.display_computer_tower = computer;
         self.phase = AnimPhase::Idle;
+        self.bomb_effect = None;
+        self.selection_fade = None;
+        self.last_selection = None;
+        self.move_clock = None;
+    }
+
+    /// End the turn animation at once, so that a round saved now resumes in a
+    /// state the player can click on: a pulsing move is applied, a reply the
+    /// computer owes is decided (searched here if need be) and applied too,
+    /// and the phase is back to `Idle`. A watched move still being searched
+    /// is dropped. The towers jump to their heights.
+    pub fn settle(&mut self, state: &mut GameState) {
+        let phase = std::mem::replace(&mut self.phase, AnimPhase::Idle);
+        let (reply_due, search) = match phase {
+            AnimPhase::Idle | AnimPhase::WaitBeforeWatched { .. } => (false, None),
+            AnimPhase::PlayerPulse { col, row, .. } => {
+                let player_one = state.player_one_to_move();
+                let result = state.make_move(col, row, player_one);
+                let due = result == MoveResult::Continue
+                    && state.round_mode == GameMode::VsComputer
+                    && !state.choosing_axis;
+                (due, None)
+            }
+            AnimPhase::WaitBeforeCpu { search, .. } => (true, Some(search)),
+            AnimPhase::CpuPulse { col, row, axis, .. } => {
+                state.make_computer_move(col, row, axis);
+                (false, None)
+            }
+        };
+        if reply_due && state.outcome == GameOutcome::Running {
+            let decision = search
+                .filter(|search| search.poll() != SearchStatus::Running && search.matches(state))
+                .and_then(|search| search.decision())
+                .unwrap_or_else(|| state.compute_ai_decision());
+            let (col, row) = decision.chosen;
+            state.make_computer_move(col, row, decision.axis);
+        }
+        self.snap(state.tower_player as f64, state.tower_computer as f64);
     }
 
     /// Pulse duration used for player/CPU pulse.
     pub fn pulse_duration(&self) -> Duration {
-        PULSE_DURATION
+        if self.reduce_motion {
+            REDUCED_MOTION_DURATION
+        } else {
+            self.pulse
+        }
+    }
+
+    /// Configured pulse duration in milliseconds, regardless of reduced motion.
+    pub fn pulse_ms(&self) -> u64 {
+        self.pulse.as_millis() as u64
+    }
+
+    /// Set the pulse duration, clamped to the offered range.
+    pub fn set_pulse_ms(&mut self, ms: u64) {
+        self.pulse = Duration::from_millis(ms.clamp(MIN_PULSE_MS, MAX_PULSE_MS));
     }
 
     /// Wait duration before the CPU acts.
     pub fn wait_before_cpu_duration(&self) -> Duration {
-        WAIT_BEFORE_CPU_DURATION
+        if self.reduce_motion {
+            REDUCED_MOTION_DURATION
+        } else {
+            self.wait_before_cpu
+        }
+    }
+
+    /// Configured pause before the CPU acts in milliseconds, regardless of
+    /// reduced motion.
+    pub fn wait_before_cpu_ms(&self) -> u64 {
+        self.wait_before_cpu.as_millis() as u64
+    }
+
+    /// Set the pause before the CPU acts, clamped to the offered range.
+    pub fn set_wait_before_cpu_ms(&mut self, ms: u64) {
+        self.wait_before_cpu =
+            Duration::from_millis(ms.clamp(MIN_WAIT_BEFORE_CPU_MS, MAX_WAIT_BEFORE_CPU_MS));
+    }
+
+    /// Configured blitz time per move in seconds; `None` without a clock.
+    pub fn blitz_seconds(&self) -> Option<u32> {
+        self.blitz.map(|limit| limit.as_secs() as u32)
+    }
+
+    /// Turn the blitz clock on (clamped to the offered range) or off. A move
+    /// already being timed starts afresh.
+    pub fn set_blitz_seconds(&mut self, seconds: Option<u32>) {
+        self.blitz = seconds
+            .map(|s| Duration::from_secs(s.clamp(MIN_BLITZ_SECONDS, MAX_BLITZ_SECONDS) as u64));
+        self.move_clock = None;
+    }
+}
+
+/// The player watches the computer play itself in the current round, so the
+/// board does not take their moves. Two-player rounds are never watched.
+pub fn watching(state: &GameState, anim: &AnimationState) -> bool {
+    anim.watching && state.round_mode == GameMode::VsComputer
+}
+
+/// Start the player's pulse on (col, row) if it is a legal move right now.
+pub fn start_player_pulse(
+    state: &RefCell<GameState>,
+    anim: &RefCell<AnimationState>,
+    col: usize,
+    row: usize,
+) -> bool {
+    let st = state.borrow();
+    if st.outcome != GameOutcome::Running || !st.is_valid_move(col, row) {
+        return false;
+    }
+    drop(st);
+    let mut an = anim.borrow_mut();
+    let dur = an.pulse_duration();
+    an.phase = AnimPhase::PlayerPulse {
+        col,
+        row,
+        time_left: dur,
+        total: dur,
+    };
+    true
+}
+
+/// Start the player's move at (col, row) like [`start_player_pulse`], but ask
+/// first if the move would hurt their tower and the player wants to be asked.
+/// Returns `true` if the pulse started right away.
+fn request_player_move(
+    state: &Rc<RefCell<GameState>>,
+    anim: &Rc<RefCell<AnimationState>>,
+    da: &DrawingArea,
+    i18n: &Rc<I18n>,
+    col: usize,
+    row: usize,
+) -> bool {
+    let ask = anim.borrow().confirm_harmful_moves && state.borrow().is_harmful_move(col, row);
+    let window = da.root().and_downcast::<ApplicationWindow>();
+    match window {
+        Some(window) if ask => {
+            let (state, anim, da) = (state.clone(), anim.clone(), da.clone());
+            let asked_at = state.borrow().moves_made;
+            dialogs::confirm_harmful_move(&window, i18n, move || {
+                // The blitz clock may have played for the player meanwhile;
+                // the cell then belongs to another position.
+                if state.borrow().moves_made != asked_at {
+                    return;
+                }
+                if !anim.borrow().is_busy() && start_player_pulse(&state, &anim, col, row) {
+                    da.queue_draw();
+                }
+            });
+            false
+        }
+        _ => start_player_pulse(state, anim, col, row),
     }
 }
 
-/// Create the game board drawing area widget with mouse handling.
+/// Create the game board drawing area widget with mouse and keyboard handling.
 pub fn create_board(
     state: Rc<RefCell<GameState>>,
     resources: Rc<GameResources>,
     anim: Rc<RefCell<AnimationState>>,
+    view: Rc<RefCell<ViewOptions>>,
+    i18n: Rc<I18n>,
 ) -> DrawingArea {
-    let drawing_area = DrawingArea::new();
+    // Drawn content only; the label and description are set by the window.
+    let drawing_area = DrawingArea::builder()
+        .accessible_role(AccessibleRole::Img)
+        .build();
     drawing_area.set_content_width(596);
     drawing_area.set_content_height(393);
     drawing_area.set_hexpand(true);
     drawing_area.set_vexpand(true);
+    drawing_area.set_focusable(true);
     let resize_state = Rc::new(RefCell::new(ResizeState::new()));
 
+    // Moved to a monitor with another scale factor: rasterize afresh for it.
+    drawing_area.connect_scale_factor_notify(|area| {
+        rendering::retain_device_scale(area.scale_factor() as f64);
+        area.queue_draw();
+    });
+
     // --- Draw handler ---
     {
         let state = state.clone();
         let resources = resources.clone();
         let anim = anim.clone();
         let resize_state = resize_state.clone();
+        let view = view.clone();
         drawing_area.set_draw_func(move |area, cr, w, h| {
             let now = Instant::now();
             let mut rs = resize_state.borrow_mut();
@@ -209,7 +776,23 @@ pub fn create_board(
             drop(rs);
 
             let st = state.borrow();
+            let threats = if view.borrow().threats {
+                anim.borrow_mut().threats(&st).to_vec()
+            } else {
+                Vec::new()
+            };
             let an = anim.borrow();
+            // Player 2 of a two-player round gets the computer's colour.
+            let right_seat_pulse = an.is_cpu_pulse() || !st.player_one_to_move();
+            // Ghost of the hovered move's result, while nothing else is moving.
+            let tower_preview = st
+                .hovered
+                .filter(|_| !an.is_busy())
+                .and_then(|(col, row)| st.projected_tower(col, row))
+                .map(|height| TowerPreview {
+                    on_player: st.player_one_to_move(),
+                    height,
+                });
             rendering::render(
                 cr,
                 &st,
@@ -219,59 +802,211 @@ pub fn create_board(
                 an.display_player_tower,
                 an.display_computer_tower,
                 an.pulse_cell(),
-                an.is_cpu_pulse(),
+                right_seat_pulse,
                 raster_quality,
+                area.scale_factor() as f64,
+                an.cpu_paused,
+                Effects {
+                    tower_preview,
+                    threats: &threats,
+                    ..an.effects()
+                },
+                &view.borrow(),
             );
         });
     }
 
+    // Set by a long press so that lifting the finger does not also play the cell.
+    let long_pressed = Rc::new(Cell::new(false));
+
     // --- Click handler ---
     {
         let state = state.clone();
         let da = drawing_area.clone();
         let anim = anim.clone();
+        let i18n = i18n.clone();
+        let long_pressed = long_pressed.clone();
         let click = GestureClick::new();
-        click.connect_released(move |_gesture, _n, x, y| {
-            // Ignore clicks while animation is busy
-            if anim.borrow().is_busy() {
+        // Any button: the secondary one picks a row while choosing the opening axis.
+        click.set_button(0);
+        {
+            let state = state.clone();
+            let da = da.clone();
+            let anim = anim.clone();
+            let long_pressed = long_pressed.clone();
+            // Touch screens have no hover: highlight the touched cell instead.
+            click.connect_pressed(move |_, _n, x, y| {
+                long_pressed.set(false);
+                if anim.borrow().is_busy() || watching(&state.borrow(), &anim.borrow()) {
+                    return;
+                }
+                let mut st = state.borrow_mut();
+                let size = st.board.size();
+                if let Some((col, row)) =
+                    rendering::mouse_to_cell(x, y, da.width(), da.height(), size)
+                {
+                    st.update_hover(col, row);
+                }
+                drop(st);
+                da.queue_draw();
+            });
+        }
+        click.connect_released(move |gesture, _n, x, y| {
+            // Ignore clicks while animation is busy or the computer plays itself
+            if anim.borrow().is_busy()
+                || long_pressed.replace(false)
+                || watching(&state.borrow(), &anim.borrow())
+            {
                 return;
             }
+            da.grab_focus();
             let w = da.width();
             let h = da.height();
-            if let Some((col, row)) = rendering::mouse_to_cell(x, y, w, h) {
-                let st = state.borrow();
-                if st.outcome != GameOutcome::Running {
-                    return;
+            let size = state.borrow().board.size();
+            let cell = rendering::mouse_to_cell(x, y, w, h, size);
+            if state.borrow().awaiting_initial_selection {
+                if let Some((col, row)) = cell {
+                    let preview = state.borrow().preview_selection;
+                    let selection = if gesture.current_button() == gtk4::gdk::BUTTON_SECONDARY {
+                        Selection::Row(row)
+                    } else {
+                        // A scrolled-to axis is committed by the click.
+                        preview.unwrap_or(Selection::Column(col))
+                    };
+                    state.borrow_mut().choose_initial_selection(selection);
+                    da.queue_draw();
                 }
-                if !st.is_valid_move(col, row) {
-                    return;
+                return;
+            }
+            if state.borrow().choosing_axis {
+                if let Some((col, row)) = cell {
+                    let preview = state.borrow().preview_selection;
+                    let selection = if gesture.current_button() == gtk4::gdk::BUTTON_SECONDARY {
+                        Selection::Row(row)
+                    } else {
+                        preview.unwrap_or(Selection::Column(col))
+                    };
+                    let mut st = state.borrow_mut();
+                    if st.choose_axis(selection) && st.round_mode == GameMode::VsComputer {
+                        // The computer was only waiting for the axis
+                        let search = CpuSearch::spawn(&st);
+                        drop(st);
+                        let mut an = anim.borrow_mut();
+                        let wait = an.wait_before_cpu_duration();
+                        an.phase = AnimPhase::WaitBeforeCpu {
+                            time_left: wait,
+                            search,
+                        };
+                    }
+                    da.queue_draw();
                 }
-                drop(st);
+                return;
+            }
+            if gesture.current_button() != gtk4::gdk::BUTTON_PRIMARY {
+                return;
+            }
+            if let Some((col, row)) = cell {
                 // Start player pulse animation (time based)
-                let mut an = anim.borrow_mut();
-                let dur = an.pulse_duration();
-                an.phase = AnimPhase::PlayerPulse {
-                    col,
-                    row,
-                    time_left: dur,
-                    total: dur,
-                };
-                da.queue_draw();
+                if request_player_move(&state, &anim, &da, &i18n, col, row) {
+                    da.queue_draw();
+                }
             }
         });
         drawing_area.add_controller(click);
     }
 
+    // --- Long press: show the hint for the pressed axis ---
+    {
+        let state = state.clone();
+        let da = drawing_area.clone();
+        let anim = anim.clone();
+        let long_press = GestureLongPress::new();
+        long_press.connect_pressed(move |_, x, y| {
+            if anim.borrow().is_busy() {
+                return;
+            }
+            let on_axis = {
+                let mut st = state.borrow_mut();
+                let size = st.board.size();
+                match rendering::mouse_to_cell(x, y, da.width(), da.height(), size) {
+                    Some((col, row)) if st.board.get(col, row).kind != CellKind::Empty => {
+                        st.update_hover(col, row);
+                        st.hovered.is_some()
+                    }
+                    _ => false,
+                }
+            };
+            if !on_axis {
+                return;
+            }
+            long_pressed.set(true);
+            // Through the action, so the hint stays off where the menu disables it.
+            let _ = WidgetExt::activate_action(&da, "win.hint", None);
+            da.queue_draw();
+        });
+        drawing_area.add_controller(long_press);
+    }
+
+    // --- Keyboard handler: arrows move the cursor, Enter/Space plays it ---
+    {
+        let state = state.clone();
+        let da = drawing_area.clone();
+        let anim = anim.clone();
+        let keys = EventControllerKey::new();
+        keys.connect_key_pressed(move |_, key, _, _| {
+            use gtk4::gdk::Key;
+            let forward = match key {
+                Key::Right | Key::Down => Some(true),
+                Key::Left | Key::Up => Some(false),
+                Key::Return | Key::KP_Enter | Key::space => None,
+                _ => return gtk4::Inhibit(false),
+            };
+            // Swallow the keys while animating so focus does not wander off the board
+            if anim.borrow().is_busy() || watching(&state.borrow(), &anim.borrow()) {
+                return gtk4::Inhibit(true);
+            }
+            match forward {
+                Some(forward) => state.borrow_mut().move_keyboard_cursor(forward),
+                None => {
+                    let target = state.borrow().keyboard_target();
+                    if let Some((col, row)) = target {
+                        request_player_move(&state, &anim, &da, &i18n, col, row);
+                    }
+                }
+            }
+            da.queue_draw();
+            gtk4::Inhibit(true)
+        });
+        drawing_area.add_controller(keys);
+    }
+
+    // Cell under the pointer, where scrolling starts its axis preview.
+    let pointer_cell = Rc::new(Cell::new(None));
+
     // --- Mouse move handler ---
     {
         let state = state.clone();
         let da = drawing_area.clone();
+        let pointer_cell = pointer_cell.clone();
         let motion = EventControllerMotion::new();
+        {
+            let state = state.clone();
+            let da = drawing_area.clone();
+            let pointer_cell = pointer_cell.clone();
+            motion.connect_leave(move |_ctrl| {
+                pointer_cell.set(None);
+                state.borrow_mut().clear_preview_selection();
+                da.queue_draw();
+            });
+        }
         motion.connect_motion(move |_ctrl, x, y| {
             let w = da.width();
             let h = da.height();
             let mut st = state.borrow_mut();
-            if let Some((col, row)) = rendering::mouse_to_cell(x, y, w, h) {
+            let size = st.board.size();
+            let cell = rendering::mouse_to_cell(x, y, w, h, size);
+            pointer_cell.set(cell);
+            if let Some((col, row)) = cell {
                 st.update_hover(col, row);
             } else {
                 st.clear_hover();
@@ -282,5 +1017,206 @@ pub fn create_board(
         drawing_area.add_controller(motion);
     }
 
+    // --- Scroll: cycle a "what-if" preview of the axes the next move could open ---
+    {
+        let state = state.clone();
+        let da = drawing_area.clone();
+        let anim = anim.clone();
+        let scroll = EventControllerScroll::new(
+            EventControllerScrollFlags::VERTICAL | EventControllerScrollFlags::DISCRETE,
+        );
+        scroll.connect_scroll(move |_ctrl, _dx, dy| {
+            // Only while a human is to move; the computer's turn keeps the board still
+            if anim.borrow().is_busy() || dy == 0.0 {
+                return gtk4::Inhibit(false);
+            }
+            state
+                .borrow_mut()
+                .scroll_preview_selection(dy > 0.0, pointer_cell.get());
+            da.queue_draw();
+            gtk4::Inhibit(true)
+        });
+        drawing_area.add_controller(scroll);
+    }
+
     drawing_area
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn towers_ease_out_and_land_exactly() {
+        let mut anim = AnimationState::new();
+        let dt = 1.0 / 60.0;
+        let mut last_move = f64::INFINITY;
+        let mut frames = 0;
+        loop {
+            let before = anim.display_player_tower;
+            if !anim.tick_towers(10.0, 0.0, dt) {
+                break;
+            }
+            let moved = anim.display_player_tower - before;
+            assert!(anim.display_player_tower <= 10.0, "never overshoots");
+            if anim.display_player_tower < 10.0 {
+                assert!(moved < last_move, "slows down towards the target");
+            }
+            last_move = moved;
+            frames += 1;
+            assert!(frames < 600, "converges within ten seconds");
+        }
+        assert_eq!(anim.display_player_tower, 10.0);
+        assert!(!anim.tick_towers(10.0, 0.0, dt));
+
+        anim.snap(3.0, 4.0);
+        assert_eq!(
+            (anim.display_player_tower, anim.display_computer_tower),
+            (3.0, 4.0)
+        );
+    }
+
+    #[test]
+    fn frame_time_is_only_measured_when_shown() {
+        let mut anim = AnimationState::new();
+        assert!(!anim.record_frame(0.5));
+        assert_eq!(anim.effects().frame_time, None);
+        anim.show_frame_time();
+        for _ in 0..200 {
+            assert!(anim.record_frame(0.02));
+        }
+        let frame_time = anim.effects().frame_time.unwrap();
+        assert!((frame_time - 0.02).abs() < 1e-6, "settles on a steady rate");
+        anim.record_frame(1.0);
+        assert!(
+            anim.effects().frame_time.unwrap() < 0.2,
+            "one hitch is smoothed"
+        );
+    }
+
+    #[test]
+    fn pulse_and_pause_stay_in_range() {
+        let mut anim = AnimationState::new();
+        anim.set_pulse_ms(10);
+        anim.set_wait_before_cpu_ms(60_000);
+        assert_eq!(anim.pulse_duration(), Duration::from_millis(MIN_PULSE_MS));
+        assert_eq!(
+            anim.wait_before_cpu_duration(),
+            Duration::from_millis(MAX_WAIT_BEFORE_CPU_MS)
+        );
+        anim.reduce_motion = true;
+        assert_eq!(anim.pulse_duration(), REDUCED_MOTION_DURATION);
+        assert_eq!(anim.pulse_ms(), MIN_PULSE_MS);
+    }
+
+    #[test]
+    fn move_clock_runs_only_while_a_human_is_to_move() {
+        let mut anim = AnimationState::new();
+        assert!(!anim.tick_move_clock(100.0, true), "no clock without blitz");
+        anim.set_blitz_seconds(Some(1));
+        assert_eq!(anim.blitz_seconds(), Some(MIN_BLITZ_SECONDS));
+
+        assert!(!anim.tick_move_clock(1.0, true));
+        assert_eq!(anim.move_clock_left(), Some(2.0 / 3.0));
+        // The computer's turn stops the clock; the next move starts afresh.
+        assert!(!anim.tick_move_clock(1.0, false));
+        assert_eq!(anim.move_clock_left(), None);
+        assert!(!anim.tick_move_clock(2.0, true));
+        anim.start_bomb_effect(true);
+        assert!(!anim.tick_move_clock(5.0, true), "stands during animations");
+        anim.bomb_effect = None;
+        assert!(!anim.tick_move_clock(2.5, true));
+        assert!(anim.tick_move_clock(0.5, true));
+        assert_eq!(anim.move_clock_left(), None);
+    }
+
+    #[test]
+    fn threats_are_searched_once_per_position() {
+        let mut state = GameState::new();
+        let mut anim = AnimationState::new();
+        assert_eq!(anim.threats(&state), state.threatening_cells());
+        // The same position is served from the cache.
+        anim.threats = Some((position_key(&state), vec![(0, 0)]));
+        assert_eq!(anim.threats(&state), [(0, 0)]);
+
+        let (col, row) = state.random_move().unwrap();
+        state.make_move(col, row, true);
+        assert_eq!(anim.threats(&state), state.threatening_cells());
+    }
+
+    #[test]
+    fn settling_applies_the_pending_moves() {
+        let mut state = GameState::new();
+        state.ai_level = 2;
+        let (col, row) = state.random_move().unwrap();
+        let mut anim = AnimationState::new();
+        let pulse = anim.pulse_duration();
+        anim.phase = AnimPhase::PlayerPulse {
+            col,
+            row,
+            time_left: pulse,
+            total: pulse,
+        };
+        anim.settle(&mut state);
+        assert!(matches!(anim.phase, AnimPhase::Idle));
+        let first = &state.move_log[0];
+        assert!(first.by_player && (first.col, first.row) == (col, row));
+        if state.outcome == GameOutcome::Running {
+            // The computer's reply is in as well: the player is to move.
+            assert_eq!(state.move_log.len(), 2);
+            assert!(!state.move_log[1].by_player);
+            assert!(state.random_move().is_some());
+        }
+        assert_eq!(
+            (anim.display_player_tower, anim.display_computer_tower),
+            (state.tower_player as f64, state.tower_computer as f64)
+        );
+
+        // A reply already searched is the one applied.
+        let mut state = GameState::new();
+        state.ai_level = 2;
+        let (col, row) = state.random_move().unwrap();
+        state.make_move(col, row, true);
+        let search = CpuSearch::spawn(&state);
+        while search.poll() == SearchStatus::Running {
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        let searched = search.decision().unwrap().chosen;
+        anim.phase = AnimPhase::WaitBeforeCpu {
+            time_left: Duration::ZERO,
+            search,
+        };
+        anim.settle(&mut state);
+        let reply = state.move_log.last().unwrap();
+        assert!(!reply.by_player && (reply.col, reply.row) == searched);
+        assert!(matches!(anim.phase, AnimPhase::Idle));
+    }
+
+    #[test]
+    fn cpu_search_delivers_the_move_for_its_position() {
+        let mut state = GameState::new();
+        state.ai_level = 2;
+        let search = CpuSearch::spawn(&state);
+        let started = Instant::now();
+        let (col, row) = loop {
+            match search.poll() {
+                SearchStatus::Done(col, row) => break (col, row),
+                SearchStatus::Running => {
+                    assert!(started.elapsed() < Duration::from_secs(10));
+                    std::thread::sleep(Duration::from_millis(1));
+                }
+                SearchStatus::Failed => panic!("the search thread died"),
+            }
+        };
+        assert!(search.matches(&state));
+        assert_eq!(
+            search.poll(),
+            SearchStatus::Done(col, row),
+            "the result stays"
+        );
+
+        // After another move the result belongs to an old position.
+        state.make_move(col, row, true);
+        assert!(!search.matches(&state));
+    }
+}
@@ -1,20 +1,49 @@
 use std::cell::RefCell;
+use std::collections::HashMap;
 use std::rc::Rc;
 
+use gdk_pixbuf::Pixbuf;
+
 use gtk4::gio;
 use gtk4::prelude::*;
-use gtk4::{Adjustment, ApplicationWindow, Dialog, Label, ResponseType, Scale, Switch};
+use gtk4::{
+    Adjustment, ApplicationWindow, Button, CheckButton, Dialog, DrawingArea, DropDown, Expander,
+    FileChooserAction, FileChooserNative, Label, Picture, ResponseType, Scale, SpinButton, Switch,
+};
+
+use fluent_bundle::FluentArgs;
 
-use super::board::AnimationState;
+use super::board::{
+    AnimationState, DEFAULT_BLITZ_SECONDS, MAX_ANIMATION_SPEED, MAX_BLITZ_SECONDS, MAX_PULSE_MS,
+    MAX_WAIT_BEFORE_CPU_MS, MIN_ANIMATION_SPEED, MIN_BLITZ_SECONDS, MIN_PULSE_MS,
+    MIN_WAIT_BEFORE_CPU_MS,
+};
+use super::rendering::{
+    self, ViewOptions, MAX_BOARD_BRIGHTNESS, MAX_BOARD_CONTRAST, MIN_BOARD_BRIGHTNESS,
+    MIN_BOARD_CONTRAST,
+};
+use super::replay_gif;
+use super::resources::GameResources;
+use crate::game::field::{Board, BoardGenConfig, MAX_BOARD_SIZE, MIN_BOARD_SIZE};
 use crate::game::logic::GameState;
+use crate::game::types::{
+    AiPersonality, BananaRule, Cell, CellKind, ColorScheme, GameMode, OpenerMode, PlayerSide,
+    Selection, Statistics, WinRule, MAX_TARGET_HEIGHT, MIN_TARGET_HEIGHT,
+};
 use crate::i18n::I18n;
+use crate::sound::SoundPlayer;
+use crate::storage::SlotInfo;
 
-/// Show a settings dialog (AI level, animation speed, reset statistics).
+/// Show a settings dialog (AI level, animation speed, display options, reset statistics).
+/// `on_apply` runs after accepted changes were stored, e.g. to redraw the board.
 pub fn show_settings_dialog(
     parent: &ApplicationWindow,
     state: Rc<RefCell<GameState>>,
     anim: Rc<RefCell<AnimationState>>,
+    view: Rc<RefCell<ViewOptions>>,
+    sound: Rc<SoundPlayer>,
     i18n: &I18n,
+    on_apply: impl Fn() + 'static,
 ) {
     let dialog = Dialog::new();
     dialog.set_transient_for(Some(parent));
@@ -67,6 +96,13 @@ pub fn show_settings_dialog(
     }
     content.append(&level_scale);
 
+    let adaptive_switch = Switch::new();
+    adaptive_switch.set_active(state.borrow().adaptive_difficulty);
+    let adaptive_box = gtk4::Box::new(gtk4::Orientation::Horizontal, 8);
+    adaptive_box.append(&Label::new(Some(&i18n.t("settings-adaptive-difficulty"))));
+    adaptive_box.append(&adaptive_switch);
+    content.append(&adaptive_box);
+
     {
         let level_label = level_label.clone();
         let key = i18n.t("settings-level");
@@ -75,6 +111,463 @@ pub fn show_settings_dialog(
         });
     }
 
+    // ── AI personality ──
+    let personality_labels: Vec<String> = AiPersonality::ALL
+        .iter()
+        .map(|p| i18n.t(p.label_key()))
+        .collect();
+    let personality_refs: Vec<&str> = personality_labels.iter().map(String::as_str).collect();
+    let personality_drop = DropDown::from_strings(&personality_refs);
+    let current_personality = state.borrow().ai_personality;
+    let personality_idx = AiPersonality::ALL
+        .iter()
+        .position(|p| *p == current_personality)
+        .unwrap_or(0);
+    personality_drop.set_selected(personality_idx as u32);
+    let personality_box = gtk4::Box::new(gtk4::Orientation::Horizontal, 8);
+    personality_box.append(&Label::new(Some(&i18n.t("settings-ai-personality"))));
+    personality_box.append(&personality_drop);
+    content.append(&personality_box);
+
+    // ── Level of the player's seat when watching AI vs AI ──
+    let watch_level_label = Label::new(Some(&format!(
+        "{}: {}",
+        i18n.t("settings-watch-level"),
+        state.borrow().watch_level
+    )));
+    content.append(&watch_level_label);
+    let watch_level_adj = Adjustment::new(
+        state.borrow().watch_level as f64,
+        0.0,
+        crate::ai::MAX_AI_LEVEL as f64,
+        1.0,
+        1.0,
+        0.0,
+    );
+    let watch_level_scale = Scale::new(gtk4::Orientation::Horizontal, Some(&watch_level_adj));
+    watch_level_scale.set_digits(0);
+    watch_level_scale.set_hexpand(true);
+    for i in 0..=crate::ai::MAX_AI_LEVEL {
+        watch_level_scale.add_mark(i as f64, gtk4::PositionType::Bottom, Some(&i.to_string()));
+    }
+    content.append(&watch_level_scale);
+    {
+        let watch_level_label = watch_level_label.clone();
+        let key = i18n.t("settings-watch-level");
+        watch_level_adj.connect_value_changed(move |adj| {
+            watch_level_label.set_text(&format!("{}: {}", key, adj.value() as i32));
+        });
+    }
+
+    // ── Animation speed (rows per second) ──
+    let current_speed = anim
+        .borrow()
+        .speed
+        .clamp(MIN_ANIMATION_SPEED, MAX_ANIMATION_SPEED);
+    let speed_label = Label::new(Some(&format!(
+        "{}: {} {}",
+        i18n.t("settings-animation-speed"),
+        current_speed.round(),
+        i18n.t("settings-animation-speed-unit")
+    )));
+    content.append(&speed_label);
+    let speed_adj = Adjustment::new(
+        current_speed,
+        MIN_ANIMATION_SPEED,
+        MAX_ANIMATION_SPEED,
+        1.0,
+        4.0,
+        0.0,
+    );
+    let speed_scale = Scale::new(gtk4::Orientation::Horizontal, Some(&speed_adj));
+    speed_scale.set_digits(0);
+    speed_scale.set_hexpand(true);
+    content.append(&speed_scale);
+    {
+        let speed_label = speed_label.clone();
+        let key = i18n.t("settings-animation-speed");
+        let unit = i18n.t("settings-animation-speed-unit");
+        speed_adj.connect_value_changed(move |adj| {
+            speed_label.set_text(&format!("{}: {} {}", key, adj.value().round(), unit));
+        });
+    }
+
+    // ── Pulse and CPU pause (milliseconds) ──
+    let (pulse_ms, wait_ms) = {
+        let an = anim.borrow();
+        (an.pulse_ms(), an.wait_before_cpu_ms())
+    };
+    let pulse_adj = duration_scale(
+        &content,
+        i18n,
+        "settings-pulse-duration",
+        pulse_ms,
+        (MIN_PULSE_MS, MAX_PULSE_MS),
+    );
+    let wait_adj = duration_scale(
+        &content,
+        i18n,
+        "settings-cpu-wait",
+        wait_ms,
+        (MIN_WAIT_BEFORE_CPU_MS, MAX_WAIT_BEFORE_CPU_MS),
+    );
+
+    let reduce_motion_check = CheckButton::with_label(&i18n.t("settings-reduce-motion"));
+    reduce_motion_check.set_active(anim.borrow().reduce_motion);
+    content.append(&reduce_motion_check);
+
+    let confirm_harmful_check = CheckButton::with_label(&i18n.t("settings-confirm-harmful"));
+    confirm_harmful_check.set_active(anim.borrow().confirm_harmful_moves);
+    content.append(&confirm_harmful_check);
+
+    // ── Blitz: seconds per move ──
+    let blitz_seconds = anim.borrow().blitz_seconds();
+    let blitz_switch = Switch::new();
+    blitz_switch.set_active(blitz_seconds.is_some());
+    let blitz_spin =
+        SpinButton::with_range(MIN_BLITZ_SECONDS as f64, MAX_BLITZ_SECONDS as f64, 1.0);
+    blitz_spin.set_value(blitz_seconds.unwrap_or(DEFAULT_BLITZ_SECONDS) as f64);
+    blitz_spin.set_sensitive(blitz_seconds.is_some());
+    blitz_switch
+        .bind_property("active", &blitz_spin, "sensitive")
+        .build();
+    let blitz_box = gtk4::Box::new(gtk4::Orientation::Horizontal, 8);
+    blitz_box.append(&Label::new(Some(&i18n.t("settings-blitz"))));
+    blitz_box.append(&blitz_switch);
+    blitz_box.append(&blitz_spin);
+    blitz_box.append(&Label::new(Some(&i18n.t("settings-blitz-unit"))));
+    content.append(&blitz_box);
+
+    // ── Opponent (applies to the next round) ──
+    let mode_labels: Vec<String> = GameMode::ALL
+        .iter()
+        .map(|m| i18n.t(m.label_key()))
+        .collect();
+    let mode_refs: Vec<&str> = mode_labels.iter().map(String::as_str).collect();
+    let mode_drop = DropDown::from_strings(&mode_refs);
+    let current_mode = state.borrow().game_mode;
+    let mode_idx = GameMode::ALL
+        .iter()
+        .position(|m| *m == current_mode)
+        .unwrap_or(0);
+    mode_drop.set_selected(mode_idx as u32);
+    let mode_box = gtk4::Box::new(gtk4::Orientation::Horizontal, 8);
+    mode_box.append(&Label::new(Some(&i18n.t("settings-game-mode"))));
+    mode_box.append(&mode_drop);
+    content.append(&mode_box);
+
+    // ── Who begins ──
+    let opener_labels: Vec<String> = OpenerMode::ALL
+        .iter()
+        .map(|m| i18n.t(m.label_key()))
+        .collect();
+    let opener_refs: Vec<&str> = opener_labels.iter().map(String::as_str).collect();
+    let opener_drop = DropDown::from_strings(&opener_refs);
+    let current_opener = state.borrow().opener;
+    let opener_idx = OpenerMode::ALL
+        .iter()
+        .position(|m| *m == current_opener)
+        .unwrap_or(0);
+    opener_drop.set_selected(opener_idx as u32);
+    let opener_box = gtk4::Box::new(gtk4::Orientation::Horizontal, 8);
+    opener_box.append(&Label::new(Some(&i18n.t("settings-opener"))));
+    opener_box.append(&opener_drop);
+    content.append(&opener_box);
+
+    // ── Opening axis chosen by the player (applies to the next round) ──
+    let pick_axis_switch = Switch::new();
+    pick_axis_switch.set_active(state.borrow().player_picks_axis);
+    let pick_axis_box = gtk4::Box::new(gtk4::Orientation::Horizontal, 8);
+    pick_axis_box.append(&Label::new(Some(&i18n.t("settings-pick-axis"))));
+    pick_axis_box.append(&pick_axis_switch);
+    content.append(&pick_axis_box);
+
+    // ── Board size (applies to the next round) ──
+    let size_spin = SpinButton::with_range(MIN_BOARD_SIZE as f64, MAX_BOARD_SIZE as f64, 1.0);
+    size_spin.set_value(state.borrow().board_size as f64);
+    let size_box = gtk4::Box::new(gtk4::Orientation::Horizontal, 8);
+    size_box.append(&Label::new(Some(&i18n.t("settings-board-size"))));
+    size_box.append(&size_spin);
+    content.append(&size_box);
+
+    // ── Balanced boards (applies to the next round) ──
+    let balanced_switch = Switch::new();
+    balanced_switch.set_active(state.borrow().balanced_boards);
+    let balanced_box = gtk4::Box::new(gtk4::Orientation::Horizontal, 8);
+    balanced_box.append(&Label::new(Some(&i18n.t("settings-balanced-boards"))));
+    balanced_box.append(&balanced_switch);
+    content.append(&balanced_box);
+
+    // ── Advanced generation: piece mix (applies to the next round) ──
+    let gen = state.borrow().board_gen;
+    let share_spin = |value: u32| {
+        let spin = SpinButton::with_range(0.0, 100.0, 1.0);
+        spin.set_value(value as f64);
+        spin
+    };
+    let banana_spin = share_spin(gen.banana_pct);
+    let stone_spin = share_spin(gen.stone_pct);
+    let bomb_spin = share_spin(gen.bomb_pct);
+    let weight_spins: Vec<SpinButton> = gen.value_weights.iter().map(|&w| share_spin(w)).collect();
+    let gen_box = gtk4::Box::new(gtk4::Orientation::Vertical, 6);
+    gen_box.set_margin_top(6);
+    for (key, spin) in [
+        ("settings-gen-bananas", &banana_spin),
+        ("settings-gen-stones", &stone_spin),
+        ("settings-gen-bombs", &bomb_spin),
+    ] {
+        let row = gtk4::Box::new(gtk4::Orientation::Horizontal, 8);
+        row.append(&Label::new(Some(&i18n.t(key))));
+        row.append(spin);
+        gen_box.append(&row);
+    }
+    let weights_box = gtk4::Box::new(gtk4::Orientation::Horizontal, 8);
+    weights_box.append(&Label::new(Some(&i18n.t("settings-gen-value-weights"))));
+    for spin in &weight_spins {
+        weights_box.append(spin);
+    }
+    gen_box.append(&weights_box);
+    let gen_hint = Label::new(Some(&i18n.t("settings-gen-hint")));
+    gen_hint.set_wrap(true);
+    gen_hint.set_halign(gtk4::Align::Start);
+    gen_box.append(&gen_hint);
+    let gen_expander = Expander::new(Some(&i18n.t("settings-advanced-generation")));
+    gen_expander.set_child(Some(&gen_box));
+    content.append(&gen_expander);
+
+    // ── Winning tower height (applies to the next round) ──
+    let target_spin =
+        SpinButton::with_range(MIN_TARGET_HEIGHT as f64, MAX_TARGET_HEIGHT as f64, 1.0);
+    target_spin.set_value(state.borrow().target_height as f64);
+    let target_box = gtk4::Box::new(gtk4::Orientation::Horizontal, 8);
+    target_box.append(&Label::new(Some(&i18n.t("settings-target-height"))));
+    target_box.append(&target_spin);
+    content.append(&target_box);
+
+    // ── Win rule (applies to the next round) ──
+    let rule_labels: Vec<String> = WinRule::ALL.iter().map(|r| i18n.t(r.label_key())).collect();
+    let rule_refs: Vec<&str> = rule_labels.iter().map(String::as_str).collect();
+    let rule_drop = DropDown::from_strings(&rule_refs);
+    let current_rule = state.borrow().win_rule;
+    let rule_idx = WinRule::ALL
+        .iter()
+        .position(|r| *r == current_rule)
+        .unwrap_or(0);
+    rule_drop.set_selected(rule_idx as u32);
+    let rule_box = gtk4::Box::new(gtk4::Orientation::Horizontal, 8);
+    rule_box.append(&Label::new(Some(&i18n.t("settings-win-rule"))));
+    rule_box.append(&rule_drop);
+    content.append(&rule_box);
+
+    // ── Banana rule (applies to the next round) ──
+    let banana_labels: Vec<String> = BananaRule::ALL
+        .iter()
+        .map(|r| i18n.t(r.label_key()))
+        .collect();
+    let banana_refs: Vec<&str> = banana_labels.iter().map(String::as_str).collect();
+    let banana_drop = DropDown::from_strings(&banana_refs);
+    let current_banana_rule = state.borrow().banana_rule;
+    let banana_idx = BananaRule::ALL
+        .iter()
+        .position(|r| *r == current_banana_rule)
+        .unwrap_or(0);
+    banana_drop.set_selected(banana_idx as u32);
+    let banana_box = gtk4::Box::new(gtk4::Orientation::Horizontal, 8);
+    banana_box.append(&Label::new(Some(&i18n.t("settings-banana-rule"))));
+    banana_box.append(&banana_drop);
+    content.append(&banana_box);
+
+    // ── Value pips ──
+    let pips_switch = Switch::new();
+    pips_switch.set_active(view.borrow().value_pips);
+    let pips_box = gtk4::Box::new(gtk4::Orientation::Horizontal, 8);
+    pips_box.append(&Label::new(Some(&i18n.t("settings-value-pips"))));
+    pips_box.append(&pips_switch);
+    content.append(&pips_box);
+
+    // ── Value labels ──
+    let values_switch = Switch::new();
+    values_switch.set_active(view.borrow().value_labels);
+    let values_box = gtk4::Box::new(gtk4::Orientation::Horizontal, 8);
+    values_box.append(&Label::new(Some(&i18n.t("settings-show-values"))));
+    values_box.append(&values_switch);
+    content.append(&values_box);
+
+    // ── Threats ──
+    let threats_switch = Switch::new();
+    threats_switch.set_active(view.borrow().threats);
+    let threats_box = gtk4::Box::new(gtk4::Orientation::Horizontal, 8);
+    threats_box.append(&Label::new(Some(&i18n.t("settings-show-threats"))));
+    threats_box.append(&threats_switch);
+    content.append(&threats_box);
+
+    // ── Axis heatmap ──
+    let heatmap_switch = Switch::new();
+    heatmap_switch.set_active(view.borrow().axis_heatmap);
+    let heatmap_box = gtk4::Box::new(gtk4::Orientation::Horizontal, 8);
+    heatmap_box.append(&Label::new(Some(&i18n.t("settings-axis-heatmap"))));
+    heatmap_box.append(&heatmap_switch);
+    content.append(&heatmap_box);
+
+    // ── Coordinates ──
+    let coordinates_switch = Switch::new();
+    coordinates_switch.set_active(view.borrow().coordinates);
+    let coordinates_box = gtk4::Box::new(gtk4::Orientation::Horizontal, 8);
+    coordinates_box.append(&Label::new(Some(&i18n.t("settings-show-coordinates"))));
+    coordinates_box.append(&coordinates_switch);
+    content.append(&coordinates_box);
+
+    // ── Fractional tower heights ──
+    let fractional_switch = Switch::new();
+    fractional_switch.set_active(view.borrow().fractional_heights);
+    let fractional_box = gtk4::Box::new(gtk4::Orientation::Horizontal, 8);
+    fractional_box.append(&Label::new(Some(&i18n.t("settings-fractional-heights"))));
+    fractional_box.append(&fractional_switch);
+    content.append(&fractional_box);
+
+    // ── Colour scheme ──
+    let scheme_labels: Vec<String> = ColorScheme::ALL
+        .iter()
+        .map(|s| i18n.t(s.label_key()))
+        .collect();
+    let scheme_refs: Vec<&str> = scheme_labels.iter().map(String::as_str).collect();
+    let scheme_drop = DropDown::from_strings(&scheme_refs);
+    let current_scheme = view.borrow().color_scheme;
+    let scheme_idx = ColorScheme::ALL
+        .iter()
+        .position(|s| *s == current_scheme)
+        .unwrap_or(0);
+    scheme_drop.set_selected(scheme_idx as u32);
+    let scheme_box = gtk4::Box::new(gtk4::Orientation::Horizontal, 8);
+    scheme_box.append(&Label::new(Some(&i18n.t("settings-color-scheme"))));
+    scheme_box.append(&scheme_drop);
+    content.append(&scheme_box);
+
+    // ── Player side ──
+    let side_labels: Vec<String> = PlayerSide::ALL
+        .iter()
+        .map(|s| i18n.t(s.label_key()))
+        .collect();
+    let side_refs: Vec<&str> = side_labels.iter().map(String::as_str).collect();
+    let side_drop = DropDown::from_strings(&side_refs);
+    side_drop.set_selected(position_of(&PlayerSide::ALL, view.borrow().player_side));
+    let side_box = gtk4::Box::new(gtk4::Orientation::Horizontal, 8);
+    side_box.append(&Label::new(Some(&i18n.t("settings-player-side"))));
+    side_box.append(&side_drop);
+    content.append(&side_box);
+
+    // ── Brightness and contrast (percent) ──
+    let tone = view.borrow().tone;
+    let brightness_adj = percent_scale(
+        &content,
+        i18n,
+        "settings-board-brightness",
+        tone.brightness,
+        (MIN_BOARD_BRIGHTNESS, MAX_BOARD_BRIGHTNESS),
+    );
+    let contrast_adj = percent_scale(
+        &content,
+        i18n,
+        "settings-board-contrast",
+        tone.contrast,
+        (MIN_BOARD_CONTRAST, MAX_BOARD_CONTRAST),
+    );
+
+    // ── Sound effects (only offered when clips were found) ──
+    let sound_switch = Switch::new();
+    sound_switch.set_active(sound.is_enabled());
+    let sound_box = gtk4::Box::new(gtk4::Orientation::Horizontal, 8);
+    sound_box.append(&Label::new(Some(&i18n.t("settings-sound"))));
+    sound_box.append(&sound_switch);
+    sound_box.set_visible(sound.has_clips());
+    content.append(&sound_box);
+
+    // ── Restore defaults: only the controls, applied on OK like any change ──
+    let defaults_button = Button::with_label(&i18n.t("settings-restore-defaults"));
+    defaults_button.set_halign(gtk4::Align::Start);
+    content.append(&defaults_button);
+    {
+        let level_adj = level_adj.clone();
+        let adaptive_switch = adaptive_switch.clone();
+        let personality_drop = personality_drop.clone();
+        let watch_level_adj = watch_level_adj.clone();
+        let speed_adj = speed_adj.clone();
+        let pulse_adj = pulse_adj.clone();
+        let wait_adj = wait_adj.clone();
+        let reduce_motion_check = reduce_motion_check.clone();
+        let confirm_harmful_check = confirm_harmful_check.clone();
+        let blitz_switch = blitz_switch.clone();
+        let blitz_spin = blitz_spin.clone();
+        let mode_drop = mode_drop.clone();
+        let opener_drop = opener_drop.clone();
+        let pick_axis_switch = pick_axis_switch.clone();
+        let size_spin = size_spin.clone();
+        let balanced_switch = balanced_switch.clone();
+        let banana_spin = banana_spin.clone();
+        let stone_spin = stone_spin.clone();
+        let bomb_spin = bomb_spin.clone();
+        let weight_spins = weight_spins.clone();
+        let target_spin = target_spin.clone();
+        let rule_drop = rule_drop.clone();
+        let banana_drop = banana_drop.clone();
+        let pips_switch = pips_switch.clone();
+        let values_switch = values_switch.clone();
+        let threats_switch = threats_switch.clone();
+        let heatmap_switch = heatmap_switch.clone();
+        let coordinates_switch = coordinates_switch.clone();
+        let fractional_switch = fractional_switch.clone();
+        let scheme_drop = scheme_drop.clone();
+        let side_drop = side_drop.clone();
+        let brightness_adj = brightness_adj.clone();
+        let contrast_adj = contrast_adj.clone();
+        let sound_switch = sound_switch.clone();
+        defaults_button.connect_clicked(move |_| {
+            let defaults = crate::storage::Settings::default();
+            // The value-changed handlers update the labels of the sliders.
+            level_adj.set_value(defaults.ai_level as f64);
+            watch_level_adj.set_value(defaults.watch_level as f64);
+            adaptive_switch.set_active(defaults.adaptive_difficulty);
+            personality_drop
+                .set_selected(position_of(&AiPersonality::ALL, defaults.ai_personality));
+            speed_adj.set_value(
+                defaults
+                    .animation_speed
+                    .clamp(MIN_ANIMATION_SPEED, MAX_ANIMATION_SPEED),
+            );
+            pulse_adj.set_value(defaults.pulse_ms as f64);
+            wait_adj.set_value(defaults.wait_before_cpu_ms as f64);
+            reduce_motion_check.set_active(defaults.reduce_motion);
+            confirm_harmful_check.set_active(defaults.confirm_harmful_moves);
+            blitz_switch.set_active(defaults.blitz_seconds.is_some());
+            blitz_spin.set_value(defaults.blitz_seconds.unwrap_or(DEFAULT_BLITZ_SECONDS) as f64);
+            mode_drop.set_selected(position_of(&GameMode::ALL, defaults.game_mode));
+            opener_drop.set_selected(position_of(&OpenerMode::ALL, defaults.opener));
+            pick_axis_switch.set_active(defaults.player_picks_axis);
+            size_spin.set_value(defaults.board_size as f64);
+            balanced_switch.set_active(defaults.balanced_boards);
+            banana_spin.set_value(defaults.board_gen.banana_pct as f64);
+            stone_spin.set_value(defaults.board_gen.stone_pct as f64);
+            bomb_spin.set_value(defaults.board_gen.bomb_pct as f64);
+            for (spin, &weight) in weight_spins.iter().zip(&defaults.board_gen.value_weights) {
+                spin.set_value(weight as f64);
+            }
+            target_spin.set_value(defaults.target_height as f64);
+            rule_drop.set_selected(position_of(&WinRule::ALL, defaults.win_rule));
+            banana_drop.set_selected(position_of(&BananaRule::ALL, defaults.banana_rule));
+            pips_switch.set_active(defaults.value_pips);
+            values_switch.set_active(defaults.show_values);
+            threats_switch.set_active(defaults.show_threats);
+            heatmap_switch.set_active(defaults.show_axis_heatmap);
+            coordinates_switch.set_active(defaults.show_coordinates);
+            fractional_switch.set_active(defaults.show_fractional_heights);
+            scheme_drop.set_selected(position_of(&ColorScheme::ALL, defaults.color_scheme));
+            side_drop.set_selected(position_of(&PlayerSide::ALL, defaults.player_side));
+            brightness_adj.set_value(defaults.board_brightness * 100.0);
+            contrast_adj.set_value(defaults.board_contrast * 100.0);
+            sound_switch.set_active(defaults.sound_enabled);
+        });
+    }
+
     // ── Reset statistics ──
     let reset_switch = Switch::new();
     reset_switch.set_active(false);
@@ -91,17 +584,105 @@ pub fn show_settings_dialog(
         if response == ResponseType::Accept {
             let mut st = state_clone.borrow_mut();
             st.ai_level = level_adj.value() as i32;
+            st.watch_level = watch_level_adj.value() as i32;
+            st.adaptive_difficulty = adaptive_switch.is_active();
+            st.ai_personality = AiPersonality::ALL
+                .get(personality_drop.selected() as usize)
+                .copied()
+                .unwrap_or_default();
+            st.game_mode = GameMode::ALL
+                .get(mode_drop.selected() as usize)
+                .copied()
+                .unwrap_or_default();
+            st.opener = OpenerMode::ALL
+                .get(opener_drop.selected() as usize)
+                .copied()
+                .unwrap_or_default();
+            st.board_size = size_spin.value_as_int() as usize;
+            st.target_height = target_spin.value_as_int();
+            st.win_rule = WinRule::ALL
+                .get(rule_drop.selected() as usize)
+                .copied()
+                .unwrap_or_default();
+            st.banana_rule = BananaRule::ALL
+                .get(banana_drop.selected() as usize)
+                .copied()
+                .unwrap_or_default();
+            st.balanced_boards = balanced_switch.is_active();
+            let mut value_weights = [0; 4];
+            for (weight, spin) in value_weights.iter_mut().zip(&weight_spins) {
+                *weight = spin.value_as_int() as u32;
+            }
+            st.board_gen = BoardGenConfig {
+                banana_pct: banana_spin.value_as_int() as u32,
+                stone_pct: stone_spin.value_as_int() as u32,
+                bomb_pct: bomb_spin.value_as_int() as u32,
+                value_weights,
+            }
+            .validated();
+            st.player_picks_axis = pick_axis_switch.is_active();
             if reset_switch_clone.is_active() {
                 st.statistics.reset();
                 let _ = crate::storage::save_statistics(&st.statistics);
             }
 
+            let blitz = blitz_switch
+                .is_active()
+                .then(|| blitz_spin.value_as_int() as u32);
             // Persist updated settings (ai level + animation speed)
-            let current_anim_speed = anim_clone.borrow().speed;
+            let current_anim_speed = {
+                let mut an = anim_clone.borrow_mut();
+                an.speed = speed_adj.value().round();
+                an.set_pulse_ms(pulse_adj.value().round() as u64);
+                an.set_wait_before_cpu_ms(wait_adj.value().round() as u64);
+                an.set_blitz_seconds(blitz);
+                an.reduce_motion = reduce_motion_check.is_active();
+                an.confirm_harmful_moves = confirm_harmful_check.is_active();
+                an.speed
+            };
             let mut settings = crate::storage::load_settings();
             settings.ai_level = st.ai_level;
+            settings.watch_level = st.watch_level;
+            settings.adaptive_difficulty = st.adaptive_difficulty;
+            settings.ai_personality = st.ai_personality;
+            settings.opener = st.opener;
+            settings.game_mode = st.game_mode;
+            settings.board_size = st.board_size;
+            settings.target_height = st.target_height;
+            settings.win_rule = st.win_rule;
+            settings.banana_rule = st.banana_rule;
+            settings.balanced_boards = st.balanced_boards;
+            settings.board_gen = st.board_gen;
+            settings.player_picks_axis = st.player_picks_axis;
             settings.animation_speed = current_anim_speed;
+            settings.pulse_ms = pulse_adj.value().round() as u64;
+            settings.wait_before_cpu_ms = wait_adj.value().round() as u64;
+            settings.blitz_seconds = blitz;
+            settings.reduce_motion = reduce_motion_check.is_active();
+            settings.confirm_harmful_moves = confirm_harmful_check.is_active();
+            settings.value_pips = pips_switch.is_active();
+            settings.show_values = values_switch.is_active();
+            settings.show_threats = threats_switch.is_active();
+            settings.show_axis_heatmap = heatmap_switch.is_active();
+            settings.show_coordinates = coordinates_switch.is_active();
+            settings.show_fractional_heights = fractional_switch.is_active();
+            settings.sound_enabled = sound_switch.is_active();
+            sound.set_enabled(settings.sound_enabled);
+            settings.color_scheme = ColorScheme::ALL
+                .get(scheme_drop.selected() as usize)
+                .copied()
+                .unwrap_or_default();
+            settings.player_side = PlayerSide::ALL
+                .get(side_drop.selected() as usize)
+                .copied()
+                .unwrap_or_default();
+            settings.board_brightness = brightness_adj.value().round() / 100.0;
+            settings.board_contrast = contrast_adj.value().round() / 100.0;
             let _ = crate::storage::save_settings(&settings);
+            drop(st);
+
+            *view.borrow_mut() = ViewOptions::from_settings(&settings);
+            on_apply();
         }
         dialog.close();
     });
@@ -109,10 +690,133 @@ pub fn show_settings_dialog(
     dialog.show();
 }
 
+/// Index of `value` in `all`, for selecting it in a drop-down.
+fn position_of<T: PartialEq>(all: &[T], value: T) -> u32 {
+    all.iter().position(|v| *v == value).unwrap_or(0) as u32
+}
+
+/// Append a labelled slider for a duration in milliseconds to `content` and
+/// return its adjustment. `key` names the setting in the label.
+fn duration_scale(
+    content: &gtk4::Box,
+    i18n: &I18n,
+    key: &str,
+    value_ms: u64,
+    (min_ms, max_ms): (u64, u64),
+) -> Adjustment {
+    let name = i18n.t(key);
+    let unit = i18n.t("settings-duration-unit");
+    let label = Label::new(Some(&format!("{}: {} {}", name, value_ms, unit)));
+    content.append(&label);
+    let adj = Adjustment::new(
+        value_ms as f64,
+        min_ms as f64,
+        max_ms as f64,
+        10.0,
+        100.0,
+        0.0,
+    );
+    let scale = Scale::new(gtk4::Orientation::Horizontal, Some(&adj));
+    scale.set_digits(0);
+    scale.set_hexpand(true);
+    content.append(&scale);
+    adj.connect_value_changed(move |adj| {
+        label.set_text(&format!("{}: {} {}", name, adj.value().round(), unit));
+    });
+    adj
+}
+
+/// Append a labelled slider in percent for a factor or share `value` to
+/// `content` and return its adjustment, which counts in percent.
+fn percent_scale(
+    content: &gtk4::Box,
+    i18n: &I18n,
+    key: &str,
+    value: f64,
+    (min, max): (f64, f64),
+) -> Adjustment {
+    let name = i18n.t(key);
+    let percent = (value * 100.0).round();
+    let label = Label::new(Some(&format!("{}: {} %", name, percent)));
+    content.append(&label);
+    let adj = Adjustment::new(percent, min * 100.0, max * 100.0, 5.0, 10.0, 0.0);
+    let scale = Scale::new(gtk4::Orientation::Horizontal, Some(&adj));
+    scale.set_digits(0);
+    scale.set_hexpand(true);
+    content.append(&scale);
+    adj.connect_value_changed(move |adj| {
+        label.set_text(&format!("{}: {} %", name, adj.value().round()));
+    });
+    adj
+}
+
 /// Show a "surrender?" confirmation dialog.
 pub fn confirm_surrender(parent: &ApplicationWindow, i18n: &I18n, on_confirm: impl Fn() + 'static) {
+    confirm(
+        parent,
+        &i18n.t("surrender-title"),
+        &i18n.t("surrender-message"),
+        i18n,
+        on_confirm,
+    );
+}
+
+/// Ask both players whether they agree to end the round as a draw.
+pub fn confirm_draw(parent: &ApplicationWindow, i18n: &I18n, on_confirm: impl Fn() + 'static) {
+    confirm(
+        parent,
+        &i18n.t("draw-offer-title"),
+        &i18n.t("draw-offer-message"),
+        i18n,
+        on_confirm,
+    );
+}
+
+/// Ask before taking a bomb that would lower the player's tower.
+pub fn confirm_harmful_move(
+    parent: &ApplicationWindow,
+    i18n: &I18n,
+    on_confirm: impl Fn() + 'static,
+) {
+    confirm(
+        parent,
+        &i18n.t("harmful-move-title"),
+        &i18n.t("harmful-move-message"),
+        i18n,
+        on_confirm,
+    );
+}
+
+/// Ask before playing a daily puzzle again; `best` describes the best result so far.
+pub fn confirm_puzzle_replay(
+    parent: &ApplicationWindow,
+    attempts: u32,
+    best: &str,
+    i18n: &I18n,
+    on_confirm: impl Fn() + 'static,
+) {
+    let mut args = FluentArgs::new();
+    args.set("attempts", attempts);
+    args.set("result", best);
+    confirm(
+        parent,
+        &i18n.t("daily-replay-title"),
+        &i18n.t_args("daily-replay-message", &args),
+        i18n,
+        on_confirm,
+    );
+}
+
+/// Ask a yes/no question and call `on_confirm` on OK.
+fn confirm(
+    parent: &ApplicationWindow,
+    title: &str,
+    message: &str,
+    i18n: &I18n,
+    on_confirm: impl Fn() + 'static,
+) {
     let dialog = Dialog::with_buttons(
-        Some(&i18n.t("surrender-title")),
+        Some(title),
         Some(parent),
         gtk4::DialogFlags::MODAL | gtk4::DialogFlags::DESTROY_WITH_PARENT,
         &[
@@ -126,7 +830,7 @@ pub fn confirm_surrender(parent: &ApplicationWindow, i18n: &I18n, on_confirm: im
     content.set_margin_end(16);
     content.set_margin_top(12);
     content.set_margin_bottom(12);
-    let label = Label::new(Some(&i18n.t("surrender-message")));
+    let label = Label::new(Some(message));
     label.set_wrap(true);
     content.append(&label);
 
@@ -140,8 +844,15 @@ pub fn confirm_surrender(parent: &ApplicationWindow, i18n: &I18n, on_confirm: im
     dialog.show();
 }
 
-/// Show a simple info message box.
-pub fn show_info(parent: &ApplicationWindow, title: &str, message: &str, i18n: &I18n) {
+/// Show a simple info message box. `details` adds a collapsed section
+/// (heading, plain text) below the message.
+pub fn show_info(
+    parent: &ApplicationWindow,
+    title: &str,
+    message: &str,
+    details: Option<(&str, &str)>,
+    i18n: &I18n,
+) {
     let dialog = Dialog::new();
     dialog.set_transient_for(Some(parent));
     dialog.set_modal(true);
@@ -174,6 +885,15 @@ pub fn show_info(parent: &ApplicationWindow, title: &str, message: &str, i18n: &
     });
     content.append(&label);
 
+    if let Some((heading, text)) = details {
+        let details_label = Label::new(Some(text));
+        details_label.set_halign(gtk4::Align::Start);
+        details_label.set_margin_top(6);
+        let expander = Expander::new(Some(heading));
+        expander.set_child(Some(&details_label));
+        content.append(&expander);
+    }
+
     dialog.connect_response(|dialog, _| {
         dialog.close();
     });
@@ -184,6 +904,613 @@ pub fn show_info(parent: &ApplicationWindow, title: &str, message: &str, i18n: &
     dialog.show();
 }
 
+/// Width of the board thumbnails in the load-game dialog, in pixels.
+const THUMBNAIL_WIDTH: i32 = 96;
+
+/// Board thumbnails of the save slots, kept while the application runs so
+/// the load-game dialog opens quickly.
+pub struct SlotThumbnails {
+    resources: Rc<GameResources>,
+    /// Per slot: the save stamp (time and move count) the thumbnail shows.
+    entries: HashMap<usize, ((u64, u32), Pixbuf)>,
+}
+
+impl SlotThumbnails {
+    pub fn new(resources: Rc<GameResources>) -> Self {
+        Self {
+            resources,
+            entries: HashMap::new(),
+        }
+    }
+
+    /// The thumbnail of a slot in use. An overwritten slot has a new save
+    /// stamp, so its old thumbnail is rendered anew.
+    fn get(&mut self, info: &SlotInfo, view: &ViewOptions) -> Option<Pixbuf> {
+        if !info.in_use {
+            self.entries.remove(&info.slot);
+            return None;
+        }
+        let stamp = (info.saved_at, info.moves_made);
+        if let Some((cached, pixbuf)) = self.entries.get(&info.slot) {
+            if *cached == stamp {
+                return Some(pixbuf.clone());
+            }
+        }
+        let state = crate::storage::load_game_slot(info.slot)?;
+        let pixbuf = rendering::render_thumbnail(&state, &self.resources, view, THUMBNAIL_WIDTH)?;
+        self.entries.insert(info.slot, (stamp, pixbuf.clone()));
+        Some(pixbuf)
+    }
+}
+
+/// List the save slots and let the player pick one. `on_pick` receives the
+/// chosen slot; an empty slot means starting a new round in it.
+pub fn show_load_game_dialog(
+    parent: &ApplicationWindow,
+    slots: &[SlotInfo],
+    current: usize,
+    thumbnails: &mut SlotThumbnails,
+    view: &ViewOptions,
+    i18n: &I18n,
+    on_pick: impl Fn(usize) + 'static,
+) {
+    let dialog = Dialog::new();
+    dialog.set_transient_for(Some(parent));
+    dialog.set_modal(true);
+    dialog.set_destroy_with_parent(true);
+    dialog.set_title(Some(&i18n.t("load-game-title")));
+    dialog.set_default_width(380);
+    let cancel_btn = dialog.add_button(&i18n.t("cancel"), ResponseType::Cancel);
+    cancel_btn.set_margin_start(8);
+    cancel_btn.set_margin_end(8);
+    cancel_btn.set_margin_top(6);
+    cancel_btn.set_margin_bottom(6);
+
+    let content = dialog.content_area();
+    content.set_spacing(8);
+    content.set_margin_start(16);
+    content.set_margin_end(16);
+    content.set_margin_top(12);
+    content.set_margin_bottom(12);
+
+    let on_pick = Rc::new(on_pick);
+    for info in slots {
+        let mut args = FluentArgs::new();
+        args.set("slot", info.slot + 1);
+        let key = if info.slot == current {
+            "load-game-slot-current"
+        } else {
+            "load-game-slot"
+        };
+        let name = Label::new(Some(&i18n.t_args(key, &args)));
+        name.set_halign(gtk4::Align::Start);
+
+        let details = if info.in_use {
+            let date = gtk4::glib::DateTime::from_unix_local(info.saved_at as i64)
+                .and_then(|d| d.format("%x %X"))
+                .map(|d| d.to_string())
+                .unwrap_or_default();
+            let mut args = FluentArgs::new();
+            args.set("moves", info.moves_made);
+            args.set("date", date);
+            i18n.t_args("load-game-details", &args)
+        } else {
+            i18n.t("load-game-empty")
+        };
+        let details = Label::new(Some(&details));
+        details.set_halign(gtk4::Align::Start);
+        details.add_css_class("dim-label");
+
+        let text = gtk4::Box::new(gtk4::Orientation::Vertical, 2);
+        text.set_hexpand(true);
+        text.append(&name);
+        text.append(&details);
+
+        let button = Button::with_label(&i18n.t(if info.in_use {
+            "load-game-open"
+        } else {
+            "load-game-start"
+        }));
+        button.set_valign(gtk4::Align::Center);
+        {
+            let dialog = dialog.clone();
+            let on_pick = on_pick.clone();
+            let slot = info.slot;
+            button.connect_clicked(move |_| {
+                dialog.close();
+                on_pick(slot);
+            });
+        }
+
+        let row = gtk4::Box::new(gtk4::Orientation::Horizontal, 8);
+        match thumbnails.get(info, view) {
+            Some(pixbuf) => {
+                let picture = Picture::for_pixbuf(&pixbuf);
+                picture.set_can_shrink(false);
+                row.append(&picture);
+            }
+            None => {
+                // Keep the texts of all slots aligned.
+                let placeholder = gtk4::Box::new(gtk4::Orientation::Horizontal, 0);
+                placeholder.set_size_request(THUMBNAIL_WIDTH, -1);
+                row.append(&placeholder);
+            }
+        }
+        row.append(&text);
+        row.append(&button);
+        content.append(&row);
+    }
+
+    dialog.connect_response(|dialog, _| {
+        dialog.close();
+    });
+    dialog.show();
+}
+
+/// Step through the moves of `game` one at a time. Starts at the latest move.
+pub fn show_replay_dialog(
+    parent: &ApplicationWindow,
+    game: GameState,
+    resources: Rc<GameResources>,
+    view: ViewOptions,
+    i18n: &I18n,
+) {
+    let dialog = Dialog::new();
+    dialog.set_transient_for(Some(parent));
+    dialog.set_modal(true);
+    dialog.set_destroy_with_parent(true);
+    dialog.set_title(Some(&i18n.t("replay-title")));
+    dialog.set_default_size(520, 560);
+    let close_btn = dialog.add_button(&i18n.t("ok"), ResponseType::Close);
+    close_btn.set_margin_start(8);
+    close_btn.set_margin_end(8);
+    close_btn.set_margin_top(6);
+    close_btn.set_margin_bottom(6);
+
+    let content = dialog.content_area();
+    content.set_spacing(8);
+    content.set_margin_start(16);
+    content.set_margin_end(16);
+    content.set_margin_top(12);
+    content.set_margin_bottom(12);
+
+    let total = game.move_log.len();
+    let step = Rc::new(RefCell::new(total));
+    let shown = Rc::new(RefCell::new(game.replay_to(total)));
+
+    let area = DrawingArea::new();
+    area.set_vexpand(true);
+    area.set_hexpand(true);
+    {
+        let shown = shown.clone();
+        let step = step.clone();
+        let game = game.clone();
+        area.set_draw_func(move |area, cr, w, h| {
+            let st = shown.borrow();
+            let last = step.borrow().checked_sub(1).map(|i| game.move_log[i]);
+            // Only redrawn on a step, so searching here is cheap enough.
+            let threats = if view.threats {
+                st.threatening_cells()
+            } else {
+                Vec::new()
+            };
+            rendering::render(
+                cr,
+                &st,
+                &resources,
+                w,
+                h,
+                st.tower_player as f64,
+                st.tower_computer as f64,
+                last.map(|m| (m.col, m.row, 0.25)),
+                last.is_some_and(|m| !m.by_player),
+                1.0,
+                area.scale_factor() as f64,
+                false,
+                rendering::Effects {
+                    threats: &threats,
+                    ..Default::default()
+                },
+                &view,
+            );
+        });
+    }
+    content.append(&area);
+
+    let nav = gtk4::Box::new(gtk4::Orientation::Horizontal, 8);
+    nav.set_halign(gtk4::Align::Center);
+    let prev_btn = Button::from_icon_name("go-previous-symbolic");
+    let next_btn = Button::from_icon_name("go-next-symbolic");
+    let step_label = Label::new(None);
+    nav.append(&prev_btn);
+    nav.append(&step_label);
+    nav.append(&next_btn);
+    content.append(&nav);
+
+    let step_texts: Vec<String> = (0..=total)
+        .map(|n| {
+            let mut args = FluentArgs::new();
+            args.set("step", n);
+            args.set("total", total);
+            i18n.t_args("replay-step", &args)
+        })
+        .collect();
+    let show_step = {
+        let step = step.clone();
+        let area = area.clone();
+        let prev_btn = prev_btn.clone();
+        let next_btn = next_btn.clone();
+        let step_label = step_label.clone();
+        move |n: usize| {
+            *step.borrow_mut() = n;
+            *shown.borrow_mut() = game.replay_to(n);
+            step_label.set_text(&step_texts[n]);
+            prev_btn.set_sensitive(n > 0);
+            next_btn.set_sensitive(n < total);
+            area.queue_draw();
+        }
+    };
+    show_step(total);
+    let show_step = Rc::new(show_step);
+    {
+        let step = step.clone();
+        let show_step = show_step.clone();
+        prev_btn.connect_clicked(move |_| {
+            let n = *step.borrow();
+            show_step(n.saturating_sub(1));
+        });
+    }
+    {
+        let show_step = show_step.clone();
+        next_btn.connect_clicked(move |_| {
+            let n = *step.borrow();
+            show_step((n + 1).min(total));
+        });
+    }
+
+    dialog.connect_response(|dialog, _| {
+        dialog.close();
+    });
+    dialog.show();
+}
+
+/// Seed of the example board behind the tutorial; column 2 is set by hand.
+const TUTORIAL_SEED: u64 = 7;
+
+/// One page of the tutorial.
+struct TutorialStep {
+    /// Key of the explanation.
+    message: &'static str,
+    /// Example position shown above it.
+    state: GameState,
+    /// Cell to point at.
+    cell: Option<(usize, usize)>,
+}
+
+/// The steps of the tutorial, in order.
+fn tutorial_steps() -> Vec<TutorialStep> {
+    let (mut board, _) = Board::new_seeded(6, TUTORIAL_SEED);
+    let piece = |kind, value| Cell { kind, value };
+    board.set(2, 0, piece(CellKind::Empty, 0));
+    board.set(2, 1, piece(CellKind::Stone, 2));
+    board.set(2, 2, piece(CellKind::Empty, 0));
+    board.set(2, 3, piece(CellKind::Bomb, 1));
+    board.set(2, 4, piece(CellKind::Banana, 0));
+    board.set(2, 5, piece(CellKind::Stone, 0));
+
+    let mut start = GameState::with_board_size(board.size());
+    start.board = board;
+    start.selection = Selection::Column(2);
+    start.tower_player = 4;
+    start.tower_computer = 3;
+    let mut flipped = start.clone();
+    flipped.make_move(2, 1, true);
+
+    let step = |message, state: &GameState, cell| TutorialStep {
+        message,
+        state: state.clone(),
+        cell,
+    };
+    vec![
+        step("tutorial-goal", &start, None),
+        step("tutorial-stones", &start, Some((2, 1))),
+        step("tutorial-bombs", &start, Some((2, 3))),
+        step("tutorial-banana", &start, Some((2, 4))),
+        step("tutorial-axis", &flipped, Some((2, 1))),
+    ]
+}
+
+/// Explain the rules step by step on an example board.
+pub fn show_tutorial(
+    parent: &ApplicationWindow,
+    resources: Rc<GameResources>,
+    view: ViewOptions,
+    i18n: &I18n,
+) {
+    let dialog = Dialog::new();
+    dialog.set_transient_for(Some(parent));
+    dialog.set_modal(true);
+    dialog.set_destroy_with_parent(true);
+    dialog.set_title(Some(&i18n.t("tutorial-title")));
+    dialog.set_default_size(520, 640);
+    let close_btn = dialog.add_button(&i18n.t("ok"), ResponseType::Close);
+    close_btn.set_margin_start(8);
+    close_btn.set_margin_end(8);
+    close_btn.set_margin_top(6);
+    close_btn.set_margin_bottom(6);
+
+    let content = dialog.content_area();
+    content.set_spacing(8);
+    content.set_margin_start(16);
+    content.set_margin_end(16);
+    content.set_margin_top(12);
+    content.set_margin_bottom(12);
+
+    let steps = Rc::new(tutorial_steps());
+    let total = steps.len();
+    let step = Rc::new(RefCell::new(0));
+
+    let area = DrawingArea::new();
+    area.set_vexpand(true);
+    area.set_hexpand(true);
+    {
+        let steps = steps.clone();
+        let step = step.clone();
+        area.set_draw_func(move |area, cr, w, h| {
+            let TutorialStep {
+                state: st, cell, ..
+            } = &steps[*step.borrow()];
+            let threats = if view.threats {
+                st.threatening_cells()
+            } else {
+                Vec::new()
+            };
+            rendering::render(
+                cr,
+                st,
+                &resources,
+                w,
+                h,
+                st.tower_player as f64,
+                st.tower_computer as f64,
+                cell.map(|(col, row)| (col, row, 0.25)),
+                false,
+                1.0,
+                area.scale_factor() as f64,
+                false,
+                rendering::Effects {
+                    threats: &threats,
+                    ..Default::default()
+                },
+                &view,
+            );
+        });
+    }
+    content.append(&area);
+
+    let text = Label::new(None);
+    text.set_wrap(true);
+    text.set_justify(gtk4::Justification::Center);
+    content.append(&text);
+
+    let nav = gtk4::Box::new(gtk4::Orientation::Horizontal, 8);
+    nav.set_halign(gtk4::Align::Center);
+    let prev_btn = Button::from_icon_name("go-previous-symbolic");
+    let next_btn = Button::from_icon_name("go-next-symbolic");
+    let step_label = Label::new(None);
+    nav.append(&prev_btn);
+    nav.append(&step_label);
+    nav.append(&next_btn);
+    content.append(&nav);
+
+    let texts: Vec<(String, String)> = steps
+        .iter()
+        .enumerate()
+        .map(|(n, step)| {
+            let mut args = FluentArgs::new();
+            args.set("step", n + 1);
+            args.set("total", total);
+            (i18n.t(step.message), i18n.t_args("tutorial-step", &args))
+        })
+        .collect();
+    let show_step = {
+        let step = step.clone();
+        let area = area.clone();
+        let prev_btn = prev_btn.clone();
+        let next_btn = next_btn.clone();
+        move |n: usize| {
+            *step.borrow_mut() = n;
+            text.set_text(&texts[n].0);
+            step_label.set_text(&texts[n].1);
+            prev_btn.set_sensitive(n > 0);
+            next_btn.set_sensitive(n + 1 < total);
+            area.queue_draw();
+        }
+    };
+    show_step(0);
+    let show_step = Rc::new(show_step);
+    {
+        let step = step.clone();
+        let show_step = show_step.clone();
+        prev_btn.connect_clicked(move |_| {
+            let n = *step.borrow();
+            show_step(n.saturating_sub(1));
+        });
+    }
+    {
+        let show_step = show_step.clone();
+        next_btn.connect_clicked(move |_| {
+            let n = *step.borrow();
+            show_step((n + 1).min(total - 1));
+        });
+    }
+
+    dialog.connect_response(|dialog, _| {
+        dialog.close();
+    });
+    dialog.show();
+}
+
+/// Ask for a file name and export the statistics as CSV. Cancelling writes nothing.
+pub fn export_statistics(parent: &ApplicationWindow, statistics: Statistics, i18n: &I18n) {
+    let chooser = FileChooserNative::new(
+        Some(&i18n.t("export-stats-title")),
+        Some(parent),
+        FileChooserAction::Save,
+        Some(&i18n.t("export-stats-save")),
+        Some(&i18n.t("cancel")),
+    );
+    chooser.set_modal(true);
+    chooser.set_current_name("toweroops-statistics.csv");
+
+    let parent = parent.clone();
+    let error_title = i18n.t("export-stats-failed");
+    let ok_label = i18n.t("ok");
+    // The closure keeps the chooser alive until it has answered.
+    let chooser_ref = chooser.clone();
+    chooser.connect_response(move |_, response| {
+        if response != ResponseType::Accept {
+            return;
+        }
+        let Some(path) = chooser_ref.file().and_then(|f| f.path()) else {
+            return;
+        };
+        let csv = crate::storage::statistics_to_csv(&statistics);
+        if let Err(e) = std::fs::write(&path, csv) {
+            eprintln!("Failed to export statistics to {}: {}", path.display(), e);
+            show_error(&parent, &error_title, &e.to_string(), &ok_label);
+        }
+    });
+    chooser.show();
+}
+
+/// Ask where to save `game` as an animated GIF and at which frame rate, then
+/// write it: the frames are rendered here, the encoding runs on a worker
+/// thread (see [`replay_gif::write_replay_gif`]) and reports errors back.
+pub fn export_replay(
+    parent: &ApplicationWindow,
+    game: GameState,
+    resources: Rc<GameResources>,
+    view: ViewOptions,
+    i18n: &I18n,
+) {
+    let chooser = FileChooserNative::new(
+        Some(&i18n.t("export-replay-title")),
+        Some(parent),
+        FileChooserAction::Save,
+        Some(&i18n.t("export-stats-save")),
+        Some(&i18n.t("cancel")),
+    );
+    chooser.set_modal(true);
+    chooser.set_current_name("toweroops-replay.gif");
+    let fps_labels: Vec<(String, String)> = replay_gif::REPLAY_FPS_CHOICES
+        .iter()
+        .map(|fps| {
+            let mut args = FluentArgs::new();
+            args.set("fps", *fps);
+            (
+                fps.to_string(),
+                i18n.t_args("export-replay-fps-option", &args),
+            )
+        })
+        .collect();
+    let options: Vec<(&str, &str)> = fps_labels
+        .iter()
+        .map(|(id, label)| (id.as_str(), label.as_str()))
+        .collect();
+    chooser.add_choice("fps", i18n.t("export-replay-fps"), &options);
+    chooser.set_choice("fps", &replay_gif::DEFAULT_REPLAY_FPS.to_string());
+
+    let parent = parent.clone();
+    let error_title = i18n.t("export-replay-failed");
+    let ok_label = i18n.t("ok");
+    // The closure keeps the chooser alive until it has answered.
+    let chooser_ref = chooser.clone();
+    chooser.connect_response(move |_, response| {
+        if response != ResponseType::Accept {
+            return;
+        }
+        let Some(path) = chooser_ref.file().and_then(|f| f.path()) else {
+            return;
+        };
+        let fps = chooser_ref
+            .choice("fps")
+            .and_then(|id| id.parse().ok())
+            .unwrap_or(replay_gif::DEFAULT_REPLAY_FPS);
+        let frames = match replay_gif::render_replay(&game, &resources, &view) {
+            Ok(frames) => frames,
+            Err(e) => {
+                eprintln!("Failed to export the replay to {}: {}", path.display(), e);
+                show_error(&parent, &error_title, &e.to_string(), &ok_label);
+                return;
+            }
+        };
+        let (sender, receiver) = gtk4::glib::MainContext::channel(gtk4::glib::PRIORITY_DEFAULT);
+        let (parent, error_title, ok_label) =
+            (parent.clone(), error_title.clone(), ok_label.clone());
+        receiver.attach(None, move |result: std::io::Result<()>| {
+            if let Err(e) = result {
+                show_error(&parent, &error_title, &e.to_string(), &ok_label);
+            }
+            gtk4::glib::Continue(false)
+        });
+        std::thread::spawn(move || {
+            let result = replay_gif::write_replay_gif(frames, fps, &path);
+            if let Err(e) = &result {
+                eprintln!("Failed to export the replay to {}: {}", path.display(), e);
+            }
+            let _ = sender.send(result);
+        });
+    });
+    chooser.show();
+}
+
+/// Tell that there is no finished round to race yet.
+pub fn show_no_last_run(parent: &ApplicationWindow, i18n: &I18n) {
+    show_error(
+        parent,
+        &i18n.t("ghost-race-title"),
+        &i18n.t("ghost-race-unavailable"),
+        &i18n.t("ok"),
+    );
+}
+
+/// Tell that the clipboard holds no usable position code.
+pub fn show_invalid_position_code(parent: &ApplicationWindow, i18n: &I18n) {
+    show_error(
+        parent,
+        &i18n.t("position-code-invalid-title"),
+        &i18n.t("position-code-invalid"),
+        &i18n.t("ok"),
+    );
+}
+
+/// Plain message box for errors (no markup).
+fn show_error(parent: &ApplicationWindow, title: &str, message: &str, ok_label: &str) {
+    let dialog = Dialog::new();
+    dialog.set_transient_for(Some(parent));
+    dialog.set_modal(true);
+    dialog.set_destroy_with_parent(true);
+    dialog.set_title(Some(title));
+    let ok_btn = dialog.add_button(ok_label, ResponseType::Accept);
+    ok_btn.set_margin_start(8);
+    ok_btn.set_margin_end(8);
+    ok_btn.set_margin_top(6);
+    ok_btn.set_margin_bottom(6);
+
+    let content = dialog.content_area();
+    content.set_margin_start(16);
+    content.set_margin_end(16);
+    content.set_margin_top(12);
+    content.set_margin_bottom(12);
+    let label = Label::new(Some(message));
+    label.set_wrap(true);
+    content.append(&label);
+
+    dialog.connect_response(|dialog, _| dialog.close());
+    dialog.show();
+}
+
 /// Show a "quit while game running?" confirmation. Returns a Dialog the caller
 /// can wait on, or use the callback approach.
 pub fn confirm_close(parent: &ApplicationWindow, i18n: &I18n) -> Dialog {
@@ -216,3 +1543,48 @@ pub fn confirm_close(parent: &ApplicationWindow, i18n: &I18n) -> Dialog {
 
     dialog
 }
+
+/// Warn that the configuration directory is not writable. "Retry" checks again
+/// and closes the dialog once the problem is gone; the game stays playable either way.
+pub fn warn_config_unwritable(parent: &ApplicationWindow, i18n: &I18n, error: &str) {
+    let dialog = Dialog::new();
+    dialog.set_transient_for(Some(parent));
+    dialog.set_modal(true);
+    dialog.set_destroy_with_parent(true);
+    dialog.set_title(Some(&i18n.t("config-warning-title")));
+
+    let retry_btn = dialog.add_button(&i18n.t("retry"), ResponseType::Other(1));
+    let ok_btn = dialog.add_button(&i18n.t("ok"), ResponseType::Accept);
+    for btn in [&retry_btn, &ok_btn] {
+        btn.set_margin_start(8);
+        btn.set_margin_end(8);
+        btn.set_margin_top(6);
+        btn.set_margin_bottom(6);
+    }
+
+    let content = dialog.content_area();
+    content.set_margin_start(16);
+    content.set_margin_end(16);
+    content.set_margin_top(12);
+    content.set_margin_bottom(12);
+    let mut args = FluentArgs::new();
+    args.set("error", error.to_string());
+    let base_message = i18n.t_args("config-warning-message", &args);
+    let label = Label::new(Some(&base_message));
+    label.set_wrap(true);
+    content.append(&label);
+
+    let retry_failed = i18n.t("config-warning-retry-failed");
+    dialog.connect_response(move |dialog, response| {
+        if response == ResponseType::Other(1) {
+            match crate::storage::check_config_dir() {
+                Ok(_) => dialog.close(),
+                Err(e) => label.set_text(&format!("{}\n\n{}: {}", base_message, retry_failed, e)),
+            }
+            return;
+        }
+        dialog.close();
+    });
+
+    dialog.show();
+}
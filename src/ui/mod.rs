@@ -2,4 +2,5 @@ pub mod app;
 pub mod board;
 pub mod dialogs;
 pub mod rendering;
+pub mod replay_gif;
 pub mod resources;
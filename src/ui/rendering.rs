@@ -6,9 +6,11 @@ use gdk_pixbuf::Pixbuf;
 use gtk4::prelude::*;
 
 use super::resources::{GameImage, GameResources};
-use crate::game::field::BOARD_SIZE;
+use crate::ai::{self, AxisValues, SearchProgress};
+use crate::game::field::DEFAULT_BOARD_SIZE;
 use crate::game::logic::GameState;
-use crate::game::types::{CellKind, GameOutcome, Selection};
+use crate::game::types::{Cell, CellKind, ColorScheme, GameOutcome, PlayerSide, Selection};
+use crate::storage::Settings;
 
 // Design-time (reference) dimensions – matches the original background bitmap size.
 // All layout constants are relative to the (0,0) origin of this coordinate space.
@@ -18,63 +20,344 @@ pub const REF_HEIGHT: f64 = 393.0;
 // Layout constants in reference coordinates
 const FIELD_OFFSET_X: f64 = 136.0;
 const FIELD_OFFSET_Y: f64 = 38.0;
-const CELL_SIZE: f64 = 41.0;
+/// Edge length of the square playing field; cells are `FIELD_SIZE / board size` wide.
+const FIELD_SIZE: f64 = 328.0;
 
 const TOWER_LEFT_X: f64 = 31.0;
 const TOWER_RIGHT_X: f64 = 501.0;
 const TOWER_BASE_Y: f64 = 380.0;
-const TOWER_ROW_HEIGHT: f64 = 16.0;
+/// Height of a finished tower; its rows are `TOWER_HEIGHT / target height` tall.
+const TOWER_HEIGHT: f64 = 320.0;
 const TOWER_ROW_WIDTH_NORMAL: f64 = 68.0;
 const TOWER_ROW_WIDTH_TOP: f64 = 84.0;
 
 // Flags are SVG 31×31; place them centered above the tower top row.
 const FLAG_SIZE: f64 = 31.0;
-const FLAG_Y: f64 = TOWER_BASE_Y - TOWER_ROW_HEIGHT * 20.0 - FLAG_SIZE; // 29.0
-                                                                        // Position so the flagpole (x=2 in the SVG) sits at the tower's horizontal center.
+const FLAG_Y: f64 = TOWER_BASE_Y - TOWER_HEIGHT - FLAG_SIZE; // 29.0
+                                                             // Position so the flagpole (x=2 in the SVG) sits at the tower's horizontal center.
 const FLAG_LEFT_X: f64 = 63.0; // center ~65 for left tower top width 84
 const FLAG_RIGHT_X: f64 = 533.0; // center ~535 for right tower top width 84
 
+/// A tower hit by a bomb: drawn shifted sideways and tinted red.
+#[derive(Debug, Clone, Copy)]
+pub struct TowerHit {
+    /// The player's tower (otherwise the computer's).
+    pub on_player: bool,
+    /// Horizontal offset in reference pixels.
+    pub offset_x: f64,
+    /// Opacity of the red tint, 0.0..1.0.
+    pub flash: f64,
+}
+
+/// Height a tower would have after the hovered move, drawn as a translucent
+/// ghost of the rows it would gain or lose.
+#[derive(Debug, Clone, Copy)]
+pub struct TowerPreview {
+    /// The player's tower (otherwise the computer's).
+    pub on_player: bool,
+    pub height: i32,
+}
+
+/// Opacity of the rows a [`TowerPreview`] would add or remove.
+const GHOST_ALPHA: f64 = 0.35;
+/// Opacity of the "what-if" axis scrolled to over the board.
+const PREVIEW_SELECTION_ALPHA: f64 = 0.3;
+
+/// Short cosmetic effects layered over the regular drawing.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Effects<'a> {
+    pub tower_hit: Option<TowerHit>,
+    pub tower_preview: Option<TowerPreview>,
+    /// The previous selection axis while it fades out, and the fade progress 0.0..1.0.
+    pub selection_fade: Option<(Selection, f64)>,
+    /// Share of the blitz time left for the move (1.0..0.0) while the clock runs.
+    pub move_clock: Option<f64>,
+    /// How far the computer's search has got while it is thinking.
+    pub search_progress: Option<SearchProgress>,
+    /// `--show-fps`: smoothed frame time in seconds, shown with the raster quality.
+    pub frame_time: Option<f64>,
+    /// Cells marked by the threat overlay (`opts.threats`), computed once per
+    /// position by the caller.
+    pub threats: &'a [(usize, usize)],
+}
+
+/// User-selectable display options. They only affect drawing, never game logic.
+#[derive(Debug, Clone, Default)]
+pub struct ViewOptions {
+    /// Draw one base texture per piece type with 1–4 value pips on top.
+    pub value_pips: bool,
+    /// Write the signed value (+1..+4, -1..-4) on stones and bombs.
+    pub value_labels: bool,
+    /// Tint cells that would let the opponent win with the next move.
+    pub threats: bool,
+    /// Tint the field edges by the value of each row and column.
+    pub axis_heatmap: bool,
+    /// Palette for the player/computer highlights and themed assets.
+    pub color_scheme: ColorScheme,
+    /// Letter the columns and number the rows along the field edges.
+    pub coordinates: bool,
+    /// Let the score outside the board follow the growing towers.
+    pub fractional_heights: bool,
+    /// Which tower, flag and highlight colour are the player's.
+    pub player_side: PlayerSide,
+    /// Brightness and contrast applied to the whole scene.
+    pub tone: BoardTone,
+}
+
+/// Range of the brightness shift offered in the settings (share of full white).
+pub const MIN_BOARD_BRIGHTNESS: f64 = -0.3;
+pub const MAX_BOARD_BRIGHTNESS: f64 = 0.3;
+/// Range of the contrast factor offered in the settings.
+pub const MIN_BOARD_CONTRAST: f64 = 0.5;
+pub const MAX_BOARD_CONTRAST: f64 = 2.0;
+
+/// A transfer curve for low-vision users: every colour channel is spread
+/// around mid grey by `contrast`, then shifted by `brightness`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoardTone {
+    pub brightness: f64,
+    pub contrast: f64,
+}
+
+impl Default for BoardTone {
+    fn default() -> Self {
+        Self {
+            brightness: 0.0,
+            contrast: 1.0,
+        }
+    }
+}
+
+impl BoardTone {
+    /// The curve leaves every colour as it is.
+    pub fn is_neutral(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// The curve as a lookup table over straight (not premultiplied) channel values.
+    fn lookup_table(&self) -> [u8; 256] {
+        let mut table = [0u8; 256];
+        for (value, out) in table.iter_mut().enumerate() {
+            let v = value as f64 / 255.0;
+            let adjusted = (v - 0.5) * self.contrast + 0.5 + self.brightness;
+            *out = (adjusted.clamp(0.0, 1.0) * 255.0).round() as u8;
+        }
+        table
+    }
+}
+
+impl ViewOptions {
+    pub fn from_settings(settings: &Settings) -> Self {
+        Self {
+            value_pips: settings.value_pips,
+            value_labels: settings.show_values,
+            threats: settings.show_threats,
+            axis_heatmap: settings.show_axis_heatmap,
+            color_scheme: settings.color_scheme,
+            coordinates: settings.show_coordinates,
+            fractional_heights: settings.show_fractional_heights,
+            player_side: settings.player_side,
+            tone: BoardTone {
+                brightness: settings
+                    .board_brightness
+                    .clamp(MIN_BOARD_BRIGHTNESS, MAX_BOARD_BRIGHTNESS),
+                contrast: settings
+                    .board_contrast
+                    .clamp(MIN_BOARD_CONTRAST, MAX_BOARD_CONTRAST),
+            },
+        }
+    }
+}
+
 // ── SVG rasterization cache ──────────────────────────────────────────────────
-// Key: (pointer to usvg::Tree as usize, render_w_px, render_h_px)
+// Key: (pointer to usvg::Tree as usize, render_w_px, render_h_px, device scale)
 // Value: pre-rasterized Pixbuf
 // This avoids re-rasterizing SVGs every single frame and dramatically
 // improves performance.  The cache is thread-local because GTK rendering
-// happens on the main thread.
+// happens on the main thread.  The device scale is part of the key so
+// monitors with different scale factors never share entries.
 thread_local! {
-    static SVG_CACHE: RefCell<HashMap<(usize, u32, u32), Pixbuf>> = RefCell::new(HashMap::new());
+    static SVG_CACHE: RefCell<LruCache<SvgCacheKey, Pixbuf>> =
+        RefCell::new(LruCache::new(SVG_CACHE_LIMIT));
 }
 
-/// Render the entire game scene, scaled to fit (widget_w, widget_h).
-/// `pulse_cell` = optional (col, row, progress 0..1) for the pulsing cell highlight.
-/// `is_cpu_pulse` = true if the pulse is for the CPU move (red), false for player (blue).
-pub fn render(
+/// (tree pointer, pixel width, pixel height, device scale × 100)
+type SvgCacheKey = (usize, u32, u32, u32);
+
+/// Upper bound on cached rasterizations; resizing through many sizes would
+/// otherwise keep every intermediate size alive.
+const SVG_CACHE_LIMIT: usize = 64;
+
+/// A small map that evicts the least recently used entry once `capacity` is
+/// reached. Eviction scans all entries, which is cheap at this size.
+struct LruCache<K, V> {
+    entries: HashMap<K, (V, u64)>,
+    capacity: usize,
+    clock: u64,
+}
+
+impl<K: std::hash::Hash + Eq + Copy, V> LruCache<K, V> {
+    fn new(capacity: usize) -> Self {
+        Self {
+            entries: HashMap::new(),
+            capacity: capacity.max(1),
+            clock: 0,
+        }
+    }
+
+    /// Look up `key` and mark it as most recently used.
+    fn get(&mut self, key: &K) -> Option<&V> {
+        self.clock += 1;
+        let clock = self.clock;
+        self.entries.get_mut(key).map(|(value, used)| {
+            *used = clock;
+            &*value
+        })
+    }
+
+    fn insert(&mut self, key: K, value: V) {
+        self.clock += 1;
+        if !self.entries.contains_key(&key) && self.entries.len() >= self.capacity {
+            let oldest = self
+                .entries
+                .iter()
+                .min_by_key(|(_, (_, used))| *used)
+                .map(|(k, _)| *k);
+            if let Some(oldest) = oldest {
+                self.entries.remove(&oldest);
+            }
+        }
+        self.entries.insert(key, (value, self.clock));
+    }
+
+    /// Drop every entry whose key fails `keep`.
+    fn retain(&mut self, mut keep: impl FnMut(&K) -> bool) {
+        self.entries.retain(|key, _| keep(key));
+    }
+
+    #[cfg(test)]
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+/// Cache key component for a device scale factor.
+fn device_scale_key(device_scale: f64) -> u32 {
+    (device_scale * 100.0).round() as u32
+}
+
+/// Forget rasterizations made for any other device scale, e.g. after the
+/// window moved to a monitor with a different scale factor. They would never
+/// be drawn again but keep the cache full.
+pub fn retain_device_scale(device_scale: f64) {
+    let scale_key = device_scale_key(device_scale);
+    SVG_CACHE.with(|cache| {
+        cache
+            .borrow_mut()
+            .retain(|&(_, _, _, scale)| scale == scale_key)
+    });
+}
+
+// ── Backdrop cache ───────────────────────────────────────────────────────────
+// Background and grid only change with the widget size, so they are
+// composited once into a surface of the widget's pixel size and that single
+// surface is painted every frame.
+thread_local! {
+    static BACKDROP_CACHE: RefCell<Option<(BackdropKey, cairo::ImageSurface)>> =
+        const { RefCell::new(None) };
+}
+
+/// (widget width, widget height, device scale × 100, raster quality × 100,
+/// board size, resource generation)
+type BackdropKey = (i32, i32, u32, u32, usize, u64);
+
+fn backdrop_key(
+    generation: u64,
+    size: usize,
+    (widget_w, widget_h): (i32, i32),
+    device_scale: f64,
+    raster_quality: f64,
+) -> BackdropKey {
+    (
+        widget_w,
+        widget_h,
+        device_scale_key(device_scale),
+        (raster_quality * 100.0).round() as u32,
+        size,
+        generation,
+    )
+}
+
+/// Paint background and grid for a board of `size`, from the cache if the
+/// widget size, scale and resources are unchanged. Without `cached` they are
+/// drawn straight onto `cr`, which must be untransformed.
+fn draw_backdrop(
     cr: &Context,
-    state: &GameState,
     res: &GameResources,
-    widget_w: i32,
-    widget_h: i32,
-    anim_player_tower: f64,
-    anim_computer_tower: f64,
-    pulse_cell: Option<(usize, usize, f64)>,
-    is_cpu_pulse: bool,
+    size: usize,
+    (widget_w, widget_h): (i32, i32),
+    device_scale: f64,
     raster_quality: f64,
+    cached: bool,
 ) {
-    let w = widget_w as f64;
-    let h = widget_h as f64;
-    let scale_x = w / REF_WIDTH;
-    let scale_y = h / REF_HEIGHT;
-    let scale = scale_x.min(scale_y);
-
-    // Centre the scaled content
-    let offset_x = (w - REF_WIDTH * scale) / 2.0;
-    let offset_y = (h - REF_HEIGHT * scale) / 2.0;
-
-    let _ = cr.save();
-    cr.translate(offset_x, offset_y);
-    cr.scale(scale, scale);
+    let key = backdrop_key(
+        res.generation(),
+        size,
+        (widget_w, widget_h),
+        device_scale,
+        raster_quality,
+    );
+    let (scale, offset_x, offset_y) = layout_transform(widget_w, widget_h);
+    let device_scale = device_scale.max(1.0);
+    let surface = cached
+        .then(|| {
+            BACKDROP_CACHE.with(|cache| {
+                let mut cache = cache.borrow_mut();
+                if let Some((cached_key, surface)) = cache.as_ref() {
+                    if *cached_key == key {
+                        return Some(surface.clone());
+                    }
+                }
+                let surface = cairo::ImageSurface::create(
+                    cairo::Format::ARgb32,
+                    (widget_w as f64 * device_scale).ceil().max(1.0) as i32,
+                    (widget_h as f64 * device_scale).ceil().max(1.0) as i32,
+                )
+                .ok()?;
+                surface.set_device_scale(device_scale, device_scale);
+                {
+                    let backdrop = Context::new(&surface).ok()?;
+                    backdrop.translate(offset_x, offset_y);
+                    backdrop.scale(scale, scale);
+                    paint_backdrop(&backdrop, res, size, scale * device_scale, raster_quality);
+                }
+                surface.flush();
+                *cache = Some((key, surface.clone()));
+                Some(surface)
+            })
+        })
+        .flatten();
+    match surface {
+        Some(surface) => {
+            let _ = cr.save();
+            let _ = cr.set_source_surface(&surface, 0.0, 0.0);
+            let _ = cr.paint();
+            let _ = cr.restore();
+        }
+        // Uncached, or no surface to spare: draw straight onto the target
+        None => {
+            let _ = cr.save();
+            cr.translate(offset_x, offset_y);
+            cr.scale(scale, scale);
+            paint_backdrop(cr, res, size, scale * device_scale, raster_quality);
+            let _ = cr.restore();
+        }
+    }
+}
 
-    // Draw background inside the same CTM so it uses the identical
-    // translation/scale as the board and UI elements.
+/// Background and grid in reference coordinates. `scale` is in device pixels
+/// per reference unit, as in [`render`].
+fn paint_backdrop(cr: &Context, res: &GameResources, size: usize, scale: f64, raster_quality: f64) {
     if let Some(bg) = res.get("background") {
         draw_image_scaled(
             cr,
@@ -86,9 +369,24 @@ pub fn render(
             scale,
             raster_quality,
         );
+    } else {
+        draw_placeholder(cr, 0.0, 0.0, REF_WIDTH, REF_HEIGHT, "background");
     }
-    // Grid overlay (same size as background)
+    // Grid overlay (same size as background). Its field lines are drawn for the
+    // default board size, so other sizes mask them out and draw their own.
     if let Some(grid) = res.get("grid") {
+        let _ = cr.save();
+        if size != DEFAULT_BOARD_SIZE {
+            cr.set_fill_rule(cairo::FillRule::EvenOdd);
+            cr.rectangle(0.0, 0.0, REF_WIDTH, REF_HEIGHT);
+            cr.rectangle(
+                FIELD_OFFSET_X - 2.0,
+                FIELD_OFFSET_Y - 2.0,
+                FIELD_SIZE + 4.0,
+                FIELD_SIZE + 4.0,
+            );
+            cr.clip();
+        }
         draw_image_scaled(
             cr,
             grid,
@@ -99,133 +397,468 @@ pub fn render(
             scale,
             raster_quality,
         );
+        let _ = cr.restore();
+        if size != DEFAULT_BOARD_SIZE {
+            draw_field_grid(cr, size);
+        }
     }
+}
 
-    // Draw the 8x8 board
-    for col in 0..BOARD_SIZE {
-        for row in 0..BOARD_SIZE {
-            let cell = state.board.get(col, row);
-            let x = FIELD_OFFSET_X + col as f64 * CELL_SIZE;
-            let y = FIELD_OFFSET_Y + row as f64 * CELL_SIZE;
-
-            let img = match cell.kind {
-                CellKind::Bomb => res.bomb(cell.value),
-                CellKind::Stone => res.stone(cell.value),
-                CellKind::Banana => res.get("banana"),
-                CellKind::Empty => None,
+/// Render the entire game scene, scaled to fit (widget_w, widget_h).
+/// `pulse_cell` = optional (col, row, progress 0..1) for the pulsing cell highlight.
+/// `is_cpu_pulse` = true if the pulse is for the CPU move, false for the player's
+/// (colours depend on `opts.color_scheme` and follow `opts.player_side`).
+/// `device_scale` = the widget's scale factor (2 on typical HiDPI screens).
+/// `cpu_paused` = show the pause marker (the computer holds its turn).
+/// A non-neutral `opts.tone` draws the scene offscreen first and passes it
+/// through the transfer curve; the layout, and so hit-testing, stays the same.
+pub fn render(
+    cr: &Context,
+    state: &GameState,
+    res: &GameResources,
+    widget_w: i32,
+    widget_h: i32,
+    anim_player_tower: f64,
+    anim_computer_tower: f64,
+    pulse_cell: Option<(usize, usize, f64)>,
+    is_cpu_pulse: bool,
+    raster_quality: f64,
+    device_scale: f64,
+    cpu_paused: bool,
+    effects: Effects,
+    opts: &ViewOptions,
+) {
+    render_scene(
+        cr,
+        state,
+        res,
+        widget_w,
+        widget_h,
+        anim_player_tower,
+        anim_computer_tower,
+        pulse_cell,
+        is_cpu_pulse,
+        raster_quality,
+        device_scale,
+        cpu_paused,
+        effects,
+        opts,
+        true,
+    );
+}
+
+/// [`render`], with the backdrop and tone caches only used when `cached`.
+/// Offscreen renders of other sizes pass `false` so they do not evict the
+/// widget's entries.
+#[allow(clippy::too_many_arguments)]
+fn render_scene(
+    cr: &Context,
+    state: &GameState,
+    res: &GameResources,
+    widget_w: i32,
+    widget_h: i32,
+    anim_player_tower: f64,
+    anim_computer_tower: f64,
+    pulse_cell: Option<(usize, usize, f64)>,
+    is_cpu_pulse: bool,
+    raster_quality: f64,
+    device_scale: f64,
+    cpu_paused: bool,
+    effects: Effects,
+    opts: &ViewOptions,
+    cached: bool,
+) {
+    let offscreen = (!opts.tone.is_neutral())
+        .then(|| take_tone_surface(widget_w, widget_h, device_scale, cached))
+        .flatten();
+    let Some(mut surface) = offscreen else {
+        draw_scene(
+            cr,
+            state,
+            res,
+            widget_w,
+            widget_h,
+            anim_player_tower,
+            anim_computer_tower,
+            pulse_cell,
+            is_cpu_pulse,
+            raster_quality,
+            device_scale,
+            cpu_paused,
+            effects,
+            opts,
+            cached,
+        );
+        return;
+    };
+    if let Ok(offscreen_cr) = Context::new(&surface) {
+        // Last frame's pixels are still there
+        offscreen_cr.set_operator(cairo::Operator::Clear);
+        let _ = offscreen_cr.paint();
+        offscreen_cr.set_operator(cairo::Operator::Over);
+        draw_scene(
+            &offscreen_cr,
+            state,
+            res,
+            widget_w,
+            widget_h,
+            anim_player_tower,
+            anim_computer_tower,
+            pulse_cell,
+            is_cpu_pulse,
+            raster_quality,
+            device_scale,
+            cpu_paused,
+            effects,
+            opts,
+            cached,
+        );
+    }
+    surface.flush();
+    let stride = surface.stride() as usize;
+    let table = tone_table(opts.tone);
+    if let Ok(mut data) = surface.data() {
+        apply_tone(&mut data, stride, &table);
+    }
+    // Restoring drops `cr`'s reference, so the next frame can write the
+    // surface's pixels again.
+    let _ = cr.save();
+    if cr.set_source_surface(&surface, 0.0, 0.0).is_ok() {
+        let _ = cr.paint();
+    }
+    let _ = cr.restore();
+    if cached {
+        TONE_CACHE.with(|cache| cache.borrow_mut().surface = Some(surface));
+    }
+}
+
+// ── Tone cache ───────────────────────────────────────────────────────────────
+// A toned scene is drawn into an offscreen surface of the widget's pixel
+// size. The surface and the curve's lookup table are kept between frames;
+// the surface is only rebuilt when the size or device scale changes.
+thread_local! {
+    static TONE_CACHE: RefCell<ToneCache> = RefCell::new(ToneCache::default());
+}
+
+#[derive(Default)]
+struct ToneCache {
+    surface: Option<cairo::ImageSurface>,
+    table: Option<(BoardTone, [u8; 256])>,
+}
+
+/// The offscreen surface for a toned frame, taken out of the cache so nothing
+/// else holds a reference while its pixels are rewritten. The caller puts it
+/// back. A fresh surface unless `cached`; `None` if cairo cannot provide one.
+fn take_tone_surface(
+    widget_w: i32,
+    widget_h: i32,
+    device_scale: f64,
+    cached: bool,
+) -> Option<cairo::ImageSurface> {
+    let scale = device_scale.max(1.0);
+    let (w, h) = (
+        (widget_w as f64 * scale).ceil() as i32,
+        (widget_h as f64 * scale).ceil() as i32,
+    );
+    let reusable = cached
+        .then(|| TONE_CACHE.with(|cache| cache.borrow_mut().surface.take()))
+        .flatten();
+    if let Some(surface) = reusable {
+        if surface.width() == w && surface.height() == h && surface.device_scale() == (scale, scale)
+        {
+            return Some(surface);
+        }
+    }
+    let surface = cairo::ImageSurface::create(cairo::Format::ARgb32, w, h).ok()?;
+    surface.set_device_scale(scale, scale);
+    Some(surface)
+}
+
+/// The lookup table of `tone`, computed again only when the tone changes.
+fn tone_table(tone: BoardTone) -> [u8; 256] {
+    TONE_CACHE.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        match cache.table {
+            Some((cached, table)) if cached == tone => table,
+            _ => {
+                let table = tone.lookup_table();
+                cache.table = Some((tone, table));
+                table
+            }
+        }
+    })
+}
+
+/// Pass every pixel of a cairo ARGB32 image (native-endian, premultiplied
+/// words in rows of `stride` bytes) through the channel lookup `table`.
+fn apply_tone(data: &mut [u8], stride: usize, table: &[u8; 256]) {
+    for row in data.chunks_mut(stride) {
+        for pixel in row.chunks_exact_mut(4) {
+            let argb = u32::from_ne_bytes([pixel[0], pixel[1], pixel[2], pixel[3]]);
+            let a = argb >> 24;
+            if a == 0 {
+                continue;
+            }
+            let channel = |shift: u32| {
+                let premultiplied = (argb >> shift) & 0xff;
+                let straight = ((premultiplied * 255 + a / 2) / a).min(255);
+                (table[straight as usize] as u32 * a + 127) / 255
             };
+            let toned = (a << 24) | (channel(16) << 16) | (channel(8) << 8) | channel(0);
+            pixel.copy_from_slice(&toned.to_ne_bytes());
+        }
+    }
+}
+
+/// Draw the game scene onto `cr`, see [`render_scene`].
+#[allow(clippy::too_many_arguments)]
+fn draw_scene(
+    cr: &Context,
+    state: &GameState,
+    res: &GameResources,
+    widget_w: i32,
+    widget_h: i32,
+    anim_player_tower: f64,
+    anim_computer_tower: f64,
+    pulse_cell: Option<(usize, usize, f64)>,
+    is_cpu_pulse: bool,
+    raster_quality: f64,
+    device_scale: f64,
+    cpu_paused: bool,
+    effects: Effects,
+    opts: &ViewOptions,
+    cached: bool,
+) {
+    let (scale, offset_x, offset_y) = layout_transform(widget_w, widget_h);
+    let size = state.board.size();
 
+    // Background and grid come composited from the cache, under everything else.
+    draw_backdrop(
+        cr,
+        res,
+        size,
+        (widget_w, widget_h),
+        device_scale,
+        raster_quality,
+        cached,
+    );
+
+    let _ = cr.save();
+    cr.translate(offset_x, offset_y);
+    cr.scale(scale, scale);
+    // From here on `scale` only sizes rasterized images: device pixels per
+    // reference unit, so HiDPI displays get full-resolution SVGs.
+    let scale = scale * device_scale.max(1.0);
+
+    // Colours belong to the sides: on the right the player takes the
+    // computer's highlight, tower and flag.
+    let player_right = opts.player_side == PlayerSide::Right;
+    let right_pulse = is_cpu_pulse != player_right;
+
+    // Draw the board
+    for col in 0..size {
+        for row in 0..size {
+            let cell = state.board.get(col, row);
+            let (x, y, cw, ch) = cell_rect(size, col, row);
+
+            let (img, pips) = piece_image(res, cell, opts.value_pips);
             if let Some(img) = img {
-                draw_image_scaled(cr, img, x, y, CELL_SIZE, CELL_SIZE, scale, raster_quality);
+                draw_image_scaled(cr, img, x, y, cw, ch, scale, raster_quality);
+            } else if let Some(name) = piece_name(cell) {
+                draw_placeholder(cr, x + 1.0, y + 1.0, cw - 2.0, ch - 2.0, &name);
+            }
+            if pips {
+                draw_value_pips(cr, x, y, cw, ch, cell);
+            }
+            if opts.value_labels {
+                draw_value_label(cr, x, y, cw, ch, cell);
             }
         }
     }
 
+    // Axis heatmap (recomputed from the board on every frame), under the coordinates
+    if opts.axis_heatmap {
+        draw_axis_heatmap(cr, size, &ai::axis_values(&state.board));
+    }
+
+    if opts.coordinates {
+        draw_coordinates(cr, size);
+    }
+
     // Selection highlight (always visible)
-    match state.selection {
-        Selection::Column(c) => {
-            if let Some(img) = res.get("vertical") {
-                draw_image(
-                    cr,
-                    img,
-                    FIELD_OFFSET_X - 1.0 + c as f64 * CELL_SIZE,
-                    FIELD_OFFSET_Y - 1.0,
-                    scale,
-                    raster_quality,
-                );
-            }
-        }
-        Selection::Row(r) => {
-            if let Some(img) = res.get("horizontal") {
-                draw_image(
-                    cr,
-                    img,
-                    FIELD_OFFSET_X - 1.0,
-                    FIELD_OFFSET_Y - 1.0 + r as f64 * CELL_SIZE,
-                    scale,
-                    raster_quality,
-                );
-            }
+    // No axis is active until the player has chosen the opening one.
+    // After a move the old axis fades out while the new one fades in.
+    let selection =
+        (!state.awaiting_initial_selection && !state.choosing_axis).then_some(state.selection);
+    let fade_progress = match effects.selection_fade {
+        Some((previous, progress)) => {
+            draw_selection(
+                cr,
+                res,
+                size,
+                previous,
+                1.0 - progress,
+                scale,
+                raster_quality,
+            );
+            progress
         }
+        None => 1.0,
+    };
+    if let Some(selection) = selection {
+        draw_selection(
+            cr,
+            res,
+            size,
+            selection,
+            fade_progress,
+            scale,
+            raster_quality,
+        );
+    }
+
+    // Faint "what-if" axis; it never becomes the selection by itself
+    if let Some(preview) = state.preview_selection.filter(|&p| Some(p) != selection) {
+        draw_selection(
+            cr,
+            res,
+            size,
+            preview,
+            PREVIEW_SELECTION_ALPHA,
+            scale,
+            raster_quality,
+        );
+    }
+
+    // Blitz clock along the active axis, in the colour of the side to move:
+    // player one sits on `opts.player_side`, player two opposite.
+    if let (Some(selection), Some(left)) = (selection, effects.move_clock) {
+        let player_two_to_move = !state.player_one_to_move();
+        let right_to_move = player_two_to_move != player_right;
+        draw_move_clock(cr, size, selection, left, right_to_move, opts.color_scheme);
     }
 
     // Pulsing highlight on the selected cell
     if let Some((pc, pr, progress)) = pulse_cell {
-        let px = FIELD_OFFSET_X + pc as f64 * CELL_SIZE;
-        let py = FIELD_OFFSET_Y + pr as f64 * CELL_SIZE;
-        draw_pulse_highlight(cr, px, py, CELL_SIZE, CELL_SIZE, progress, is_cpu_pulse);
+        let (px, py, pw, ph) = cell_rect(size, pc, pr);
+        draw_pulse_highlight(cr, px, py, pw, ph, progress, right_pulse, opts.color_scheme);
     }
 
     // Hover highlight
     if let Some((hx, hy)) = state.hovered {
         if state.outcome == GameOutcome::Running {
             if let Some(img) = res.get("shadow") {
-                draw_image(
-                    cr,
-                    img,
-                    FIELD_OFFSET_X - 1.0 + hx as f64 * CELL_SIZE,
-                    FIELD_OFFSET_Y - 1.0 + hy as f64 * CELL_SIZE,
-                    scale,
-                    raster_quality,
-                );
+                let (x, y, cw, ch) = cell_rect(size, hx, hy);
+                let (w, h) = (cw + 1.0, ch + 1.0);
+                draw_image_scaled(cr, img, x - 1.0, y - 1.0, w, h, scale, raster_quality);
             }
         }
     }
 
+    // Threats, searched by the caller once per position
+    if opts.threats {
+        for &(tx, ty) in effects.threats {
+            let (x, y, cw, ch) = cell_rect(size, tx, ty);
+            draw_threat_marker(cr, x, y, cw, ch);
+        }
+    }
+
+    // Keyboard cursor
+    if let Some((kc, kr)) = state.keyboard_target() {
+        let (x, y, cw, ch) = cell_rect(size, kc, kr);
+        draw_keyboard_cursor(cr, x, y, cw, ch);
+    }
+
     // Tip
     if let Some((tx, ty)) = state.tip {
         if let Some(img) = res.get("tip") {
-            draw_image(
-                cr,
-                img,
-                FIELD_OFFSET_X - 1.0 + tx as f64 * CELL_SIZE,
-                FIELD_OFFSET_Y - 1.0 + ty as f64 * CELL_SIZE,
-                scale,
-                raster_quality,
-            );
+            let (x, y, cw, ch) = cell_rect(size, tx, ty);
+            let (w, h) = (cw + 1.0, ch + 1.0);
+            draw_image_scaled(cr, img, x - 1.0, y - 1.0, w, h, scale, raster_quality);
+        }
+        if let Some(score) = state.tip_score {
+            let (x, y, cw, _) = cell_rect(size, tx, ty);
+            draw_score_badge(cr, x + cw, y, score);
         }
     }
 
-    // Left tower (player) - uses animated height
-    draw_tower(
-        cr,
-        res,
-        anim_player_tower,
-        TOWER_LEFT_X,
-        scale,
-        raster_quality,
-    );
-    // Right tower (computer)
-    draw_tower(
-        cr,
-        res,
-        anim_computer_tower,
-        TOWER_RIGHT_X,
-        scale,
-        raster_quality,
-    );
-
-    // Flags
-    if anim_player_tower >= 20.0 {
-        if let Some(img) = res.get("flag_blue") {
-            draw_image_scaled(
+    // Player and computer towers on their sides - use animated heights
+    let (player_x, computer_x) = if player_right {
+        (TOWER_RIGHT_X, TOWER_LEFT_X)
+    } else {
+        (TOWER_LEFT_X, TOWER_RIGHT_X)
+    };
+    for (height, base_x, on_player) in [
+        (anim_player_tower, player_x, true),
+        (anim_computer_tower, computer_x, false),
+    ] {
+        let hit = effects.tower_hit.filter(|h| h.on_player == on_player);
+        if hit.is_some() {
+            cr.push_group();
+        }
+        let offset_x = hit.map_or(0.0, |h| h.offset_x);
+        let preview = effects
+            .tower_preview
+            .filter(|p| p.on_player == on_player && state.outcome == GameOutcome::Running);
+        match preview {
+            Some(preview) => draw_tower_preview(
                 cr,
-                img,
-                FLAG_LEFT_X,
-                FLAG_Y,
-                FLAG_SIZE,
-                FLAG_SIZE,
+                res,
+                height,
+                preview.height as f64,
+                state.round_target,
+                base_x + offset_x,
                 scale,
                 raster_quality,
-            );
+            ),
+            None => draw_tower(
+                cr,
+                res,
+                height,
+                state.round_target,
+                base_x + offset_x,
+                scale,
+                raster_quality,
+            ),
+        }
+        if let Some(hit) = hit {
+            // Tint only where the tower was drawn.
+            cr.set_operator(cairo::Operator::Atop);
+            cr.set_source_rgba(0.9, 0.1, 0.05, hit.flash);
+            let _ = cr.paint();
+            cr.set_operator(cairo::Operator::Over);
+            let _ = cr.pop_group_to_source();
+            let _ = cr.paint();
         }
     }
-    if anim_computer_tower >= 20.0 {
-        if let Some(img) = res.get("flag_red") {
+
+    // Search progress over the computer's tower while it is thinking
+    if let Some(progress) = effects.search_progress {
+        draw_search_progress(cr, computer_x, progress, !player_right, opts.color_scheme);
+    }
+
+    if cpu_paused {
+        draw_pause_marker(cr, FIELD_OFFSET_X + FIELD_SIZE / 2.0, FIELD_OFFSET_Y / 2.0);
+    }
+
+    // Flags on the towers that reached the target, in the colour of their side
+    let (left_tower, right_tower) = if player_right {
+        (anim_computer_tower, anim_player_tower)
+    } else {
+        (anim_player_tower, anim_computer_tower)
+    };
+    for (height, flag, flag_x) in [
+        (left_tower, "flag_blue", FLAG_LEFT_X),
+        (right_tower, "flag_red", FLAG_RIGHT_X),
+    ] {
+        if height < state.round_target as f64 {
+            continue;
+        }
+        if let Some(img) = res.get_themed(flag, opts.color_scheme) {
             draw_image_scaled(
                 cr,
                 img,
-                FLAG_RIGHT_X,
+                flag_x,
                 FLAG_Y,
                 FLAG_SIZE,
                 FLAG_SIZE,
@@ -244,91 +877,496 @@ pub fn render(
             _ => None,
         };
         if let Some(idx) = idx {
-            if let Some(img) = res.outcome_overlay(idx) {
+            if let Some(img) = res.outcome_overlay(idx, opts.color_scheme) {
                 draw_image(cr, img, 0.0, 0.0, scale, raster_quality);
             }
         }
     }
 
     let _ = cr.restore();
+
+    // In widget pixels, so it stays legible at any size
+    if let Some(frame_time) = effects.frame_time {
+        draw_frame_stats(cr, frame_time, raster_quality);
+    }
+}
+
+/// Render `state` at rest into a `width` pixels wide image, e.g. as a save
+/// slot thumbnail. `None` if cairo cannot provide the surface.
+pub fn render_thumbnail(
+    state: &GameState,
+    res: &GameResources,
+    opts: &ViewOptions,
+    width: i32,
+) -> Option<Pixbuf> {
+    let surface = render_to_surface(state, res, opts, width)?;
+    gtk4::gdk::pixbuf_get_from_surface(&surface, 0, 0, surface.width(), surface.height())
+}
+
+/// Render `state` at rest into a `width` pixels wide offscreen surface: the
+/// one render-to-buffer path behind thumbnails and exported replays.
+/// `None` if cairo cannot provide the surface.
+pub fn render_to_surface(
+    state: &GameState,
+    res: &GameResources,
+    opts: &ViewOptions,
+    width: i32,
+) -> Option<cairo::ImageSurface> {
+    let height = (width as f64 * REF_HEIGHT / REF_WIDTH).round() as i32;
+    let surface = cairo::ImageSurface::create(cairo::Format::ARgb32, width, height).ok()?;
+    {
+        let cr = Context::new(&surface).ok()?;
+        render_scene(
+            &cr,
+            state,
+            res,
+            width,
+            height,
+            state.tower_player as f64,
+            state.tower_computer as f64,
+            None,
+            false,
+            1.0,
+            1.0,
+            false,
+            Effects::default(),
+            opts,
+            false,
+        );
+    }
+    Some(surface)
+}
+
+/// Straight RGBA bytes of a cairo ARGB32 image (native-endian, premultiplied
+/// words in rows of `stride` bytes), e.g. for an image encoder.
+pub fn argb32_to_rgba(data: &[u8], width: usize, height: usize, stride: usize) -> Vec<u8> {
+    let mut rgba = Vec::with_capacity(width * height * 4);
+    for row in data.chunks(stride).take(height) {
+        for pixel in row.chunks_exact(4).take(width) {
+            let argb = u32::from_ne_bytes([pixel[0], pixel[1], pixel[2], pixel[3]]);
+            let a = argb >> 24;
+            let unpremultiply = |shift: u32| {
+                let c = (argb >> shift) & 0xff;
+                (c * 255 + a / 2)
+                    .checked_div(a)
+                    .map_or(0, |c| c.min(255) as u8)
+            };
+            rgba.extend([
+                unpremultiply(16),
+                unpremultiply(8),
+                unpremultiply(0),
+                a as u8,
+            ]);
+        }
+    }
+    rgba
+}
+
+/// Draw the highlight of a selected row or column with opacity `alpha`.
+fn draw_selection(
+    cr: &Context,
+    res: &GameResources,
+    size: usize,
+    selection: Selection,
+    alpha: f64,
+    scale: f64,
+    raster_quality: f64,
+) {
+    let (img, x, y, w, h) = match selection {
+        Selection::Column(c) => {
+            let (x, y, cw, _) = cell_rect(size, c, 0);
+            (res.get("vertical"), x, y, cw + 1.0, FIELD_SIZE + 1.0)
+        }
+        Selection::Row(r) => {
+            let (x, y, _, ch) = cell_rect(size, 0, r);
+            (res.get("horizontal"), x, y, FIELD_SIZE + 1.0, ch + 1.0)
+        }
+    };
+    let Some(img) = img else {
+        return;
+    };
+    if alpha >= 1.0 {
+        draw_image_scaled(cr, img, x - 1.0, y - 1.0, w, h, scale, raster_quality);
+    } else {
+        draw_image_alpha_scaled(
+            cr,
+            img,
+            x - 1.0,
+            y - 1.0,
+            w,
+            h,
+            alpha,
+            scale,
+            raster_quality,
+        );
+    }
 }
 
 fn draw_tower(
     cr: &Context,
     res: &GameResources,
     height: f64,
+    target: i32,
     base_x: f64,
     scale: f64,
     raster_quality: f64,
 ) {
     let int_height = height.floor() as i32;
     let frac = height - height.floor();
+    let row_height = TOWER_HEIGHT / target.max(1) as f64;
 
-    for i in 0..20i32 {
+    for i in 0..target {
         if i < int_height || (i == int_height && frac > 0.0) {
             let alpha = if i == int_height { frac } else { 1.0 };
-            let (img, x_offset, target_w) = if i < 18 {
-                (res.tower_row(i as usize % 2), 0.0, TOWER_ROW_WIDTH_NORMAL)
-            } else if i == 18 {
-                (res.tower_row(2), -8.0, TOWER_ROW_WIDTH_TOP)
+            // The two top rows are the wider battlements.
+            let (row_idx, x_offset, target_w) = if i < target - 2 {
+                (i as usize % 2, 0.0, TOWER_ROW_WIDTH_NORMAL)
+            } else if i == target - 2 {
+                (2, -8.0, TOWER_ROW_WIDTH_TOP)
             } else {
-                (res.tower_row(3), -8.0, TOWER_ROW_WIDTH_TOP)
+                (3, -8.0, TOWER_ROW_WIDTH_TOP)
             };
 
-            if let Some(img) = img {
-                let x = base_x + x_offset;
-                let y = TOWER_BASE_Y - TOWER_ROW_HEIGHT - i as f64 * TOWER_ROW_HEIGHT;
-                if alpha >= 1.0 {
-                    draw_image_scaled(
-                        cr,
-                        img,
-                        x,
-                        y,
-                        target_w,
-                        TOWER_ROW_HEIGHT,
-                        scale,
-                        raster_quality,
-                    );
-                } else {
+            let x = base_x + x_offset;
+            let y = TOWER_BASE_Y - row_height - i as f64 * row_height;
+            match res.tower_row(row_idx) {
+                Some(img) if alpha >= 1.0 => {
+                    draw_image_scaled(cr, img, x, y, target_w, row_height, scale, raster_quality);
+                }
+                Some(img) => {
                     draw_image_alpha_scaled(
                         cr,
                         img,
                         x,
                         y,
                         target_w,
-                        TOWER_ROW_HEIGHT,
+                        row_height,
                         alpha,
                         scale,
                         raster_quality,
                     );
                 }
+                None => {
+                    let name = GameResources::TOWER_ROWS[row_idx];
+                    draw_placeholder(cr, x, y, target_w, row_height, name);
+                }
             }
         }
     }
 }
 
-/// Convert widget-space mouse coordinates back to reference coordinates,
-/// then to board (col, row).
-pub fn mouse_to_cell(x: f64, y: f64, widget_w: i32, widget_h: i32) -> Option<(usize, usize)> {
+/// Draw a tower of `height` rows with the rows up to `projected` as a ghost:
+/// translucent additional rows when it would grow, translucent top rows when
+/// it would shrink.
+#[allow(clippy::too_many_arguments)]
+fn draw_tower_preview(
+    cr: &Context,
+    res: &GameResources,
+    height: f64,
+    projected: f64,
+    target: i32,
+    base_x: f64,
+    scale: f64,
+    raster_quality: f64,
+) {
+    let row_height = TOWER_HEIGHT / target.max(1) as f64;
+    let cut_y = TOWER_BASE_Y - height.min(projected) * row_height;
+
+    cr.push_group();
+    draw_tower(
+        cr,
+        res,
+        height.max(projected),
+        target,
+        base_x,
+        scale,
+        raster_quality,
+    );
+    let _ = cr.pop_group_to_source();
+    let _ = cr.save();
+    cr.rectangle(0.0, cut_y, REF_WIDTH, REF_HEIGHT - cut_y);
+    cr.clip();
+    let _ = cr.paint();
+    let _ = cr.restore();
+    let _ = cr.save();
+    cr.rectangle(0.0, 0.0, REF_WIDTH, cut_y);
+    cr.clip();
+    let _ = cr.paint_with_alpha(GHOST_ALPHA);
+    let _ = cr.restore();
+}
+
+/// Rectangle (x, y, w, h) of the board cell (col, row) in reference coordinates,
+/// for a board with `size` cells per edge.
+///
+/// This is the single source of truth for the cell layout: drawing and
+/// hit-testing both go through it so they cannot drift apart.
+pub fn cell_rect(size: usize, col: usize, row: usize) -> (f64, f64, f64, f64) {
+    let cell = FIELD_SIZE / size.max(1) as f64;
+    (
+        FIELD_OFFSET_X + col as f64 * cell,
+        FIELD_OFFSET_Y + row as f64 * cell,
+        cell,
+        cell,
+    )
+}
+
+/// Draw dotted field lines matching the grid artwork for non-default board sizes.
+fn draw_field_grid(cr: &Context, size: usize) {
+    let _ = cr.save();
+    cr.set_source_rgb(1.0, 1.0, 1.0);
+    cr.set_line_width(0.865);
+    cr.set_dash(&[0.865, 2.595], 0.0);
+    let (x0, y0) = (FIELD_OFFSET_X - 0.6, FIELD_OFFSET_Y - 0.6);
+    let cell = FIELD_SIZE / size as f64;
+    for i in 0..=size {
+        let d = i as f64 * cell;
+        cr.move_to(x0 + d, y0);
+        cr.line_to(x0 + d, y0 + FIELD_SIZE);
+        cr.move_to(x0, y0 + d);
+        cr.line_to(x0 + FIELD_SIZE, y0 + d);
+    }
+    let _ = cr.stroke();
+    let _ = cr.restore();
+}
+
+/// Width of the heatmap bands along the top and left field edges.
+const HEATMAP_BAND: f64 = 6.0;
+/// Average `cell_value` per cell at which a band is fully tinted.
+const HEATMAP_FULL_VALUE: f64 = 20.0;
+
+/// Tint of an axis worth `sum` on a board of `size`: green for valuable axes,
+/// red for bomb-heavy ones, more opaque the further from neutral.
+fn heatmap_rgba(sum: i32, size: usize) -> (f64, f64, f64, f64) {
+    let heat = (sum as f64 / (size.max(1) as f64 * HEATMAP_FULL_VALUE)).clamp(-1.0, 1.0);
+    let alpha = 0.85 * heat.abs();
+    if heat >= 0.0 {
+        (0.15, 0.8, 0.25, alpha)
+    } else {
+        (0.9, 0.15, 0.1, alpha)
+    }
+}
+
+/// Bands just outside the field: one per column above it and one per row left
+/// of it, tinted by the axis value.
+fn draw_axis_heatmap(cr: &Context, size: usize, values: &AxisValues) {
+    let _ = cr.save();
+    for (col, &sum) in values.columns.iter().enumerate() {
+        let (x, _, w, _) = cell_rect(size, col, 0);
+        let (r, g, b, a) = heatmap_rgba(sum, size);
+        cr.set_source_rgba(r, g, b, a);
+        cr.rectangle(x, FIELD_OFFSET_Y - HEATMAP_BAND, w, HEATMAP_BAND);
+        let _ = cr.fill();
+    }
+    for (row, &sum) in values.rows.iter().enumerate() {
+        let (_, y, _, h) = cell_rect(size, 0, row);
+        let (r, g, b, a) = heatmap_rgba(sum, size);
+        cr.set_source_rgba(r, g, b, a);
+        cr.rectangle(FIELD_OFFSET_X - HEATMAP_BAND, y, HEATMAP_BAND, h);
+        let _ = cr.fill();
+    }
+    let _ = cr.restore();
+}
+
+/// Gap between the field edge and the centre of a coordinate label.
+const COORDINATE_MARGIN: f64 = 9.0;
+const COORDINATE_FONT_SIZE: f64 = 11.0;
+
+/// Column letters (A, B, …) centred above the field and row numbers (1, 2, …)
+/// centred left of it: each label with the centre point it is drawn around.
+fn coordinate_labels(size: usize) -> Vec<(String, f64, f64)> {
+    let columns = (0..size).map(|col| {
+        let (x, _, w, _) = cell_rect(size, col, 0);
+        let letter = char::from(b'A' + col as u8).to_string();
+        (letter, x + w / 2.0, FIELD_OFFSET_Y - COORDINATE_MARGIN)
+    });
+    let rows = (0..size).map(|row| {
+        let (_, y, _, h) = cell_rect(size, 0, row);
+        (
+            (row + 1).to_string(),
+            FIELD_OFFSET_X - COORDINATE_MARGIN,
+            y + h / 2.0,
+        )
+    });
+    columns.chain(rows).collect()
+}
+
+/// Draw the coordinate guide outside the field, where it never covers a piece.
+fn draw_coordinates(cr: &Context, size: usize) {
+    let _ = cr.save();
+    cr.select_font_face("Sans", cairo::FontSlant::Normal, cairo::FontWeight::Bold);
+    cr.set_font_size(COORDINATE_FONT_SIZE);
+    for (text, cx, cy) in coordinate_labels(size) {
+        let Ok(ext) = cr.text_extents(&text) else {
+            continue;
+        };
+        cr.move_to(
+            cx - ext.width() / 2.0 - ext.x_bearing(),
+            cy - ext.height() / 2.0 - ext.y_bearing(),
+        );
+        cr.text_path(&text);
+    }
+    cr.set_source_rgba(0.0, 0.0, 0.0, 0.5);
+    cr.set_line_width(2.0);
+    cr.set_line_join(cairo::LineJoin::Round);
+    let _ = cr.stroke_preserve();
+    cr.set_source_rgba(1.0, 1.0, 1.0, 0.8);
+    let _ = cr.fill();
+    let _ = cr.restore();
+}
+
+/// Uniform scale and centring offset that map reference coordinates into a
+/// widget of the given size. Returns (scale, offset_x, offset_y).
+pub fn layout_transform(widget_w: i32, widget_h: i32) -> (f64, f64, f64) {
     let w = widget_w as f64;
     let h = widget_h as f64;
     let scale_x = w / REF_WIDTH;
     let scale_y = h / REF_HEIGHT;
     let scale = scale_x.min(scale_y);
+
+    // Centre the scaled content
     let offset_x = (w - REF_WIDTH * scale) / 2.0;
     let offset_y = (h - REF_HEIGHT * scale) / 2.0;
+    (scale, offset_x, offset_y)
+}
 
-    let rx = (x - offset_x) / scale;
-    let ry = (y - offset_y) / scale;
+/// Pick the texture for a cell. Returns the image and whether value pips must
+/// be drawn on top (pip mode requested, or the theme lacks numbered variants).
+fn piece_image<'a>(
+    res: &'a GameResources,
+    cell: &Cell,
+    value_pips: bool,
+) -> (Option<&'a GameImage>, bool) {
+    let numbered = match cell.kind {
+        CellKind::Bomb => res.bomb(cell.value),
+        CellKind::Stone => res.stone(cell.value),
+        CellKind::Banana => return (res.get("banana"), false),
+        CellKind::Empty => return (None, false),
+    };
+    match numbered {
+        Some(img) if !value_pips => (Some(img), false),
+        _ => (res.base_piece(cell.kind), true),
+    }
+}
 
-    let col = ((rx - FIELD_OFFSET_X) / CELL_SIZE).floor() as i32;
-    let row = ((ry - FIELD_OFFSET_Y) / CELL_SIZE).floor() as i32;
+/// Resource name of the numbered texture of a piece, e.g. `3s`.
+fn piece_name(cell: &Cell) -> Option<String> {
+    match cell.kind {
+        CellKind::Stone => Some(format!("{}s", cell.value + 1)),
+        CellKind::Bomb => Some(format!("{}b", cell.value + 1)),
+        CellKind::Banana => Some("banana".to_string()),
+        CellKind::Empty => None,
+    }
+}
 
-    if col >= 0 && col < BOARD_SIZE as i32 && row >= 0 && row < BOARD_SIZE as i32 {
-        Some((col as usize, row as usize))
-    } else {
-        None
+/// Stand-in for a missing image: a hatched grey box labelled with the image
+/// name, so a broken installation is obvious instead of silently blank.
+fn draw_placeholder(cr: &Context, x: f64, y: f64, w: f64, h: f64, name: &str) {
+    let _ = cr.save();
+    cr.rectangle(x, y, w, h);
+    cr.clip_preserve();
+    cr.set_source_rgb(0.35, 0.35, 0.38);
+    let _ = cr.fill_preserve();
+    cr.set_source_rgb(0.8, 0.2, 0.6);
+    cr.set_line_width(1.0);
+    let _ = cr.stroke();
+    let step = 8.0;
+    let mut d = -h;
+    while d < w {
+        cr.move_to(x + d, y + h);
+        cr.line_to(x + d + h, y);
+        d += step;
+    }
+    cr.set_source_rgba(1.0, 1.0, 1.0, 0.15);
+    let _ = cr.stroke();
+
+    cr.select_font_face("Sans", cairo::FontSlant::Normal, cairo::FontWeight::Bold);
+    cr.set_font_size((h * 0.5).min(12.0));
+    if let Ok(ext) = cr.text_extents(name) {
+        cr.move_to(
+            x + (w - ext.width()) / 2.0 - ext.x_bearing(),
+            y + (h - ext.height()) / 2.0 - ext.y_bearing(),
+        );
+        cr.set_source_rgb(1.0, 1.0, 1.0);
+        let _ = cr.show_text(name);
+    }
+    let _ = cr.restore();
+}
+
+/// Overlay 1–4 pips along the bottom edge of a cell to show its value.
+fn draw_value_pips(cr: &Context, x: f64, y: f64, w: f64, h: f64, cell: &Cell) {
+    let count = (cell.value + 1).clamp(1, 4);
+    let radius = w * 0.07;
+    let spacing = radius * 2.8;
+    let total = spacing * (count - 1) as f64;
+    let cy = y + h - radius * 2.0;
+    for i in 0..count {
+        let cx = x + w / 2.0 - total / 2.0 + i as f64 * spacing;
+        cr.arc(cx, cy, radius, 0.0, 2.0 * std::f64::consts::PI);
+        if cell.kind == CellKind::Bomb {
+            cr.set_source_rgb(0.9, 0.2, 0.15);
+        } else {
+            cr.set_source_rgb(0.95, 0.95, 0.9);
+        }
+        let _ = cr.fill_preserve();
+        cr.set_source_rgba(0.0, 0.0, 0.0, 0.8);
+        cr.set_line_width(radius * 0.35);
+        let _ = cr.stroke();
+    }
+}
+
+/// Write the signed value of a stone or bomb centred on its cell. White text
+/// with a dark outline stays readable on any piece art. Bananas get no label.
+fn draw_value_label(cr: &Context, x: f64, y: f64, w: f64, h: f64, cell: &Cell) {
+    let value = match cell.kind {
+        CellKind::Stone => cell.value + 1,
+        CellKind::Bomb => -(cell.value + 1),
+        _ => return,
+    };
+    let text = format!("{:+}", value);
+    let _ = cr.save();
+    cr.select_font_face("Sans", cairo::FontSlant::Normal, cairo::FontWeight::Bold);
+    cr.set_font_size(h * 0.4);
+    let Ok(ext) = cr.text_extents(&text) else {
+        let _ = cr.restore();
+        return;
+    };
+    cr.move_to(
+        x + (w - ext.width()) / 2.0 - ext.x_bearing(),
+        y + (h - ext.height()) / 2.0 - ext.y_bearing(),
+    );
+    cr.text_path(&text);
+    cr.set_source_rgba(0.0, 0.0, 0.0, 0.85);
+    cr.set_line_width(h * 0.06);
+    cr.set_line_join(cairo::LineJoin::Round);
+    let _ = cr.stroke_preserve();
+    cr.set_source_rgb(1.0, 1.0, 1.0);
+    let _ = cr.fill();
+    let _ = cr.restore();
+}
+
+/// Convert widget-space mouse coordinates back to reference coordinates,
+/// then to (col, row) on a board with `size` cells per edge.
+pub fn mouse_to_cell(
+    x: f64,
+    y: f64,
+    widget_w: i32,
+    widget_h: i32,
+    size: usize,
+) -> Option<(usize, usize)> {
+    let (scale, offset_x, offset_y) = layout_transform(widget_w, widget_h);
+    if scale <= 0.0 {
+        return None;
     }
+
+    let rx = (x - offset_x) / scale;
+    let ry = (y - offset_y) / scale;
+
+    (0..size)
+        .flat_map(|col| (0..size).map(move |row| (col, row)))
+        .find(|&(col, row)| {
+            let (cx, cy, cw, ch) = cell_rect(size, col, row);
+            rx >= cx && rx < cx + cw && ry >= cy && ry < cy + ch
+        })
 }
 
 // ── Image drawing helpers ────────────────────────────────────────────────────
@@ -457,7 +1495,8 @@ fn draw_image_alpha_scaled(
 /// with reference size (w x h).
 ///
 /// The key insight: we rasterize the SVG at device-pixel resolution
-/// (`w * scale`, `h * scale`) so the output is crisp, then temporarily
+/// (`w * scale`, `h * scale`, where `scale` already includes the device
+/// scale factor) so the output is crisp, then temporarily
 /// undo the CTM scaling to paint the pre-scaled pixbuf 1:1 onto the
 /// device surface.  This avoids Cairo's bilinear upscaling of a small
 /// raster image.
@@ -492,8 +1531,14 @@ fn render_svg(
         return;
     }
 
-    // Use the tree pointer + pixel dimensions as cache key
-    let cache_key = (tree as *const _ as usize, render_w, render_h);
+    // Use the tree pointer + pixel dimensions + device scale as cache key
+    let device_scale = cr.target().device_scale().0;
+    let cache_key = (
+        tree as *const _ as usize,
+        render_w,
+        render_h,
+        device_scale_key(device_scale),
+    );
 
     // Try to get a cached pixbuf; if not found, rasterize and cache it
     let pixbuf = SVG_CACHE.with(|cache| {
@@ -561,21 +1606,423 @@ fn render_svg(
     let _ = cr.restore();
 }
 
+/// Draw a "⏸" marker (dark disc with two bars) centred at (cx, cy).
+fn draw_pause_marker(cr: &Context, cx: f64, cy: f64) {
+    let _ = cr.save();
+    cr.arc(cx, cy, 13.0, 0.0, 2.0 * std::f64::consts::PI);
+    cr.set_source_rgba(0.0, 0.0, 0.0, 0.65);
+    let _ = cr.fill();
+    cr.set_source_rgba(1.0, 1.0, 1.0, 0.95);
+    cr.rectangle(cx - 5.0, cy - 6.0, 3.5, 12.0);
+    cr.rectangle(cx + 1.5, cy - 6.0, 3.5, 12.0);
+    let _ = cr.fill();
+    let _ = cr.restore();
+}
+
+/// Draw a small "+N" / "-N" label whose top-right corner sits at (right, top).
+fn draw_score_badge(cr: &Context, right: f64, top: f64, score: i32) {
+    let text = format!("{:+}", score);
+    let _ = cr.save();
+    cr.select_font_face("Sans", cairo::FontSlant::Normal, cairo::FontWeight::Bold);
+    cr.set_font_size(9.0);
+    let Ok(ext) = cr.text_extents(&text) else {
+        let _ = cr.restore();
+        return;
+    };
+    let pad = 2.0;
+    let (w, h) = (ext.width() + pad * 2.0, ext.height() + pad * 2.0);
+    let (x, y) = (right - w, top);
+    cr.rectangle(x, y, w, h);
+    cr.set_source_rgba(0.0, 0.0, 0.0, 0.7);
+    let _ = cr.fill();
+    if score >= 0 {
+        cr.set_source_rgb(0.55, 1.0, 0.55);
+    } else {
+        cr.set_source_rgb(1.0, 0.55, 0.55);
+    }
+    cr.move_to(x + pad - ext.x_bearing(), y + pad - ext.y_bearing());
+    let _ = cr.show_text(&text);
+    let _ = cr.restore();
+}
+
+/// Draw the keyboard cursor as a dashed frame so it stands apart from the
+/// (image based) mouse hover highlight.
+fn draw_keyboard_cursor(cr: &Context, x: f64, y: f64, w: f64, h: f64) {
+    let _ = cr.save();
+    cr.set_source_rgba(1.0, 0.85, 0.1, 0.95);
+    cr.set_line_width(2.0);
+    cr.set_dash(&[4.0, 3.0], 0.0);
+    cr.rectangle(x + 2.0, y + 2.0, w - 4.0, h - 4.0);
+    let _ = cr.stroke();
+    let _ = cr.restore();
+}
+
+/// Tint a cell that hands the opponent a win. The diagonal hatching keeps it
+/// recognisable in every colour scheme.
+fn draw_threat_marker(cr: &Context, x: f64, y: f64, w: f64, h: f64) {
+    let _ = cr.save();
+    cr.rectangle(x, y, w, h);
+    cr.clip();
+    cr.set_source_rgba(1.0, 0.45, 0.0, 0.3);
+    let _ = cr.paint();
+    cr.set_source_rgba(0.85, 0.2, 0.0, 0.6);
+    cr.set_line_width(1.5);
+    let step = 6.0;
+    let mut offset = -h;
+    while offset < w {
+        cr.move_to(x + offset, y + h);
+        cr.line_to(x + offset + h, y);
+        offset += step;
+    }
+    let _ = cr.stroke();
+    let _ = cr.restore();
+}
+
+/// Pulse colour (RGB) of the player or the computer in the given scheme.
+/// The colour-blind variants use pairs from the Okabe–Ito palette.
+fn pulse_rgb(scheme: ColorScheme, is_cpu: bool) -> (f64, f64, f64) {
+    match (scheme, is_cpu) {
+        (ColorScheme::Default, false) => (0.2, 0.5, 1.0),
+        (ColorScheme::Default, true) => (1.0, 0.2, 0.2),
+        // Red-green deficiencies: blue vs. orange
+        (ColorScheme::Deuteranopia | ColorScheme::Protanopia, false) => (0.0, 0.45, 0.7),
+        (ColorScheme::Deuteranopia | ColorScheme::Protanopia, true) => (0.9, 0.6, 0.0),
+        // Blue-yellow deficiency: bluish green vs. vermilion
+        (ColorScheme::Tritanopia, false) => (0.0, 0.62, 0.45),
+        (ColorScheme::Tritanopia, true) => (0.84, 0.37, 0.0),
+    }
+}
+
 /// Draw a pulsing coloured rectangle around a cell.
 /// `progress` goes from 0.0 to 1.0 over the pulse duration.
 /// The alpha and line width oscillate using a sine wave for a smooth pulse effect.
-fn draw_pulse_highlight(cr: &Context, x: f64, y: f64, w: f64, h: f64, progress: f64, is_cpu: bool) {
+/// Outside the default scheme the computer's frame is also dashed, so the two
+/// sides differ in shape and not only in colour.
+#[allow(clippy::too_many_arguments)]
+fn draw_pulse_highlight(
+    cr: &Context,
+    x: f64,
+    y: f64,
+    w: f64,
+    h: f64,
+    progress: f64,
+    is_cpu: bool,
+    scheme: ColorScheme,
+) {
     let t = (progress * 3.0 * 2.0 * std::f64::consts::PI).sin().abs();
     let alpha = 0.3 + 0.7 * t;
     let line_w = 2.0 + 2.0 * t;
 
-    if is_cpu {
-        cr.set_source_rgba(1.0, 0.2, 0.2, alpha);
-    } else {
-        cr.set_source_rgba(0.2, 0.5, 1.0, alpha);
-    }
+    let (r, g, b) = pulse_rgb(scheme, is_cpu);
+    cr.set_source_rgba(r, g, b, alpha);
     cr.set_line_width(line_w);
+    if is_cpu && scheme != ColorScheme::Default {
+        cr.set_dash(&[6.0, 4.0], 0.0);
+    }
     let inset = line_w / 2.0;
     cr.rectangle(x + inset, y + inset, w - line_w, h - line_w);
     let _ = cr.stroke();
+    cr.set_dash(&[], 0.0);
+}
+
+/// Bar along the far edge of the active axis whose length is the share `left`
+/// of the move's time; it shrinks toward the start of the axis.
+fn draw_move_clock(
+    cr: &Context,
+    size: usize,
+    selection: Selection,
+    left: f64,
+    is_cpu: bool,
+    scheme: ColorScheme,
+) {
+    const THICKNESS: f64 = 4.0;
+    let length = FIELD_SIZE * left;
+    let (x, y, w, h) = match selection {
+        Selection::Row(r) => {
+            let (x, y, _, ch) = cell_rect(size, 0, r);
+            (x, y + ch - THICKNESS, length, THICKNESS)
+        }
+        Selection::Column(c) => {
+            let (x, y, cw, _) = cell_rect(size, c, 0);
+            (x + cw - THICKNESS, y, THICKNESS, length)
+        }
+    };
+    let (r, g, b) = pulse_rgb(scheme, is_cpu);
+    cr.set_source_rgba(r, g, b, 0.85);
+    cr.rectangle(x, y, w, h);
+    let _ = cr.fill();
+}
+
+/// A thin bar above the tower at `base_x`: filled by the share of root moves
+/// a fixed-depth search has scored, or labelled with the depth iterative
+/// deepening is searching.
+fn draw_search_progress(
+    cr: &Context,
+    base_x: f64,
+    progress: SearchProgress,
+    is_cpu: bool,
+    scheme: ColorScheme,
+) {
+    const THICKNESS: f64 = 4.0;
+    const GAP: f64 = 8.0;
+    let (x, w) = (base_x - 8.0, TOWER_ROW_WIDTH_TOP);
+    let y = TOWER_BASE_Y - TOWER_HEIGHT - GAP - THICKNESS;
+    let (r, g, b) = pulse_rgb(scheme, is_cpu);
+    let _ = cr.save();
+    match progress {
+        SearchProgress::RootMoves { done, total } => {
+            cr.set_source_rgba(0.0, 0.0, 0.0, 0.4);
+            cr.rectangle(x, y, w, THICKNESS);
+            let _ = cr.fill();
+            let share = done as f64 / total.max(1) as f64;
+            cr.set_source_rgba(r, g, b, 0.85);
+            cr.rectangle(x, y, w * share.clamp(0.0, 1.0), THICKNESS);
+            let _ = cr.fill();
+        }
+        SearchProgress::Depth(depth) => {
+            let text = depth.to_string();
+            cr.select_font_face("Sans", cairo::FontSlant::Normal, cairo::FontWeight::Bold);
+            cr.set_font_size(9.0);
+            if let Ok(ext) = cr.text_extents(&text) {
+                let h = ext.height() + 4.0;
+                cr.set_source_rgba(0.0, 0.0, 0.0, 0.6);
+                cr.rectangle(x, y + THICKNESS - h, w, h);
+                let _ = cr.fill();
+                cr.set_source_rgb(r, g, b);
+                cr.move_to(
+                    x + (w - ext.width()) / 2.0 - ext.x_bearing(),
+                    y + THICKNESS - 2.0 - ext.height() - ext.y_bearing(),
+                );
+                let _ = cr.show_text(&text);
+            }
+        }
+    }
+    let _ = cr.restore();
+}
+
+/// The `--show-fps` counter in the top left corner: smoothed frame time,
+/// the frame rate it amounts to and the raster quality of this frame.
+fn draw_frame_stats(cr: &Context, frame_time: f64, raster_quality: f64) {
+    let text = format!(
+        "{:.1} ms ({:.0} fps) · quality {:.1}",
+        frame_time * 1000.0,
+        1.0 / frame_time.max(1e-6),
+        raster_quality
+    );
+    let _ = cr.save();
+    cr.select_font_face(
+        "Monospace",
+        cairo::FontSlant::Normal,
+        cairo::FontWeight::Normal,
+    );
+    cr.set_font_size(12.0);
+    if let Ok(extents) = cr.text_extents(&text) {
+        cr.set_source_rgba(0.0, 0.0, 0.0, 0.6);
+        cr.rectangle(4.0, 4.0, extents.x_advance() + 8.0, 18.0);
+        let _ = cr.fill();
+    }
+    cr.set_source_rgb(1.0, 1.0, 1.0);
+    cr.move_to(8.0, 17.0);
+    let _ = cr.show_text(&text);
+    let _ = cr.restore();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::field::{MAX_BOARD_SIZE, MIN_BOARD_SIZE};
+
+    const SIZES: [(i32, i32); 5] = [
+        (596, 393),
+        (1192, 786),
+        (800, 600),
+        (300, 700),
+        (1920, 1080),
+    ];
+
+    /// Map a reference-space point into widget space using the forward layout.
+    fn to_widget(rx: f64, ry: f64, w: i32, h: i32) -> (f64, f64) {
+        let (scale, offset_x, offset_y) = layout_transform(w, h);
+        (offset_x + rx * scale, offset_y + ry * scale)
+    }
+
+    #[test]
+    fn heatmap_tints_grow_with_the_axis_value() {
+        let alpha = |sum| heatmap_rgba(sum, 8).3;
+        assert_eq!(alpha(0), 0.0);
+        assert!(alpha(40) < alpha(80));
+        assert_eq!(alpha(8 * 20), alpha(8 * 50), "saturates");
+        let (r, g, _, _) = heatmap_rgba(60, 8);
+        assert!(g > r, "valuable axes are green");
+        let (r, g, _, _) = heatmap_rgba(-60, 8);
+        assert!(r > g, "bomb-heavy axes are red");
+        assert_eq!(alpha(-60), alpha(60));
+    }
+
+    #[test]
+    fn tone_curves_spread_and_shift_the_channels() {
+        let neutral = BoardTone::default();
+        assert!(neutral.is_neutral());
+        let table = neutral.lookup_table();
+        assert!((0..=255u8).all(|v| table[v as usize] == v));
+
+        let contrast = BoardTone {
+            brightness: 0.0,
+            contrast: 2.0,
+        }
+        .lookup_table();
+        assert_eq!((contrast[64], contrast[128], contrast[192]), (0, 129, 255));
+        let brighter = BoardTone {
+            brightness: 0.2,
+            contrast: 1.0,
+        }
+        .lookup_table();
+        assert_eq!((brighter[0], brighter[255]), (51, 255));
+
+        // Opaque pixels take the table as is; translucent ones keep their alpha.
+        let words: [u32; 2] = [0xff40_80c0, 0x8020_4060];
+        let mut data: Vec<u8> = words.iter().flat_map(|w| w.to_ne_bytes()).collect();
+        apply_tone(&mut data, 8, &brighter);
+        let toned: Vec<u32> = data
+            .chunks_exact(4)
+            .map(|p| u32::from_ne_bytes([p[0], p[1], p[2], p[3]]))
+            .collect();
+        let (o, t) = (toned[0], toned[1]);
+        assert_eq!(
+            o,
+            0xff00_0000
+                | (brighter[0x40] as u32) << 16
+                | (brighter[0x80] as u32) << 8
+                | brighter[0xc0] as u32
+        );
+        assert_eq!(t >> 24, 0x80);
+        assert!((t >> 16) & 0xff > 0x20 && (t >> 16) & 0xff <= 0x80);
+    }
+
+    #[test]
+    fn argb32_pixels_become_straight_rgba() {
+        let words: [u32; 3] = [0xff10_2030, 0x8040_2000, 0x0000_0000];
+        let mut data: Vec<u8> = words.iter().flat_map(|w| w.to_ne_bytes()).collect();
+        // Row padding beyond the width is skipped.
+        data.extend([0xaa; 4]);
+        let rgba = argb32_to_rgba(&data, 3, 1, 16);
+        assert_eq!(
+            rgba,
+            vec![0x10, 0x20, 0x30, 0xff, 0x80, 0x40, 0x00, 0x80, 0, 0, 0, 0]
+        );
+    }
+
+    #[test]
+    fn lru_cache_stays_bounded_and_keeps_recent_entries() {
+        let mut cache = LruCache::new(4);
+        for i in 0..4u32 {
+            cache.insert(i, i * 10);
+        }
+        // Touch 0 so that 1 becomes the least recently used entry.
+        assert_eq!(cache.get(&0), Some(&0));
+        for i in 4..10u32 {
+            cache.insert(i, i * 10);
+            assert!(cache.len() <= 4);
+        }
+        assert_eq!(cache.len(), 4);
+        for i in 6..10u32 {
+            assert_eq!(cache.get(&i), Some(&(i * 10)));
+        }
+        assert_eq!(cache.get(&1), None);
+        assert_eq!(cache.get(&0), None);
+
+        // Re-inserting an existing key does not evict anything.
+        cache.insert(9, 99);
+        assert_eq!(cache.len(), 4);
+        assert_eq!(cache.get(&6), Some(&60));
+    }
+
+    #[test]
+    fn lru_cache_keeps_only_the_current_device_scale() {
+        let mut cache = LruCache::new(8);
+        for (i, scale) in [1.0, 2.0, 1.0, 1.25].into_iter().enumerate() {
+            cache.insert((7, 10 * i as u32, 10, device_scale_key(scale)), i);
+        }
+        let current = device_scale_key(2.0);
+        cache.retain(|&(_, _, _, scale)| scale == current);
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.get(&(7, 10, 10, current)), Some(&1));
+    }
+
+    #[test]
+    fn backdrop_is_composited_again_only_when_its_inputs_change() {
+        let generation = 1;
+        let key = backdrop_key(generation, 8, (800, 600), 1.0, 1.0);
+        assert_eq!(backdrop_key(generation, 8, (800, 600), 1.0, 1.0), key);
+        for other in [
+            backdrop_key(generation, 8, (640, 600), 1.0, 1.0),
+            backdrop_key(generation, 8, (800, 600), 2.0, 1.0),
+            backdrop_key(generation, 8, (800, 600), 1.0, 0.5),
+            backdrop_key(generation, 10, (800, 600), 1.0, 1.0),
+            backdrop_key(generation + 1, 8, (800, 600), 1.0, 1.0),
+        ] {
+            assert_ne!(other, key);
+        }
+    }
+
+    #[test]
+    fn mouse_to_cell_inverts_cell_centres() {
+        for n in MIN_BOARD_SIZE..=MAX_BOARD_SIZE {
+            for &(w, h) in &SIZES {
+                for col in 0..n {
+                    for row in 0..n {
+                        let (cx, cy, cw, ch) = cell_rect(n, col, row);
+                        let (x, y) = to_widget(cx + cw / 2.0, cy + ch / 2.0, w, h);
+                        assert_eq!(
+                            mouse_to_cell(x, y, w, h, n),
+                            Some((col, row)),
+                            "board {n}, size {w}x{h}"
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn coordinate_labels_sit_outside_the_field() {
+        for n in MIN_BOARD_SIZE..=MAX_BOARD_SIZE {
+            let labels = coordinate_labels(n);
+            assert_eq!(labels.len(), 2 * n);
+            assert_eq!(labels[0].0, "A");
+            assert_eq!(labels[n - 1].0, char::from(b'A' + n as u8 - 1).to_string());
+            assert_eq!(labels[2 * n - 1].0, n.to_string());
+            for &(w, h) in &SIZES {
+                for (text, cx, cy) in &labels {
+                    let (x, y) = to_widget(*cx, *cy, w, h);
+                    assert_eq!(mouse_to_cell(x, y, w, h, n), None, "label {text}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn mouse_to_cell_rejects_points_outside_board() {
+        let n = DEFAULT_BOARD_SIZE;
+        for &(w, h) in &SIZES {
+            let (left, top, _, _) = cell_rect(n, 0, 0);
+            let (x1, y1, cw, ch) = cell_rect(n, n - 1, n - 1);
+            let (right, bottom) = (x1 + cw, y1 + ch);
+            let mid_x = (left + right) / 2.0;
+            let mid_y = (top + bottom) / 2.0;
+            for (rx, ry) in [
+                (left - 0.5, mid_y),
+                (right + 0.5, mid_y),
+                (mid_x, top - 0.5),
+                (mid_x, bottom + 0.5),
+            ] {
+                let (x, y) = to_widget(rx, ry, w, h);
+                assert_eq!(
+                    mouse_to_cell(x, y, w, h, n),
+                    None,
+                    "size {w}x{h} at ({rx}, {ry})"
+                );
+            }
+        }
+    }
 }
@@ -0,0 +1,129 @@
+use std::fs::File;
+use std::io::{self, BufWriter};
+use std::path::Path;
+
+use super::rendering::{self, ViewOptions};
+use super::resources::GameResources;
+use crate::game::logic::GameState;
+
+/// Frame rates offered when exporting a replay, in frames per second.
+pub const REPLAY_FPS_CHOICES: [u32; 3] = [1, 2, 4];
+/// Frame rate preselected in the file chooser.
+pub const DEFAULT_REPLAY_FPS: u32 = 2;
+/// Width of the exported frames in pixels; the height follows the layout.
+const REPLAY_WIDTH: i32 = 480;
+/// The final position stays up this many frames before the loop restarts.
+const FINAL_FRAME_HOLD: u16 = 4;
+/// NeuQuant sampling speed (1 = best colours, 30 = fastest).
+const QUANTIZE_SPEED: i32 = 10;
+
+/// Render the frames of `game`: the initial board, then the board after
+/// every logged move. Cairo and the resources stay on the main thread, so
+/// this runs there; [`write_replay_gif`] can then go to a worker thread.
+pub fn render_replay(
+    game: &GameState,
+    res: &GameResources,
+    opts: &ViewOptions,
+) -> io::Result<Vec<RgbaFrame>> {
+    (0..=game.move_log.len())
+        .map(|step| render_frame(&game.replay_to(step), res, opts))
+        .collect()
+}
+
+/// Write `frames` to `path` as a looping GIF, `fps` frames per second.
+/// Quantizing every frame is slow, so call this off the main thread.
+pub fn write_replay_gif(mut frames: Vec<RgbaFrame>, fps: u32, path: &Path) -> io::Result<()> {
+    let other = |e: gif::EncodingError| io::Error::new(io::ErrorKind::Other, e);
+    let delay = (100 / fps.max(1)).max(1) as u16;
+    // The first frame fixes the size of the image.
+    let Some(first) = frames.first() else {
+        return Ok(());
+    };
+    let file = BufWriter::new(File::create(path)?);
+    let mut encoder = gif::Encoder::new(file, first.width, first.height, &[]).map_err(other)?;
+    encoder.set_repeat(gif::Repeat::Infinite).map_err(other)?;
+    let last = frames.len() - 1;
+    for (step, frame) in frames.iter_mut().enumerate() {
+        let mut encoded =
+            gif::Frame::from_rgba_speed(frame.width, frame.height, &mut frame.rgba, QUANTIZE_SPEED);
+        encoded.delay = if step == last {
+            delay * FINAL_FRAME_HOLD
+        } else {
+            delay
+        };
+        encoder.write_frame(&encoded).map_err(other)?;
+    }
+    Ok(())
+}
+
+/// One rendered position as straight RGBA pixels.
+pub struct RgbaFrame {
+    width: u16,
+    height: u16,
+    rgba: Vec<u8>,
+}
+
+fn render_frame(
+    state: &GameState,
+    res: &GameResources,
+    opts: &ViewOptions,
+) -> io::Result<RgbaFrame> {
+    let mut surface = rendering::render_to_surface(state, res, opts, REPLAY_WIDTH)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "cannot render a frame"))?;
+    let (width, height, stride) = (
+        surface.width() as usize,
+        surface.height() as usize,
+        surface.stride() as usize,
+    );
+    let data = surface
+        .data()
+        .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+    Ok(RgbaFrame {
+        width: width as u16,
+        height: height as u16,
+        rgba: rendering::argb32_to_rgba(&data, width, height, stride),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_position_of_a_game_becomes_a_frame() {
+        let mut game = GameState::with_board_size(6);
+        for _ in 0..3 {
+            let (col, row) = game.compute_ai_move();
+            let player_one = game.player_one_to_move();
+            game.make_move(col, row, player_one);
+        }
+        // Shade each position by its towers, as a stand-in for rendering it.
+        let frames: Vec<RgbaFrame> = (0..=game.move_log.len())
+            .map(|step| {
+                let replay = game.replay_to(step);
+                let shade = (replay.tower_player * 8 + replay.tower_computer) as u8;
+                RgbaFrame {
+                    width: 4,
+                    height: 3,
+                    rgba: [shade, 0, 255 - shade, 255].repeat(4 * 3),
+                }
+            })
+            .collect();
+        let path =
+            std::env::temp_dir().join(format!("toweroops-replay-{}.gif", std::process::id()));
+        write_replay_gif(frames, 2, &path).unwrap();
+
+        let mut options = gif::DecodeOptions::new();
+        options.set_color_output(gif::ColorOutput::RGBA);
+        let mut decoder = options.read_info(File::open(&path).unwrap()).unwrap();
+        assert_eq!((decoder.width(), decoder.height()), (4, 3));
+        let mut delays = Vec::new();
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            delays.push(frame.delay);
+        }
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(delays.len(), game.move_log.len() + 1);
+        assert_eq!(delays[0], 50);
+        assert_eq!(delays.last(), Some(&(50 * FINAL_FRAME_HOLD)));
+    }
+}
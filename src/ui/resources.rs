@@ -1,7 +1,11 @@
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
 
 use gdk_pixbuf::Pixbuf;
+use gtk4::gio;
+
+use crate::game::types::{CellKind, ColorScheme};
 
 /// Represents a loaded image – either a raster Pixbuf or SVG tree.
 pub enum GameImage {
@@ -29,94 +33,217 @@ impl GameImage {
     }
 }
 
-/// All game images loaded from the resources directory.
+/// Path prefix of the assets in the bundled GResource (see `build.rs`).
+pub const RESOURCE_PREFIX: &str = "/io/github/laserlicht/TowerOops";
+
+/// Where image files are read from.
+enum Source<'a> {
+    Dir(&'a Path),
+    /// The GResource bundle compiled into the binary.
+    Bundle,
+}
+
+impl Source<'_> {
+    fn describe(&self, file: &str) -> String {
+        match self {
+            Source::Dir(dir) => dir.join(file).display().to_string(),
+            Source::Bundle => format!("{}/{}", RESOURCE_PREFIX, file),
+        }
+    }
+
+    /// Contents of `file`, or `None` if there is no such file.
+    fn read(&self, file: &str) -> Option<Vec<u8>> {
+        match self {
+            Source::Dir(dir) => std::fs::read(dir.join(file)).ok(),
+            Source::Bundle => gio::resources_lookup_data(
+                &format!("{}/{}", RESOURCE_PREFIX, file),
+                gio::ResourceLookupFlags::NONE,
+            )
+            .ok()
+            .map(|bytes| bytes.to_vec()),
+        }
+    }
+
+    fn pixbuf(&self, file: &str) -> Result<Pixbuf, gtk4::glib::Error> {
+        match self {
+            Source::Dir(dir) => Pixbuf::from_file(dir.join(file)),
+            Source::Bundle => Pixbuf::from_resource(&format!("{}/{}", RESOURCE_PREFIX, file)),
+        }
+    }
+}
+
+/// All game images loaded from the resources directory or the bundle.
 pub struct GameResources {
     images: HashMap<String, GameImage>,
-    #[allow(dead_code)]
-    pub res_dir: PathBuf,
+    /// `None` when the images came from the bundle.
+    pub res_dir: Option<PathBuf>,
+    /// Distinct for every load, so caches of rendered images can tell
+    /// resources apart; see [`GameResources::generation`].
+    generation: u64,
+}
+
+/// Source of [`GameResources::generation`].
+static NEXT_GENERATION: AtomicU64 = AtomicU64::new(1);
+
+/// How many of the loaded images are vector and how many raster images.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AssetSummary {
+    pub svg: usize,
+    pub raster: usize,
 }
 
 impl GameResources {
+    /// Images that may come in colour-scheme specific variants.
+    const THEMED: [&'static str; 5] = ["flag_blue", "flag_red", "won", "lost", "drawn"];
+
+    /// Images every theme must provide; see [`GameResources::missing`].
+    const REQUIRED: [&'static str; 26] = [
+        "background",
+        "grid",
+        "banana",
+        "1b",
+        "2b",
+        "3b",
+        "4b",
+        "1s",
+        "2s",
+        "3s",
+        "4s",
+        "horizontal",
+        "vertical",
+        "row1",
+        "row2",
+        "row_pre_last",
+        "row_last",
+        "won",
+        "lost",
+        "drawn",
+        "selected",
+        "tip",
+        "shadow",
+        "flag_blue",
+        "flag_red",
+        "icon",
+    ];
+
+    /// Tower row textures from the bottom rows up to the top one.
+    pub const TOWER_ROWS: [&'static str; 4] = ["row1", "row2", "row_pre_last", "row_last"];
+
     /// Load all needed images from the given directory.
     /// Automatically picks .svg if available, otherwise .png.
     pub fn load<P: AsRef<Path>>(dir: P) -> Self {
-        let dir = dir.as_ref().to_path_buf();
+        let dir = dir.as_ref();
+        Self {
+            images: Self::load_images(&Source::Dir(dir)),
+            res_dir: Some(dir.to_path_buf()),
+            generation: NEXT_GENERATION.fetch_add(1, Ordering::Relaxed),
+        }
+    }
+
+    /// Load all needed images from the GResource bundle, for installs without
+    /// a resources directory. Nothing is found unless the binary was built
+    /// with the `bundled-resources` feature.
+    pub fn load_from_gresource() -> Self {
+        Self {
+            images: Self::load_images(&Source::Bundle),
+            res_dir: None,
+            generation: NEXT_GENERATION.fetch_add(1, Ordering::Relaxed),
+        }
+    }
+
+    fn load_images(source: &Source) -> HashMap<String, GameImage> {
         let mut images = HashMap::new();
 
-        let files = [
-            "background",
-            "grid",
-            "banana",
-            "1b",
-            "2b",
-            "3b",
-            "4b",
-            "1s",
-            "2s",
-            "3s",
-            "4s",
-            "horizontal",
-            "vertical",
-            "row1",
-            "row2",
-            "row_pre_last",
-            "row_last",
-            "won",
-            "lost",
-            "drawn",
-            "selected",
-            "tip",
-            "shadow",
-            "flag_blue",
-            "flag_red",
-            "icon",
-        ];
-
-        for name in &files {
+        // Base piece textures for value-pip rendering; themes may leave them out.
+        let mut optional = vec!["stone".to_string(), "bomb".to_string()];
+        // Colour-blind variants of the coloured assets, used when present.
+        for suffix in ColorScheme::ALL.iter().filter_map(|s| s.asset_suffix()) {
+            for name in Self::THEMED {
+                optional.push(format!("{}_{}", name, suffix));
+            }
+        }
+
+        let files = Self::REQUIRED.iter().map(|n| n.to_string());
+        for name in files.chain(optional.iter().cloned()) {
             // Prefer SVG if it exists
-            let svg_path = dir.join(format!("{}.svg", name));
-            let png_path = dir.join(format!("{}.png", name));
+            let svg_file = format!("{}.svg", name);
+            let png_file = format!("{}.png", name);
 
-            if svg_path.exists() {
-                match Self::load_svg(&svg_path) {
+            if let Some(data) = source.read(&svg_file) {
+                match Self::load_svg(&data) {
                     Some(img) => {
-                        images.insert(name.to_string(), img);
+                        images.insert(name, img);
                         continue;
                     }
                     None => {
-                        eprintln!("Warning: could not load SVG {}", svg_path.display());
+                        eprintln!("Warning: could not load SVG {}", source.describe(&svg_file));
                     }
                 }
             }
 
-            match Pixbuf::from_file(&png_path) {
+            match source.pixbuf(&png_file) {
                 Ok(pb) => {
-                    images.insert(name.to_string(), GameImage::Raster(pb));
+                    images.insert(name, GameImage::Raster(pb));
                 }
                 Err(e) => {
-                    eprintln!("Warning: could not load {}: {}", png_path.display(), e);
+                    if !optional.contains(&name) {
+                        eprintln!(
+                            "Warning: could not load {}: {}",
+                            source.describe(&png_file),
+                            e
+                        );
+                    }
                 }
             }
         }
-
-        Self {
-            images,
-            res_dir: dir,
-        }
+        images
     }
 
-    fn load_svg(path: &Path) -> Option<GameImage> {
-        let data = std::fs::read(path).ok()?;
+    fn load_svg(data: &[u8]) -> Option<GameImage> {
         let opt = resvg::usvg::Options::default();
-        let tree = resvg::usvg::Tree::from_data(&data, &opt).ok()?;
+        let tree = resvg::usvg::Tree::from_data(data, &opt).ok()?;
         Some(GameImage::Svg { tree })
     }
 
+    /// Count the loaded images by format, for the info dialog.
+    pub fn asset_summary(&self) -> AssetSummary {
+        let mut summary = AssetSummary::default();
+        for image in self.images.values() {
+            match image {
+                GameImage::Svg { .. } => summary.svg += 1,
+                GameImage::Raster(_) => summary.raster += 1,
+            }
+        }
+        summary
+    }
+
+    /// Required images that could not be loaded, in load order.
+    pub fn missing(&self) -> Vec<String> {
+        Self::REQUIRED
+            .iter()
+            .filter(|name| !self.images.contains_key(**name))
+            .map(|name| name.to_string())
+            .collect()
+    }
+
+    /// Identifies this set of loaded images; no two loads share it.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
     /// Get an image by name (without extension).
     pub fn get(&self, name: &str) -> Option<&GameImage> {
         self.images.get(name)
     }
 
+    /// Get an image in the variant for `scheme`, falling back to the default one.
+    pub fn get_themed(&self, name: &str, scheme: ColorScheme) -> Option<&GameImage> {
+        scheme
+            .asset_suffix()
+            .and_then(|suffix| self.images.get(&format!("{}_{}", name, suffix)))
+            .or_else(|| self.images.get(name))
+    }
+
     /// Get bomb texture by value (0–3).
     pub fn bomb(&self, value: i32) -> Option<&GameImage> {
         let name = format!("{}b", value + 1);
@@ -129,15 +256,24 @@ impl GameResources {
         self.images.get(&name)
     }
 
+    /// Get the single base texture for a piece kind, used with value pips.
+    /// Falls back to the lowest-valued numbered texture when the theme has no base image.
+    pub fn base_piece(&self, kind: CellKind) -> Option<&GameImage> {
+        match kind {
+            CellKind::Stone => self.images.get("stone").or_else(|| self.stone(0)),
+            CellKind::Bomb => self.images.get("bomb").or_else(|| self.bomb(0)),
+            _ => None,
+        }
+    }
+
     /// Get tower row texture by index.
     pub fn tower_row(&self, idx: usize) -> Option<&GameImage> {
-        let names = ["row1", "row2", "row_pre_last", "row_last"];
-        names.get(idx).and_then(|n| self.images.get(*n))
+        Self::TOWER_ROWS.get(idx).and_then(|n| self.images.get(*n))
     }
 
     /// Get win/loss/draw overlay (0=won, 1=lost, 2=drawn).
-    pub fn outcome_overlay(&self, idx: usize) -> Option<&GameImage> {
+    pub fn outcome_overlay(&self, idx: usize, scheme: ColorScheme) -> Option<&GameImage> {
         let names = ["won", "lost", "drawn"];
-        names.get(idx).and_then(|n| self.images.get(*n))
+        names.get(idx).and_then(|n| self.get_themed(n, scheme))
     }
 }